@@ -59,6 +59,9 @@ pub async fn run(execution: Execution) -> anyhow::Result<()> {
     Execution::PullDeployment(data) => {
       println!("{}: {data:?}", "Data".dimmed())
     }
+    Execution::RollbackDeployment(data) => {
+      println!("{}: {data:?}", "Data".dimmed())
+    }
     Execution::StartDeployment(data) => {
       println!("{}: {data:?}", "Data".dimmed())
     }
@@ -258,6 +261,10 @@ pub async fn run(execution: Execution) -> anyhow::Result<()> {
       .execute(request)
       .await
       .map(ExecutionResult::Single),
+    Execution::RollbackDeployment(request) => komodo_client()
+      .execute(request)
+      .await
+      .map(ExecutionResult::Single),
     Execution::StartDeployment(request) => komodo_client()
       .execute(request)
       .await
@@ -0,0 +1,105 @@
+use handlebars::{handlebars_helper, no_escape, Handlebars};
+use reqwest::{
+  header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE},
+  Method,
+};
+use serde_json::Value;
+
+use super::*;
+
+#[instrument(level = "debug", skip(endpoint), fields(url = endpoint.url))]
+pub async fn send_alert(
+  endpoint: &CustomAlerterEndpoint,
+  alert: &Alert,
+) -> anyhow::Result<()> {
+  let method = match endpoint.method {
+    CustomAlerterMethod::Post => Method::POST,
+    CustomAlerterMethod::Put => Method::PUT,
+    CustomAlerterMethod::Patch => Method::PATCH,
+  };
+  let headers = interpolate_credential(
+    &endpoint.headers,
+    "Custom alerter headers",
+  )
+  .await?;
+  let headers = parse_headers(&headers)?;
+
+  let mut req = http_client()
+    .request(method, &endpoint.url)
+    .headers(headers.clone());
+
+  req = if endpoint.body.trim().is_empty() {
+    req.json(alert)
+  } else {
+    let template =
+      interpolate_credential(&endpoint.body, "Custom alerter body")
+        .await?;
+    let body = render_body(&template, alert)
+      .context("Failed to render body template")?;
+    if !headers.contains_key(CONTENT_TYPE) {
+      req = req.header(CONTENT_TYPE, "application/json");
+    }
+    req.body(body)
+  };
+
+  let response =
+    req.send().await.context("failed at request to alerter")?;
+  check_response(response)
+    .await
+    .context("request to alerter failed")
+}
+
+/// Parses `Name: Value` lines into headers.
+/// Empty lines and lines starting with `#` are skipped.
+fn parse_headers(headers: &str) -> anyhow::Result<HeaderMap> {
+  let mut map = HeaderMap::new();
+  for line in headers
+    .lines()
+    .map(str::trim)
+    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+  {
+    let (name, value) = line.split_once(':').with_context(|| {
+      format!("Invalid header line, expected 'Name: Value': {line}")
+    })?;
+    let name = HeaderName::try_from(name.trim())
+      .with_context(|| format!("Invalid header name: {name}"))?;
+    let value = HeaderValue::try_from(value.trim())
+      .with_context(|| format!("Invalid value for header {name}"))?;
+    map.append(name, value);
+  }
+  Ok(map)
+}
+
+handlebars_helper!(json: |value: Value| value.to_string());
+
+fn render_body(
+  template: &str,
+  alert: &Alert,
+) -> anyhow::Result<String> {
+  let mut handlebars = Handlebars::new();
+  // Bodies are usually JSON, not HTML.
+  handlebars.register_escape_fn(no_escape);
+  handlebars.register_helper("json", Box::new(json));
+
+  let mut context = serde_json::to_value(alert)
+    .context("Failed to serialize alert")?;
+  let (title, message) =
+    standard_alert_content(alert).unwrap_or_default();
+  if let Value::Object(context) = &mut context {
+    context.insert("id".into(), alert.id.clone().into());
+    context.insert("severity".into(), alert.level.to_string().into());
+    context.insert(
+      "target_name".into(),
+      alert_target_name(alert).unwrap_or_default().into(),
+    );
+    context.insert("title".into(), title.into());
+    context.insert("message".into(), message.into());
+    context.insert(
+      "link".into(),
+      alert_link(alert).unwrap_or_default().into(),
+    );
+  }
+
+  let body = handlebars.render_template(template, &context)?;
+  Ok(body)
+}
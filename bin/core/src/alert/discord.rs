@@ -1,5 +1,3 @@
-use std::sync::OnceLock;
-
 use serde::Serialize;
 
 use super::*;
@@ -49,6 +47,32 @@ pub async fn send_alert(
         "{level} | **{name}**{region} cpu usage at **{percentage:.1}%**\n{link}"
       )
     }
+    AlertData::ServerNetwork {
+      id,
+      name,
+      region,
+      interface,
+      rx_mbps,
+      tx_mbps,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      format!(
+        "{level} | **{name}**{region} network usage on **{interface}** at **{rx_mbps:.1} Mbps** in / **{tx_mbps:.1} Mbps** out 📶\n{link}"
+      )
+    }
+    AlertData::ServerLoad {
+      id,
+      name,
+      region,
+      load_average,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      format!(
+        "{level} | **{name}**{region} 5 minute load average at **{load_average:.2}**\n{link}"
+      )
+    }
     AlertData::ServerMem {
       id,
       name,
@@ -123,6 +147,33 @@ pub async fn send_alert(
       let link = resource_link(ResourceTargetVariant::Stack, id);
       format!("⬆ Stack **{name}** has an update available\nserver: **{server_name}**\nservice: **{service}**\nimage: **{image}**\n{link}")
     }
+    AlertData::ContainerUnhealthy {
+      name,
+      server_name,
+      container,
+      ..
+    } => {
+      let link = alert_link(alert).unwrap_or_default();
+      let kind = alert.target.extract_variant();
+      match alert.level {
+        SeverityLevel::Ok => format!("{level} | {kind:?} **{name}** container **{container}** is no longer unhealthy\nserver: **{server_name}**\n{link}"),
+        _ => format!("{level} | {kind:?} **{name}** container **{container}** is **unhealthy** 🩺\nserver: **{server_name}**\n{link}"),
+      }
+    }
+    AlertData::ContainerRestartLoop {
+      name,
+      server_name,
+      container,
+      restarts,
+      ..
+    } => {
+      let link = alert_link(alert).unwrap_or_default();
+      let kind = alert.target.extract_variant();
+      match alert.level {
+        SeverityLevel::Ok => format!("{level} | {kind:?} **{name}** container **{container}** is no longer restarting\nserver: **{server_name}**\n{link}"),
+        _ => format!("{level} | {kind:?} **{name}** container **{container}** is in a **restart loop** 🔁\nrestarted **{restarts}** times in the last 5 minutes\nserver: **{server_name}**\n{link}"),
+      }
+    }
     AlertData::AwsBuilderTerminationFailed {
       instance_id,
       message,
@@ -144,6 +195,29 @@ pub async fn send_alert(
       let link = resource_link(ResourceTargetVariant::Repo, id);
       format!("{level} | Repo build for **{name}** failed\n{link}")
     }
+    AlertData::ProcedureFailed {
+      id,
+      name,
+      stage,
+      error,
+    } => {
+      let link = resource_link(ResourceTargetVariant::Procedure, id);
+      format!("{level} | Procedure **{name}** failed\nstage: **{stage}**\n```\n{error}\n```\n{link}")
+    }
+    AlertData::ActionFailed { id, name, error } => {
+      let link = resource_link(ResourceTargetVariant::Action, id);
+      format!("{level} | Action **{name}** failed\n```\n{error}\n```\n{link}")
+    }
+    AlertData::ResourceSyncFailed {
+      id,
+      name,
+      stage,
+      error,
+    } => {
+      let link =
+        resource_link(ResourceTargetVariant::ResourceSync, id);
+      format!("{level} | Resource sync **{name}** failed\nstage: **{stage}**\n```\n{error}\n```\n{link}")
+    }
     AlertData::None {} => Default::default(),
   };
   if !content.is_empty() {
@@ -179,11 +253,6 @@ async fn send_message(
   }
 }
 
-fn http_client() -> &'static reqwest::Client {
-  static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
-  CLIENT.get_or_init(reqwest::Client::new)
-}
-
 #[derive(Serialize)]
 struct DiscordMessageBody<'a> {
   content: &'a str,
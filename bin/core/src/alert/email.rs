@@ -0,0 +1,90 @@
+use lettre::{
+  message::header::ContentType,
+  transport::smtp::authentication::Credentials, AsyncSmtpTransport,
+  AsyncTransport, Message, Tokio1Executor,
+};
+
+use super::*;
+
+#[instrument(level = "debug", skip(endpoint), fields(host = endpoint.host))]
+pub async fn send_alert(
+  endpoint: &EmailAlerterEndpoint,
+  alert: &Alert,
+) -> anyhow::Result<()> {
+  let Some((subject, body)) = standard_alert_content(alert) else {
+    return Ok(());
+  };
+  send_email(endpoint, format!("[Komodo] {subject}"), body).await
+}
+
+async fn send_email(
+  EmailAlerterEndpoint {
+    host,
+    port,
+    security,
+    username,
+    password,
+    from,
+    to,
+  }: &EmailAlerterEndpoint,
+  subject: String,
+  body: String,
+) -> anyhow::Result<()> {
+  let to = to
+    .iter()
+    .map(|to| to.trim())
+    .filter(|to| !to.is_empty())
+    .collect::<Vec<_>>();
+  if to.is_empty() {
+    return Err(anyhow!(
+      "Email alerter has no recipients configured"
+    ));
+  }
+
+  let mut message = Message::builder()
+    .from(
+      from
+        .parse()
+        .with_context(|| format!("Invalid 'from' address: {from}"))?,
+    )
+    .subject(subject)
+    .header(ContentType::TEXT_PLAIN);
+  for to in to {
+    message = message.to(
+      to.parse()
+        .with_context(|| format!("Invalid 'to' address: {to}"))?,
+    );
+  }
+  let message = message
+    .body(body)
+    .context("Failed to build email message")?;
+
+  let mut transport = match security {
+    SmtpSecurity::None => {
+      AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host)
+    }
+    SmtpSecurity::StartTls => {
+      AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)
+        .context("Failed to initialize STARTTLS SMTP transport")?
+    }
+    SmtpSecurity::Tls => {
+      AsyncSmtpTransport::<Tokio1Executor>::relay(host)
+        .context("Failed to initialize TLS SMTP transport")?
+    }
+  }
+  .port(*port);
+
+  if !username.is_empty() {
+    transport = transport.credentials(Credentials::new(
+      username.clone(),
+      interpolate_credential(password, "Email alerter password")
+        .await?,
+    ));
+  }
+
+  transport.build().send(message).await.with_context(|| {
+    format!("Failed to send email via {host}:{port}")
+  })?;
+
+  Ok(())
+}
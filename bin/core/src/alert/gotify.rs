@@ -0,0 +1,60 @@
+use serde::Serialize;
+use serde_json::json;
+
+use super::*;
+
+#[instrument(level = "debug", skip(endpoint), fields(url = endpoint.url))]
+pub async fn send_alert(
+  endpoint: &GotifyAlerterEndpoint,
+  alert: &Alert,
+) -> anyhow::Result<()> {
+  let Some((title, message)) = standard_alert_content(alert) else {
+    return Ok(());
+  };
+  let extras = alert_link(alert).map(|url| {
+    json!({
+      "client::notification": {
+        "click": { "url": url }
+      }
+    })
+  });
+  let body = GotifyMessage {
+    title: &title,
+    message: &message,
+    priority: gotify_priority(alert.level),
+    extras,
+  };
+  let token =
+    interpolate_credential(&endpoint.token, "Gotify alerter token")
+      .await?;
+  let response = http_client()
+    .post(format!("{}/message", endpoint.url.trim_end_matches('/')))
+    .header("X-Gotify-Key", token)
+    .json(&body)
+    .send()
+    .await
+    .context("Failed to send message to Gotify")?;
+  check_response(response)
+    .await
+    .context("Failed to send message to Gotify")
+}
+
+/// Gotify priorities range from 0 to 10.
+/// Android clients only make a sound for 4 and above,
+/// and show a popup for 8 and above.
+fn gotify_priority(level: SeverityLevel) -> u8 {
+  match level {
+    SeverityLevel::Ok => 2,
+    SeverityLevel::Warning => 5,
+    SeverityLevel::Critical => 8,
+  }
+}
+
+#[derive(Serialize)]
+struct GotifyMessage<'a> {
+  title: &'a str,
+  message: &'a str,
+  priority: u8,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  extras: Option<serde_json::Value>,
+}
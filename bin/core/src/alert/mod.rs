@@ -1,3 +1,5 @@
+use std::sync::OnceLock;
+
 use ::slack::types::Block;
 use anyhow::{anyhow, Context};
 use derive_variants::ExtractVariant;
@@ -7,16 +9,32 @@ use komodo_client::entities::{
   alerter::*,
   deployment::DeploymentState,
   stack::StackState,
+  update::Update,
   ResourceTargetVariant,
 };
 use mungos::{find::find_collect, mongodb::bson::doc};
 use tracing::Instrument;
 
-use crate::{config::core_config, state::db_client};
+use crate::{
+  config::core_config,
+  helpers::{
+    interpolate::interpolate_variables_secrets_into_string,
+    query::get_variables_and_secrets,
+  },
+  state::db_client,
+};
 
+mod custom;
 mod discord;
+mod email;
+mod gotify;
+mod ntfy;
+mod pushover;
+mod silence;
 mod slack;
 
+pub use silence::apply_silences;
+
 pub async fn send_alerts(alerts: &[Alert]) {
   if alerts.is_empty() {
     return;
@@ -39,15 +57,39 @@ pub async fn send_alerts(alerts: &[Alert]) {
       return;
     };
 
-    let handles =
-      alerts.iter().map(|alert| send_alert(&alerters, alert));
-
-    join_all(handles).await;
+    send_alerts_to_alerters(&alerters, alerts).await
   }
   .instrument(span)
   .await
 }
 
+/// Sends the alerts to the given alerters,
+/// skipping any which are silenced.
+pub async fn send_alerts_to_alerters(
+  alerters: &[Alerter],
+  alerts: &[Alert],
+) {
+  if alerters.is_empty() || alerts.is_empty() {
+    return;
+  }
+
+  let silences = silence::active_silences()
+    .await
+    .inspect_err(|e| warn!("{e:#}"))
+    .unwrap_or_default();
+
+  let handles = alerts.iter().map(|alert| async {
+    // Don't send alerts silenced right now. The stored `silenced` flag
+    // reflects when the alert was last updated, and is not a permanent mute.
+    if silence::is_silenced(&silences, alert).await {
+      return;
+    }
+    send_alert(alerters, alert).await
+  });
+
+  join_all(handles).await;
+}
+
 #[instrument(level = "debug")]
 async fn send_alert(alerters: &[Alerter], alert: &Alert) {
   if alerters.is_empty() {
@@ -82,8 +124,8 @@ async fn send_alert(alerters: &[Alerter], alert: &Alert) {
     }
 
     match &alerter.config.endpoint {
-      AlerterEndpoint::Custom(CustomAlerterEndpoint { url }) => {
-        send_custom_alert(url, alert).await.with_context(|| {
+      AlerterEndpoint::Custom(endpoint) => {
+        custom::send_alert(endpoint, alert).await.with_context(|| {
           format!(
             "failed to send alert to custom alerter {}",
             alerter.name
@@ -106,6 +148,40 @@ async fn send_alert(alerters: &[Alerter], alert: &Alert) {
           )
         })
       }
+      AlerterEndpoint::Email(endpoint) => {
+        email::send_alert(endpoint, alert).await.with_context(|| {
+          format!(
+            "failed to send alert to Email alerter {}",
+            alerter.name
+          )
+        })
+      }
+      AlerterEndpoint::Ntfy(endpoint) => {
+        ntfy::send_alert(endpoint, alert).await.with_context(|| {
+          format!(
+            "failed to send alert to ntfy alerter {}",
+            alerter.name
+          )
+        })
+      }
+      AlerterEndpoint::Gotify(endpoint) => {
+        gotify::send_alert(endpoint, alert).await.with_context(|| {
+          format!(
+            "failed to send alert to Gotify alerter {}",
+            alerter.name
+          )
+        })
+      }
+      AlerterEndpoint::Pushover(endpoint) => {
+        pushover::send_alert(endpoint, alert).await.with_context(
+          || {
+            format!(
+              "failed to send alert to Pushover alerter {}",
+              alerter.name
+            )
+          },
+        )
+      }
     }
   });
 
@@ -116,28 +192,394 @@ async fn send_alert(alerters: &[Alerter], alert: &Alert) {
     .for_each(|e| error!("{e:#}"));
 }
 
-#[instrument(level = "debug")]
-async fn send_custom_alert(
-  url: &str,
-  alert: &Alert,
+/// Renders the alert into a plain text (title, body),
+/// for use with endpoints that don't support rich formatting.
+/// Returns None if there is nothing to send.
+fn standard_alert_content(alert: &Alert) -> Option<(String, String)> {
+  let level = alert.level.to_string();
+  let (subject, body) = match &alert.data {
+    AlertData::ServerUnreachable {
+      id,
+      name,
+      region,
+      err,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      match alert.level {
+        SeverityLevel::Ok => (
+          format!("{level} | {name}{region} is now reachable"),
+          format!("Server {name}{region} is now reachable.\n\n{link}"),
+        ),
+        SeverityLevel::Critical => {
+          let err = err
+            .as_ref()
+            .map(|e| format!("\n\nerror: {e:#?}"))
+            .unwrap_or_default();
+          (
+            format!("{level} | {name}{region} is unreachable"),
+            format!(
+              "Server {name}{region} is unreachable.{err}\n\n{link}"
+            ),
+          )
+        }
+        _ => unreachable!(),
+      }
+    }
+    AlertData::ServerCpu {
+      id,
+      name,
+      region,
+      percentage,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      (
+        format!(
+          "{level} | {name}{region} cpu usage at {percentage:.1}%"
+        ),
+        format!(
+          "Server {name}{region} cpu usage is at {percentage:.1}%.\n\n{link}"
+        ),
+      )
+    }
+    AlertData::ServerNetwork {
+      id,
+      name,
+      region,
+      interface,
+      rx_mbps,
+      tx_mbps,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      (
+        format!(
+          "{level} | {name}{region} network usage on {interface} at {rx_mbps:.1} / {tx_mbps:.1} Mbps"
+        ),
+        format!(
+          "Server {name}{region} network usage on {interface} is at {rx_mbps:.1} Mbps received, {tx_mbps:.1} Mbps transmitted.\n\n{link}"
+        ),
+      )
+    }
+    AlertData::ServerLoad {
+      id,
+      name,
+      region,
+      load_average,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      (
+        format!(
+          "{level} | {name}{region} load average at {load_average:.2}"
+        ),
+        format!(
+          "Server {name}{region} 5 minute load average is at {load_average:.2}.\n\n{link}"
+        ),
+      )
+    }
+    AlertData::ServerMem {
+      id,
+      name,
+      region,
+      used_gb,
+      total_gb,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      let percentage = 100.0 * used_gb / total_gb;
+      (
+        format!(
+          "{level} | {name}{region} memory usage at {percentage:.1}%"
+        ),
+        format!(
+          "Server {name}{region} memory usage is at {percentage:.1}%.\nusing: {used_gb:.1} GiB / {total_gb:.1} GiB\n\n{link}"
+        ),
+      )
+    }
+    AlertData::ServerDisk {
+      id,
+      name,
+      region,
+      path,
+      used_gb,
+      total_gb,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      let percentage = 100.0 * used_gb / total_gb;
+      (
+        format!(
+          "{level} | {name}{region} disk usage at {percentage:.1}%"
+        ),
+        format!(
+          "Server {name}{region} disk usage is at {percentage:.1}%.\nmount point: {path:?}\nusing: {used_gb:.1} GiB / {total_gb:.1} GiB\n\n{link}"
+        ),
+      )
+    }
+    AlertData::ContainerStateChange {
+      id,
+      name,
+      server_id: _server_id,
+      server_name,
+      from,
+      to,
+    } => {
+      let link = resource_link(ResourceTargetVariant::Deployment, id);
+      (
+        format!("{level} | Deployment {name} is now {to}"),
+        format!(
+          "Deployment {name} is now {to}.\nserver: {server_name}\nprevious: {from}\n\n{link}"
+        ),
+      )
+    }
+    AlertData::DeploymentImageUpdateAvailable {
+      id,
+      name,
+      server_id: _server_id,
+      server_name,
+      image,
+    } => {
+      let link = resource_link(ResourceTargetVariant::Deployment, id);
+      (
+        format!("{level} | Deployment {name} has an update available"),
+        format!(
+          "Deployment {name} has an update available.\nserver: {server_name}\nimage: {image}\n\n{link}"
+        ),
+      )
+    }
+    AlertData::StackStateChange {
+      id,
+      name,
+      server_id: _server_id,
+      server_name,
+      from,
+      to,
+    } => {
+      let link = resource_link(ResourceTargetVariant::Stack, id);
+      (
+        format!("{level} | Stack {name} is now {to}"),
+        format!(
+          "Stack {name} is now {to}.\nserver: {server_name}\nprevious: {from}\n\n{link}"
+        ),
+      )
+    }
+    AlertData::StackImageUpdateAvailable {
+      id,
+      name,
+      server_id: _server_id,
+      server_name,
+      service,
+      image,
+    } => {
+      let link = resource_link(ResourceTargetVariant::Stack, id);
+      (
+        format!("{level} | Stack {name} has an update available"),
+        format!(
+          "Stack {name} has an update available.\nserver: {server_name}\nservice: {service}\nimage: {image}\n\n{link}"
+        ),
+      )
+    }
+    AlertData::ContainerUnhealthy {
+      name,
+      server_name,
+      container,
+      ..
+    } => {
+      let kind = alert.target.extract_variant();
+      let link = alert_link(alert).unwrap_or_default();
+      match alert.level {
+        SeverityLevel::Ok => (
+          format!("{level} | {kind:?} {name} container {container} is no longer unhealthy"),
+          format!(
+            "{kind:?} {name} container {container} is no longer unhealthy.\nserver: {server_name}\n\n{link}"
+          ),
+        ),
+        _ => (
+          format!("{level} | {kind:?} {name} container {container} is unhealthy"),
+          format!(
+            "{kind:?} {name} container {container} is reporting unhealthy.\nserver: {server_name}\n\n{link}"
+          ),
+        ),
+      }
+    }
+    AlertData::ContainerRestartLoop {
+      name,
+      server_name,
+      container,
+      restarts,
+      ..
+    } => {
+      let kind = alert.target.extract_variant();
+      let link = alert_link(alert).unwrap_or_default();
+      match alert.level {
+        SeverityLevel::Ok => (
+          format!("{level} | {kind:?} {name} container {container} is no longer restarting"),
+          format!(
+            "{kind:?} {name} container {container} is no longer restarting.\nserver: {server_name}\n\n{link}"
+          ),
+        ),
+        _ => (
+          format!("{level} | {kind:?} {name} container {container} is in a restart loop"),
+          format!(
+            "{kind:?} {name} container {container} restarted {restarts} times in the last 5 minutes.\nserver: {server_name}\n\n{link}"
+          ),
+        ),
+      }
+    }
+    AlertData::AwsBuilderTerminationFailed {
+      instance_id,
+      message,
+    } => (
+      format!("{level} | Failed to terminate AWS builder instance"),
+      format!(
+        "Failed to terminate AWS builder instance.\ninstance id: {instance_id}\n\n{message}"
+      ),
+    ),
+    AlertData::ResourceSyncPendingUpdates { id, name } => {
+      let link =
+        resource_link(ResourceTargetVariant::ResourceSync, id);
+      (
+        format!("{level} | Pending resource sync updates on {name}"),
+        format!(
+          "Resource sync {name} has pending updates.\n\n{link}"
+        ),
+      )
+    }
+    AlertData::BuildFailed { id, name, version } => {
+      let link = resource_link(ResourceTargetVariant::Build, id);
+      (
+        format!("{level} | Build {name} failed"),
+        format!(
+          "Build {name} has failed.\nversion: v{version}\n\n{link}"
+        ),
+      )
+    }
+    AlertData::RepoBuildFailed { id, name } => {
+      let link = resource_link(ResourceTargetVariant::Repo, id);
+      (
+        format!("{level} | Repo build for {name} failed"),
+        format!("Repo build for {name} has failed.\n\n{link}"),
+      )
+    }
+    AlertData::ProcedureFailed {
+      id,
+      name,
+      stage,
+      error,
+    } => {
+      let link = resource_link(ResourceTargetVariant::Procedure, id);
+      (
+        format!("{level} | Procedure {name} failed"),
+        format!(
+          "Procedure {name} has failed.\nstage: {stage}\n\n{error}\n\n{link}"
+        ),
+      )
+    }
+    AlertData::ActionFailed { id, name, error } => {
+      let link = resource_link(ResourceTargetVariant::Action, id);
+      (
+        format!("{level} | Action {name} failed"),
+        format!("Action {name} has failed.\n\n{error}\n\n{link}"),
+      )
+    }
+    AlertData::ResourceSyncFailed {
+      id,
+      name,
+      stage,
+      error,
+    } => {
+      let link =
+        resource_link(ResourceTargetVariant::ResourceSync, id);
+      (
+        format!("{level} | Resource sync {name} failed"),
+        format!(
+          "Resource sync {name} has failed.\nstage: {stage}\n\n{error}\n\n{link}"
+        ),
+      )
+    }
+    AlertData::None {} => return None,
+  };
+  Some((subject, body))
+}
+
+/// Max length of the error excerpt included in failure alerts.
+const ERROR_EXCERPT_LENGTH: usize = 500;
+
+/// Gets the stage of the first failed log on the update,
+/// along with an excerpt of its output for failure alerts.
+pub fn failed_log_excerpt(
+  update: &Update,
+) -> Option<(String, String)> {
+  let log = update.logs.iter().find(|log| !log.success)?;
+  let output = if log.stderr.trim().is_empty() {
+    &log.stdout
+  } else {
+    &log.stderr
+  };
+  Some((log.stage.clone(), error_excerpt(output)))
+}
+
+/// Strips the html formatting from log output,
+/// and truncates it to [ERROR_EXCERPT_LENGTH] characters.
+pub fn error_excerpt(output: &str) -> String {
+  let mut excerpt = String::with_capacity(output.len());
+  let mut rest = output;
+  while let Some(start) = rest.find("<span") {
+    excerpt.push_str(&rest[..start]);
+    let Some(end) = rest[start..].find('>') else {
+      break;
+    };
+    rest = &rest[start + end + 1..];
+  }
+  excerpt.push_str(rest);
+  let excerpt = excerpt.replace("</span>", "");
+  let excerpt = excerpt.trim();
+  match excerpt.char_indices().nth(ERROR_EXCERPT_LENGTH) {
+    Some((i, _)) => format!("{}...", &excerpt[..i]),
+    None => excerpt.to_string(),
+  }
+}
+
+/// Alerter credentials reference core secrets / variables,
+/// which are interpolated in only when sending.
+async fn interpolate_credential(
+  value: &str,
+  what: &str,
+) -> anyhow::Result<String> {
+  let mut value = value.to_string();
+  if !value.contains("[[") {
+    return Ok(value);
+  }
+  interpolate_variables_secrets_into_string(
+    &get_variables_and_secrets().await?,
+    &mut value,
+    &mut Default::default(),
+    &mut Default::default(),
+  )
+  .with_context(|| format!("Failed to interpolate {what}"))?;
+  Ok(value)
+}
+
+fn http_client() -> &'static reqwest::Client {
+  static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+  CLIENT.get_or_init(reqwest::Client::new)
+}
+
+/// Returns an error including the response body
+/// if the response status is not successful.
+async fn check_response(
+  response: reqwest::Response,
 ) -> anyhow::Result<()> {
-  let res = reqwest::Client::new()
-    .post(url)
-    .json(alert)
-    .send()
-    .await
-    .context("failed at post request to alerter")?;
-  let status = res.status();
-  if !status.is_success() {
-    let text = res
-      .text()
-      .await
-      .context("failed to get response text on alerter response")?;
-    return Err(anyhow!(
-      "post to alerter failed | {status} | {text}"
-    ));
+  let status = response.status();
+  if status.is_success() {
+    return Ok(());
   }
-  Ok(())
+  let text = response.text().await.with_context(|| {
+    format!("{status} | failed to get response text")
+  })?;
+  Err(anyhow!("{status} | {text}"))
 }
 
 fn fmt_region(region: &Option<String>) -> String {
@@ -175,6 +617,42 @@ fn fmt_level(level: SeverityLevel) -> &'static str {
   }
 }
 
+/// The name of the alerting resource, if the alert has one.
+fn alert_target_name(alert: &Alert) -> Option<&str> {
+  match &alert.data {
+    AlertData::None {} => None,
+    AlertData::AwsBuilderTerminationFailed {
+      instance_id, ..
+    } => Some(instance_id),
+    AlertData::ServerUnreachable { name, .. }
+    | AlertData::ServerCpu { name, .. }
+    | AlertData::ServerMem { name, .. }
+    | AlertData::ServerDisk { name, .. }
+    | AlertData::ServerNetwork { name, .. }
+    | AlertData::ServerLoad { name, .. }
+    | AlertData::ContainerStateChange { name, .. }
+    | AlertData::DeploymentImageUpdateAvailable { name, .. }
+    | AlertData::StackStateChange { name, .. }
+    | AlertData::StackImageUpdateAvailable { name, .. }
+    | AlertData::ContainerUnhealthy { name, .. }
+    | AlertData::ContainerRestartLoop { name, .. }
+    | AlertData::ResourceSyncPendingUpdates { name, .. }
+    | AlertData::BuildFailed { name, .. }
+    | AlertData::RepoBuildFailed { name, .. }
+    | AlertData::ProcedureFailed { name, .. }
+    | AlertData::ActionFailed { name, .. }
+    | AlertData::ResourceSyncFailed { name, .. } => Some(name),
+  }
+}
+
+/// The link to the alert target in the Komodo UI, if it has one.
+fn alert_link(alert: &Alert) -> Option<String> {
+  match alert.target.extract_variant_id() {
+    (ResourceTargetVariant::System, _) => None,
+    (variant, id) => resource_link(variant, id).into(),
+  }
+}
+
 fn resource_link(
   resource_type: ResourceTargetVariant,
   id: &str,
@@ -0,0 +1,66 @@
+use serde::Serialize;
+
+use super::*;
+
+#[instrument(level = "debug", skip(endpoint), fields(url = endpoint.url, topic = endpoint.topic))]
+pub async fn send_alert(
+  endpoint: &NtfyAlerterEndpoint,
+  alert: &Alert,
+) -> anyhow::Result<()> {
+  let Some((title, message)) = standard_alert_content(alert) else {
+    return Ok(());
+  };
+  let link = alert_link(alert);
+  let body = NtfyMessage {
+    topic: &endpoint.topic,
+    title: &title,
+    message: &message,
+    priority: ntfy_priority(alert.level),
+    tags: [ntfy_tag(alert.level)],
+    click: link.as_deref(),
+  };
+  let mut req = http_client()
+    // Publishing as JSON is done to the root url
+    .post(endpoint.url.trim_end_matches('/'))
+    .json(&body);
+  if !endpoint.token.is_empty() {
+    let token =
+      interpolate_credential(&endpoint.token, "ntfy alerter token")
+        .await?;
+    req = req.bearer_auth(token);
+  }
+  let response =
+    req.send().await.context("Failed to send message to ntfy")?;
+  check_response(response)
+    .await
+    .context("Failed to send message to ntfy")
+}
+
+/// ntfy priorities range from 1 (min) to 5 (max), with 3 the default.
+fn ntfy_priority(level: SeverityLevel) -> u8 {
+  match level {
+    SeverityLevel::Ok => 3,
+    SeverityLevel::Warning => 4,
+    SeverityLevel::Critical => 5,
+  }
+}
+
+/// Tags matching an emoji shortcode are displayed as the emoji.
+fn ntfy_tag(level: SeverityLevel) -> &'static str {
+  match level {
+    SeverityLevel::Ok => "white_check_mark",
+    SeverityLevel::Warning => "warning",
+    SeverityLevel::Critical => "rotating_light",
+  }
+}
+
+#[derive(Serialize)]
+struct NtfyMessage<'a> {
+  topic: &'a str,
+  title: &'a str,
+  message: &'a str,
+  priority: u8,
+  tags: [&'a str; 1],
+  #[serde(skip_serializing_if = "Option::is_none")]
+  click: Option<&'a str>,
+}
@@ -0,0 +1,65 @@
+use serde::Serialize;
+
+use super::*;
+
+const PUSHOVER_MESSAGES_URL: &str =
+  "https://api.pushover.net/1/messages.json";
+
+#[instrument(level = "debug", skip(endpoint))]
+pub async fn send_alert(
+  endpoint: &PushoverAlerterEndpoint,
+  alert: &Alert,
+) -> anyhow::Result<()> {
+  let Some((title, message)) = standard_alert_content(alert) else {
+    return Ok(());
+  };
+  let token =
+    interpolate_credential(&endpoint.token, "Pushover alerter token")
+      .await?;
+  let link = alert_link(alert);
+  let body = PushoverMessage {
+    token: &token,
+    user: &endpoint.user,
+    device: (!endpoint.device.is_empty())
+      .then_some(endpoint.device.as_str()),
+    title: &title,
+    message: &message,
+    priority: pushover_priority(alert.level),
+    url: link.as_deref(),
+    url_title: link.as_ref().map(|_| "Open in Komodo"),
+  };
+  let response = http_client()
+    .post(PUSHOVER_MESSAGES_URL)
+    .json(&body)
+    .send()
+    .await
+    .context("Failed to send message to Pushover")?;
+  check_response(response)
+    .await
+    .context("Failed to send message to Pushover")
+}
+
+/// Pushover priorities range from -2 (lowest) to 2 (emergency).
+/// Emergency requires retry / expire parameters, so Critical maps to high (1).
+fn pushover_priority(level: SeverityLevel) -> i8 {
+  match level {
+    SeverityLevel::Ok => -1,
+    SeverityLevel::Warning => 0,
+    SeverityLevel::Critical => 1,
+  }
+}
+
+#[derive(Serialize)]
+struct PushoverMessage<'a> {
+  token: &'a str,
+  user: &'a str,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  device: Option<&'a str>,
+  title: &'a str,
+  message: &'a str,
+  priority: i8,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  url: Option<&'a str>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  url_title: Option<&'a str>,
+}
@@ -0,0 +1,156 @@
+use anyhow::Context;
+use komodo_client::entities::{
+  action::Action, alert_silence::AlertSilence, build::Build,
+  builder::Builder, deployment::Deployment, komodo_timestamp,
+  procedure::Procedure, repo::Repo, server::Server,
+  server_template::ServerTemplate, stack::Stack, sync::ResourceSync,
+  ResourceTarget,
+};
+use mungos::find::find_collect;
+
+use crate::resource::{self, KomodoResource};
+
+use super::*;
+
+/// Marks whether the alerts match an active [AlertSilence].
+/// Call before the alerts are stored, so they are recorded as silenced.
+/// This is re-evaluated on every update, so an alert opened during a
+/// silence is no longer marked silenced once the silence ends.
+pub async fn apply_silences(alerts: Vec<&mut Alert>) {
+  if alerts.is_empty() {
+    return;
+  }
+  let silences = match active_silences().await {
+    Ok(silences) => silences,
+    Err(e) => {
+      warn!("{e:#}");
+      return;
+    }
+  };
+  for alert in alerts {
+    alert.silenced = is_silenced(&silences, alert).await;
+  }
+}
+
+pub async fn active_silences() -> anyhow::Result<Vec<AlertSilence>> {
+  let now = komodo_timestamp();
+  find_collect(
+    &db_client().alert_silences,
+    doc! {
+      "start_ts": { "$lte": now },
+      "end_ts": { "$gt": now },
+    },
+    None,
+  )
+  .await
+  .context("failed to query db for active alert silences")
+}
+
+/// Whether the alert matches any of the silences.
+pub async fn is_silenced(
+  silences: &[AlertSilence],
+  alert: &Alert,
+) -> bool {
+  let now = komodo_timestamp();
+  let silences = silences
+    .iter()
+    .filter(|silence| silence.is_active(now))
+    .filter(|silence| matches_without_tags(silence, alert))
+    .collect::<Vec<_>>();
+  if silences.is_empty() {
+    return false;
+  }
+  // Only need to look up the target tags
+  // if all of the otherwise matching silences have tag matchers.
+  if silences.iter().any(|silence| silence.tags.is_empty()) {
+    return true;
+  }
+  let tags = match get_target_tags(&alert.target).await {
+    Ok(tags) => tags,
+    Err(e) => {
+      warn!("failed to get tags for alert target | {e:#}");
+      return false;
+    }
+  };
+  silences
+    .iter()
+    .any(|silence| silence.tags.iter().any(|tag| tags.contains(tag)))
+}
+
+fn matches_without_tags(
+  silence: &AlertSilence,
+  alert: &Alert,
+) -> bool {
+  if !silence.levels.is_empty()
+    && !silence.levels.contains(&alert.level)
+  {
+    return false;
+  }
+  if !silence.alert_types.is_empty()
+    && !silence.alert_types.contains(&alert.data.extract_variant())
+  {
+    return false;
+  }
+  if silence.targets.is_empty() {
+    return true;
+  }
+  if silence.targets.contains(&alert.target) {
+    return true;
+  }
+  // Silencing a Server also silences alerts
+  // on the Deployments / Stacks it hosts.
+  let Some(server_id) = alert_server_id(alert) else {
+    return false;
+  };
+  silence.targets.iter().any(|target| {
+    matches!(target, ResourceTarget::Server(id) if id == server_id)
+  })
+}
+
+/// The server hosting the alerting resource, if applicable.
+fn alert_server_id(alert: &Alert) -> Option<&str> {
+  match &alert.data {
+    AlertData::ContainerStateChange { server_id, .. }
+    | AlertData::DeploymentImageUpdateAvailable {
+      server_id, ..
+    }
+    | AlertData::StackStateChange { server_id, .. }
+    | AlertData::StackImageUpdateAvailable { server_id, .. }
+    | AlertData::ContainerUnhealthy { server_id, .. }
+    | AlertData::ContainerRestartLoop { server_id, .. } => {
+      Some(server_id)
+    }
+    _ => None,
+  }
+}
+
+async fn get_target_tags(
+  target: &ResourceTarget,
+) -> anyhow::Result<Vec<String>> {
+  match target {
+    ResourceTarget::System(_) => Ok(Vec::new()),
+    ResourceTarget::Server(id) => get_tags::<Server>(id).await,
+    ResourceTarget::Deployment(id) => {
+      get_tags::<Deployment>(id).await
+    }
+    ResourceTarget::Stack(id) => get_tags::<Stack>(id).await,
+    ResourceTarget::Build(id) => get_tags::<Build>(id).await,
+    ResourceTarget::Builder(id) => get_tags::<Builder>(id).await,
+    ResourceTarget::Repo(id) => get_tags::<Repo>(id).await,
+    ResourceTarget::Alerter(id) => get_tags::<Alerter>(id).await,
+    ResourceTarget::Procedure(id) => get_tags::<Procedure>(id).await,
+    ResourceTarget::Action(id) => get_tags::<Action>(id).await,
+    ResourceTarget::ServerTemplate(id) => {
+      get_tags::<ServerTemplate>(id).await
+    }
+    ResourceTarget::ResourceSync(id) => {
+      get_tags::<ResourceSync>(id).await
+    }
+  }
+}
+
+async fn get_tags<T: KomodoResource>(
+  id: &str,
+) -> anyhow::Result<Vec<String>> {
+  Ok(resource::get::<T>(id).await?.tags)
+}
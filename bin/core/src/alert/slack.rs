@@ -86,6 +86,48 @@ pub async fn send_alert(
         }
       }
     }
+    AlertData::ServerNetwork {
+      id,
+      name,
+      region,
+      interface,
+      rx_mbps,
+      tx_mbps,
+    } => {
+      let region = fmt_region(region);
+      let text = format!("{level} | *{name}*{region} network usage on *{interface}* at *{rx_mbps:.1} Mbps* in / *{tx_mbps:.1} Mbps* out 📶");
+      let blocks = vec![
+        Block::header(level),
+        Block::section(format!(
+          "*{name}*{region} network usage on *{interface}* at *{rx_mbps:.1} Mbps* in / *{tx_mbps:.1} Mbps* out 📶"
+        )),
+        Block::section(resource_link(
+          ResourceTargetVariant::Server,
+          id,
+        )),
+      ];
+      (text, blocks.into())
+    }
+    AlertData::ServerLoad {
+      id,
+      name,
+      region,
+      load_average,
+    } => {
+      let region = fmt_region(region);
+      let text = format!("{level} | *{name}*{region} 5 minute load average at *{load_average:.2}*");
+      let blocks = vec![
+        Block::header(level),
+        Block::section(format!(
+          "*{name}*{region} 5 minute load average at *{load_average:.2}*"
+        )),
+        Block::section(resource_link(
+          ResourceTargetVariant::Server,
+          id,
+        )),
+      ];
+      (text, blocks.into())
+    }
     AlertData::ServerMem {
       id,
       name,
@@ -259,6 +301,59 @@ pub async fn send_alert(
       ];
       (text, blocks.into())
     }
+    AlertData::ContainerUnhealthy {
+      name,
+      server_name,
+      container,
+      ..
+    } => {
+      let kind = alert.target.extract_variant();
+      let text = match alert.level {
+        SeverityLevel::Ok => format!(
+          "{level} | {kind:?} *{name}* container *{container}* is no longer unhealthy"
+        ),
+        _ => format!(
+          "{level} | {kind:?} *{name}* container *{container}* is *unhealthy* 🩺"
+        ),
+      };
+      let blocks = vec![
+        Block::header(text.clone()),
+        Block::section(format!("server: *{server_name}*")),
+        Block::section(alert_link(alert).unwrap_or_default()),
+      ];
+      (text, blocks.into())
+    }
+    AlertData::ContainerRestartLoop {
+      name,
+      server_name,
+      container,
+      restarts,
+      ..
+    } => {
+      let kind = alert.target.extract_variant();
+      let (text, details) = match alert.level {
+        SeverityLevel::Ok => (
+          format!(
+            "{level} | {kind:?} *{name}* container *{container}* is no longer restarting"
+          ),
+          format!("server: *{server_name}*"),
+        ),
+        _ => (
+          format!(
+            "{level} | {kind:?} *{name}* container *{container}* is in a restart loop 🔁"
+          ),
+          format!(
+            "restarted *{restarts}* times in the last 5 minutes\nserver: *{server_name}*"
+          ),
+        ),
+      };
+      let blocks = vec![
+        Block::header(text.clone()),
+        Block::section(details),
+        Block::section(alert_link(alert).unwrap_or_default()),
+      ];
+      (text, blocks.into())
+    }
     AlertData::AwsBuilderTerminationFailed {
       instance_id,
       message,
@@ -317,6 +412,60 @@ pub async fn send_alert(
       ];
       (text, blocks.into())
     }
+    AlertData::ProcedureFailed {
+      id,
+      name,
+      stage,
+      error,
+    } => {
+      let text = format!("{level} | Procedure *{name}* has *failed*");
+      let blocks = vec![
+        Block::header(text.clone()),
+        Block::section(format!(
+          "procedure name: *{name}*\nstage: *{stage}*",
+        )),
+        Block::section(format!("```{error}```")),
+        Block::section(resource_link(
+          ResourceTargetVariant::Procedure,
+          id,
+        )),
+      ];
+      (text, blocks.into())
+    }
+    AlertData::ActionFailed { id, name, error } => {
+      let text = format!("{level} | Action *{name}* has *failed*");
+      let blocks = vec![
+        Block::header(text.clone()),
+        Block::section(format!("action name: *{name}*")),
+        Block::section(format!("```{error}```")),
+        Block::section(resource_link(
+          ResourceTargetVariant::Action,
+          id,
+        )),
+      ];
+      (text, blocks.into())
+    }
+    AlertData::ResourceSyncFailed {
+      id,
+      name,
+      stage,
+      error,
+    } => {
+      let text =
+        format!("{level} | Resource sync *{name}* has *failed*");
+      let blocks = vec![
+        Block::header(text.clone()),
+        Block::section(format!(
+          "sync name: *{name}*\nstage: *{stage}*",
+        )),
+        Block::section(format!("```{error}```")),
+        Block::section(resource_link(
+          ResourceTargetVariant::ResourceSync,
+          id,
+        )),
+      ];
+      (text, blocks.into())
+    }
     AlertData::None {} => Default::default(),
   };
   if !text.is_empty() {
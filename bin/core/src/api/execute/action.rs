@@ -14,10 +14,13 @@ use komodo_client::{
   },
   entities::{
     action::Action,
+    alert::{Alert, AlertData, SeverityLevel},
     config::core::CoreConfig,
+    komodo_timestamp,
     permission::PermissionLevel,
     update::Update,
     user::{action_user, User},
+    ResourceTarget,
   },
 };
 use mungos::{by_id::update_one_by_id, mongodb::bson::to_document};
@@ -25,6 +28,7 @@ use resolver_api::Resolve;
 use tokio::fs;
 
 use crate::{
+  alert::{error_excerpt, failed_log_excerpt, send_alerts},
   api::execute::ExecuteRequest,
   config::core_config,
   helpers::{
@@ -63,7 +67,7 @@ impl Resolve<RunAction, (User, Update)> for State {
   async fn resolve(
     &self,
     RunAction { action }: RunAction,
-    (user, mut update): (User, Update),
+    (user, update): (User, Update),
   ) -> anyhow::Result<Update> {
     let mut action = resource::get_check_permissions::<Action>(
       &action,
@@ -85,85 +89,155 @@ impl Resolve<RunAction, (User, Update)> for State {
 
     update_update(update.clone()).await?;
 
-    let CreateApiKeyResponse { key, secret } = State
-      .resolve(
-        CreateApiKey {
-          name: update.id.clone(),
-          expires: 0,
-        },
-        action_user().to_owned(),
-      )
-      .await?;
+    let res = run_action(&mut action, update).await;
+
+    let error = match &res {
+      Ok(update) if update.success => return res,
+      Ok(update) => failed_log_excerpt(update).unwrap_or_default().1,
+      Err(e) => error_excerpt(&format!("{e:#}")),
+    };
 
-    let contents = &mut action.config.file_contents;
+    warn!("action unsuccessful, alerting...");
+    tokio::spawn(async move {
+      let alert = Alert {
+        id: Default::default(),
+        target: ResourceTarget::Action(action.id.clone()),
+        ts: komodo_timestamp(),
+        resolved_ts: Some(komodo_timestamp()),
+        silenced: false,
+        acknowledged: false,
+        acknowledged_by: None,
+        acknowledged_ts: None,
+        resolved: true,
+        level: SeverityLevel::Warning,
+        data: AlertData::ActionFailed {
+          id: action.id,
+          name: action.name,
+          error,
+        },
+      };
+      send_alerts(&[alert]).await
+    });
 
-    // Wrap the file contents in the execution context.
-    *contents = full_contents(contents, &key, &secret);
+    res
+  }
+}
 
-    let replacers =
-      interpolate(contents, &mut update, key.clone(), secret.clone())
-        .await?
-        .into_iter()
-        .collect::<Vec<_>>();
+async fn run_action(
+  action: &mut Action,
+  mut update: Update,
+) -> anyhow::Result<Update> {
+  let CreateApiKeyResponse { key, secret } = State
+    .resolve(
+      CreateApiKey {
+        name: update.id.clone(),
+        expires: 0,
+      },
+      action_user().to_owned(),
+    )
+    .await?;
 
-    let file = format!("{}.ts", random_string(10));
-    let path = core_config().action_directory.join(&file);
+  let file = format!("{}.ts", random_string(10));
+  let path = core_config().action_directory.join(&file);
 
-    if let Some(parent) = path.parent() {
-      let _ = fs::create_dir_all(parent).await;
+  let replacers = match write_action_file(
+    &mut action.config.file_contents,
+    &path,
+    &mut update,
+    &key,
+    &secret,
+  )
+  .await
+  {
+    Ok(replacers) => replacers,
+    Err(e) => {
+      delete_api_key(key).await;
+      return Err(e);
     }
+  };
+
+  let mut res = run_komodo_command(
+    // Keep this stage name as is, the UI will find the latest update log by matching the stage name
+    "Execute Action",
+    None,
+    format!("deno run --allow-all {}", path.display()),
+    false,
+  )
+  .await;
+
+  res.stdout = svi::replace_in_string(&res.stdout, &replacers)
+    .replace(&key, "<ACTION_API_KEY>");
+  res.stderr = svi::replace_in_string(&res.stderr, &replacers)
+    .replace(&secret, "<ACTION_API_SECRET>");
+
+  cleanup_run(file + ".js", &path).await;
+
+  delete_api_key(key).await;
 
-    fs::write(&path, contents).await.with_context(|| {
-      format!("Failed to write action file to {path:?}")
-    })?;
+  update.logs.push(res);
+  update.finalize();
 
-    let mut res = run_komodo_command(
-      // Keep this stage name as is, the UI will find the latest update log by matching the stage name
-      "Execute Action",
+  // Need to manually update the update before cache refresh,
+  // and before broadcast with update_update.
+  // The Err case of to_document should be unreachable,
+  // but will fail to update cache in that case.
+  if let Ok(update_doc) = to_document(&update) {
+    let _ = update_one_by_id(
+      &db_client().updates,
+      &update.id,
+      mungos::update::Update::Set(update_doc),
       None,
-      format!("deno run --allow-all {}", path.display()),
-      false,
     )
     .await;
+    refresh_action_state_cache().await;
+  }
 
-    res.stdout = svi::replace_in_string(&res.stdout, &replacers)
-      .replace(&key, "<ACTION_API_KEY>");
-    res.stderr = svi::replace_in_string(&res.stderr, &replacers)
-      .replace(&secret, "<ACTION_API_SECRET>");
-
-    cleanup_run(file + ".js", &path).await;
+  update_update(update.clone()).await?;
 
-    if let Err(e) = State
-      .resolve(DeleteApiKey { key }, action_user().to_owned())
-      .await
-    {
-      warn!(
-        "Failed to delete API key after action execution | {e:#}"
-      );
-    };
+  Ok(update)
+}
 
-    update.logs.push(res);
-    update.finalize();
-
-    // Need to manually update the update before cache refresh,
-    // and before broadcast with update_update.
-    // The Err case of to_document should be unreachable,
-    // but will fail to update cache in that case.
-    if let Ok(update_doc) = to_document(&update) {
-      let _ = update_one_by_id(
-        &db_client().updates,
-        &update.id,
-        mungos::update::Update::Set(update_doc),
-        None,
-      )
-      .await;
-      refresh_action_state_cache().await;
-    }
+/// Wraps the action in the execution context, interpolates
+/// variables / secrets, and writes it to the action directory.
+/// Returns the secret replacers to sanitize the output with.
+async fn write_action_file(
+  contents: &mut String,
+  path: &Path,
+  update: &mut Update,
+  key: &str,
+  secret: &str,
+) -> anyhow::Result<Vec<(String, String)>> {
+  // Wrap the file contents in the execution context.
+  *contents = full_contents(contents, key, secret);
 
-    update_update(update.clone()).await?;
+  let replacers = interpolate(
+    contents,
+    update,
+    key.to_string(),
+    secret.to_string(),
+  )
+  .await?
+  .into_iter()
+  .collect::<Vec<_>>();
 
-    Ok(update)
+  if let Some(parent) = path.parent() {
+    let _ = fs::create_dir_all(parent).await;
   }
+
+  fs::write(path, contents).await.with_context(|| {
+    format!("Failed to write action file to {path:?}")
+  })?;
+
+  Ok(replacers)
+}
+
+async fn delete_api_key(key: String) {
+  if let Err(e) = State
+    .resolve(DeleteApiKey { key }, action_user().to_owned())
+    .await
+  {
+    warn!("Failed to delete API key after action execution | {e:#}");
+  };
 }
 
 async fn interpolate(
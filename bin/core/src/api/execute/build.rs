@@ -396,6 +396,10 @@ impl Resolve<RunBuild, (User, Update)> for State {
           target,
           ts: komodo_timestamp(),
           resolved_ts: Some(komodo_timestamp()),
+          silenced: false,
+          acknowledged: false,
+          acknowledged_by: None,
+          acknowledged_ts: None,
           resolved: true,
           level: SeverityLevel::Warning,
           data: AlertData::BuildFailed {
@@ -445,6 +449,10 @@ async fn handle_early_return(
         target,
         ts: komodo_timestamp(),
         resolved_ts: Some(komodo_timestamp()),
+        silenced: false,
+        acknowledged: false,
+        acknowledged_by: None,
+        acknowledged_ts: None,
         resolved: true,
         level: SeverityLevel::Warning,
         data: AlertData::BuildFailed {
@@ -1,4 +1,8 @@
-use std::{collections::HashSet, sync::OnceLock};
+use std::{
+  collections::HashSet,
+  sync::OnceLock,
+  time::{Duration, Instant},
+};
 
 use anyhow::{anyhow, Context};
 use cache::TimeoutCache;
@@ -6,19 +10,29 @@ use formatting::format_serror;
 use komodo_client::{
   api::execute::*,
   entities::{
+    all_logs_success,
     build::{Build, ImageRegistryConfig},
     deployment::{
-      extract_registry_domain, Deployment, DeploymentImage,
+      extract_registry_domain, fixed_host_port_mappings,
+      offset_host_ports, DeployStrategy, Deployment,
+      DeploymentConfig, DeploymentImage, RestartMode,
+      REPLICA_DEPLOYMENT_LABEL, REPLICA_INDEX_LABEL,
     },
+    docker::container::{ContainerStateStatusEnum, HealthStatusEnum},
     get_image_name, komodo_timestamp, optional_string,
     permission::PermissionLevel,
     server::Server,
     update::{Log, Update},
     user::User,
-    Version,
+    Operation, ResourceTarget, SystemCommand, TerminationSignal,
+    Version, I64,
   },
 };
-use periphery_client::api;
+use mungos::{
+  by_id::find_one_by_id,
+  mongodb::{bson::doc, options::FindOneOptions},
+};
+use periphery_client::{api, PeripheryClient};
 use resolver_api::Resolve;
 
 use crate::{
@@ -35,7 +49,7 @@ use crate::{
   },
   monitor::update_cache_for_server,
   resource,
-  state::{action_states, State},
+  state::{action_states, db_client, server_status_cache, State},
 };
 
 use super::ExecuteRequest;
@@ -115,53 +129,129 @@ impl Resolve<Deploy, (User, Update)> for State {
     // Send update after setting action state, this way frontend gets correct state.
     update_update(update.clone()).await?;
 
-    // This block resolves the attached Build to an actual versioned image
-    let (version, registry_token) = match &deployment.config.image {
-      DeploymentImage::Build { build_id, version } => {
-        let build = resource::get::<Build>(build_id).await?;
-        let image_name = get_image_name(&build)
-          .context("failed to create image name")?;
-        let version = if version.is_none() {
-          build.config.version
-        } else {
-          *version
-        };
-        let version_str = version.to_string();
-        // Potentially add the build image_tag postfix
-        let version_str = if build.config.image_tag.is_empty() {
-          version_str
-        } else {
-          format!("{version_str}-{}", build.config.image_tag)
-        };
-        // replace image with corresponding build image.
-        deployment.config.image = DeploymentImage::Image {
-          image: format!("{image_name}:{version_str}"),
-        };
-        if build.config.image_registry.domain.is_empty() {
-          (version, None)
-        } else {
-          let ImageRegistryConfig {
-            domain, account, ..
-          } = build.config.image_registry;
-          if deployment.config.image_registry_account.is_empty() {
-            deployment.config.image_registry_account = account
-          }
-          let token = if !deployment
-            .config
-            .image_registry_account
-            .is_empty()
-          {
-            registry_token(&domain, &deployment.config.image_registry_account).await.with_context(
-              || format!("Failed to get git token in call to db. Stopping run. | {domain} | {}", deployment.config.image_registry_account),
-            )?
-          } else {
-            None
-          };
-          (version, token)
-        }
+    let (version, registry_token) =
+      resolve_deployment_image(&mut deployment).await?;
+
+    // Stored on the update if the deploy succeeds,
+    // to be rolled back to later.
+    let known_good = serde_json::to_string(&deployment.config)
+      .context("failed to serialize deployment config")?;
+
+    update.version = version;
+    update_update(update.clone()).await?;
+
+    let periphery = periphery_client(&server)?;
+
+    let removed_old = deploy_container(
+      deployment.clone(),
+      registry_token,
+      &periphery,
+      stop_signal,
+      stop_time,
+      &mut update,
+    )
+    .await?;
+
+    if all_logs_success(&update.logs) {
+      update.other_data = known_good;
+    } else if removed_old && deployment.config.auto_rollback {
+      if let Err(e) =
+        auto_rollback(deployment, &periphery, &mut update).await
+      {
+        update.push_error_log("Rollback", format_serror(&e.into()));
       }
-      DeploymentImage::Image { image } => {
-        let domain = extract_registry_domain(image)?;
+    }
+
+    update_cache_for_server(&server).await;
+
+    update.finalize();
+    update_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
+/// Redeploys the last successful deploy after the deploy in the update failed.
+async fn auto_rollback(
+  mut deployment: Deployment,
+  periphery: &PeripheryClient,
+  update: &mut Update,
+) -> anyhow::Result<()> {
+  let failed_stage = update
+    .logs
+    .iter()
+    .find(|log| !log.success)
+    .map(|log| log.stage.clone())
+    .unwrap_or_default();
+  let Some(known_good) =
+    latest_known_good_deploy(&deployment.id, update.start_ts).await?
+  else {
+    update.push_simple_log(
+      "Rollback",
+      format!(
+        "Deploy failed at '{failed_stage}', but there is no earlier successful deploy to roll back to"
+      ),
+    );
+    return Ok(());
+  };
+  deployment.config = known_good_config(&deployment, &known_good)?;
+  update.push_simple_log(
+    "Rollback",
+    format!(
+      "Deploy failed at '{failed_stage}'. Rolling back to {}",
+      describe_known_good(&known_good, &deployment)
+    ),
+  );
+  update_update(update.clone()).await?;
+  let (_, registry_token) =
+    resolve_deployment_image(&mut deployment).await?;
+  deploy_container(
+    deployment,
+    registry_token,
+    periphery,
+    None,
+    None,
+    update,
+  )
+  .await
+  .map(|_| ())
+}
+
+/// Resolves the attached Build to an actual versioned image,
+/// and gets the token to pull the image.
+async fn resolve_deployment_image(
+  deployment: &mut Deployment,
+) -> anyhow::Result<(Version, Option<String>)> {
+  match &deployment.config.image {
+    DeploymentImage::Build { build_id, version } => {
+      let build = resource::get::<Build>(build_id).await?;
+      let image_name = get_image_name(&build)
+        .context("failed to create image name")?;
+      let version = if version.is_none() {
+        build.config.version
+      } else {
+        *version
+      };
+      let version_str = version.to_string();
+      // Potentially add the build image_tag postfix
+      let version_str = if build.config.image_tag.is_empty() {
+        version_str
+      } else {
+        format!("{version_str}-{}", build.config.image_tag)
+      };
+      // replace image with corresponding build image.
+      deployment.config.image = DeploymentImage::Image {
+        image: format!("{image_name}:{version_str}"),
+      };
+      if build.config.image_registry.domain.is_empty() {
+        Ok((version, None))
+      } else {
+        let ImageRegistryConfig {
+          domain, account, ..
+        } = build.config.image_registry;
+        if deployment.config.image_registry_account.is_empty() {
+          deployment.config.image_registry_account = account
+        }
         let token = if !deployment
           .config
           .image_registry_account
@@ -173,86 +263,752 @@ impl Resolve<Deploy, (User, Update)> for State {
         } else {
           None
         };
-        (Version::default(), token)
+        Ok((version, token))
       }
-    };
+    }
+    DeploymentImage::Image { image } => {
+      let domain = extract_registry_domain(image)?;
+      let token = if !deployment
+        .config
+        .image_registry_account
+        .is_empty()
+      {
+        registry_token(&domain, &deployment.config.image_registry_account).await.with_context(
+          || format!("Failed to get git token in call to db. Stopping run. | {domain} | {}", deployment.config.image_registry_account),
+        )?
+      } else {
+        None
+      };
+      Ok((Version::default(), token))
+    }
+  }
+}
 
-    // interpolate variables / secrets, returning the sanitizing replacers to send to
-    // periphery so it may sanitize the final command for safe logging (avoids exposing secret values)
-    let secret_replacers = if !deployment.config.skip_secret_interp {
-      let vars_and_secrets = get_variables_and_secrets().await?;
-
-      let mut global_replacers = HashSet::new();
-      let mut secret_replacers = HashSet::new();
-
-      interpolate_variables_secrets_into_string(
-        &vars_and_secrets,
-        &mut deployment.config.environment,
-        &mut global_replacers,
-        &mut secret_replacers,
-      )?;
-
-      interpolate_variables_secrets_into_string(
-        &vars_and_secrets,
-        &mut deployment.config.ports,
-        &mut global_replacers,
-        &mut secret_replacers,
-      )?;
-
-      interpolate_variables_secrets_into_string(
-        &vars_and_secrets,
-        &mut deployment.config.volumes,
-        &mut global_replacers,
-        &mut secret_replacers,
-      )?;
-
-      interpolate_variables_secrets_into_extra_args(
-        &vars_and_secrets,
-        &mut deployment.config.extra_args,
-        &mut global_replacers,
-        &mut secret_replacers,
-      )?;
-
-      interpolate_variables_secrets_into_string(
-        &vars_and_secrets,
-        &mut deployment.config.command,
-        &mut global_replacers,
-        &mut secret_replacers,
-      )?;
-
-      add_interp_update_log(
-        &mut update,
-        &global_replacers,
-        &secret_replacers,
-      );
+/// Interpolates the variables / secrets, deploys the container,
+/// and verifies it, pushing the logs to the update.
+/// Returns whether the old container was removed.
+async fn deploy_container(
+  mut deployment: Deployment,
+  registry_token: Option<String>,
+  periphery: &PeripheryClient,
+  stop_signal: Option<TerminationSignal>,
+  stop_time: Option<i32>,
+  update: &mut Update,
+) -> anyhow::Result<bool> {
+  // interpolate variables / secrets, returning the sanitizing replacers to send to
+  // periphery so it may sanitize the final command for safe logging (avoids exposing secret values)
+  let secret_replacers = if !deployment.config.skip_secret_interp {
+    let vars_and_secrets = get_variables_and_secrets().await?;
+
+    let mut global_replacers = HashSet::new();
+    let mut secret_replacers = HashSet::new();
+
+    interpolate_variables_secrets_into_string(
+      &vars_and_secrets,
+      &mut deployment.config.environment,
+      &mut global_replacers,
+      &mut secret_replacers,
+    )?;
+
+    interpolate_variables_secrets_into_string(
+      &vars_and_secrets,
+      &mut deployment.config.ports,
+      &mut global_replacers,
+      &mut secret_replacers,
+    )?;
+
+    interpolate_variables_secrets_into_string(
+      &vars_and_secrets,
+      &mut deployment.config.volumes,
+      &mut global_replacers,
+      &mut secret_replacers,
+    )?;
+
+    interpolate_variables_secrets_into_extra_args(
+      &vars_and_secrets,
+      &mut deployment.config.extra_args,
+      &mut global_replacers,
+      &mut secret_replacers,
+    )?;
+
+    interpolate_variables_secrets_into_string(
+      &vars_and_secrets,
+      &mut deployment.config.command,
+      &mut global_replacers,
+      &mut secret_replacers,
+    )?;
+
+    interpolate_variables_secrets_into_string(
+      &vars_and_secrets,
+      &mut deployment.config.healthcheck_command,
+      &mut global_replacers,
+      &mut secret_replacers,
+    )?;
+
+    interpolate_variables_secrets_into_string(
+      &vars_and_secrets,
+      &mut deployment.config.cutover_command,
+      &mut global_replacers,
+      &mut secret_replacers,
+    )?;
+
+    add_interp_update_log(
+      update,
+      &global_replacers,
+      &secret_replacers,
+    );
+
+    secret_replacers
+  } else {
+    Default::default()
+  };
+
+  update_update(update.clone()).await?;
+
+  let replacers = secret_replacers.into_iter().collect::<Vec<_>>();
+  let replicas = deployment.config.replicas;
+
+  // Checked on create / update too, but another Deployment
+  // may have been created by a sync since.
+  resource::check_container_name_collisions(
+    &deployment.id,
+    &deployment.name,
+    replicas,
+  )
+  .await?;
+
+  // Removed first, so they don't hold the host ports
+  // the replicas publish after a change in replica count.
+  let mut removed_old = remove_extra_replicas(
+    &deployment,
+    periphery,
+    stop_signal,
+    stop_time,
+    update,
+  )
+  .await?;
+  if !all_logs_success(&update.logs) {
+    return Ok(removed_old);
+  }
 
-      secret_replacers
-    } else {
-      Default::default()
+  // Replicas are deployed one at a time,
+  // stopping at the first which fails.
+  for (index, replica) in
+    replica_deployments(&deployment)?.into_iter().enumerate()
+  {
+    let first_log = update.logs.len();
+    removed_old |= match deployment.config.deploy_strategy {
+      DeployStrategy::Recreate => {
+        recreate_deploy(
+          replica,
+          registry_token.clone(),
+          replacers.clone(),
+          periphery,
+          stop_signal,
+          stop_time,
+          update,
+        )
+        .await?
+      }
+      DeployStrategy::BlueGreen => {
+        blue_green_deploy(
+          replica,
+          registry_token.clone(),
+          replacers.clone(),
+          periphery,
+          stop_signal,
+          stop_time,
+          update,
+        )
+        .await?
+      }
     };
+    if replicas > 1 {
+      for log in &mut update.logs[first_log..] {
+        log.stage = format!("{} (replica {})", log.stage, index + 1);
+      }
+    }
+    if !all_logs_success(&update.logs) {
+      return Ok(removed_old);
+    }
+  }
 
-    update.version = version;
+  Ok(removed_old)
+}
+
+/// The deployment to run for each replica.
+/// With more than one, the replica containers are named by index,
+/// labeled, and have their host ports offset.
+fn replica_deployments(
+  deployment: &Deployment,
+) -> anyhow::Result<Vec<Deployment>> {
+  if deployment.config.replicas <= 1 {
+    return Ok(vec![deployment.clone()]);
+  }
+  deployment
+    .container_names()
+    .into_iter()
+    .enumerate()
+    .map(|(offset, name)| {
+      let mut replica = deployment.clone();
+      replica.config.ports =
+        offset_host_ports(&deployment.config.ports, offset as u16)
+          .context("failed to offset ports for replica")?;
+      replica.config.labels = format!(
+        "{}\n{REPLICA_DEPLOYMENT_LABEL}={}\n{REPLICA_INDEX_LABEL}={}",
+        deployment.config.labels,
+        deployment.id,
+        offset + 1
+      );
+      replica.name = name;
+      Ok(replica)
+    })
+    .collect()
+}
+
+/// Removes the containers of replicas above the replica count,
+/// including the unindexed container after scaling up from one replica,
+/// and the indexed containers after scaling down to one.
+/// Returns whether any were removed.
+async fn remove_extra_replicas(
+  deployment: &Deployment,
+  periphery: &PeripheryClient,
+  stop_signal: Option<TerminationSignal>,
+  stop_time: Option<i32>,
+  update: &mut Update,
+) -> anyhow::Result<bool> {
+  let Some(status) = server_status_cache()
+    .get(&deployment.config.server_id)
+    .await
+  else {
+    return Ok(false);
+  };
+  let names = deployment.container_names();
+  let extra = status
+    .containers
+    .iter()
+    .flatten()
+    .filter(|container| {
+      !names.contains(&container.name)
+        && (container.name == deployment.name
+          || container.labels.get(REPLICA_DEPLOYMENT_LABEL)
+            == Some(&deployment.id))
+    })
+    .map(|container| container.name.clone())
+    .collect::<Vec<_>>();
+  let removed = !extra.is_empty();
+  for name in extra {
+    let log = periphery
+      .request(api::container::RemoveContainer {
+        name,
+        signal: stop_signal,
+        time: stop_time,
+      })
+      .await
+      .unwrap_or_else(|e| {
+        Log::error("Remove Replica", format_serror(&e.into()))
+      });
+    update.logs.push(log);
+  }
+  if removed {
     update_update(update.clone()).await?;
+  }
+  Ok(removed)
+}
+
+/// Removes the old container, then starts the new one.
+/// Returns whether the old container was removed.
+async fn recreate_deploy(
+  deployment: Deployment,
+  registry_token: Option<String>,
+  replacers: Vec<(String, String)>,
+  periphery: &PeripheryClient,
+  stop_signal: Option<TerminationSignal>,
+  stop_time: Option<i32>,
+  update: &mut Update,
+) -> anyhow::Result<bool> {
+  let container_name = deployment.name.clone();
+  let healthy_timeout = healthy_timeout(&deployment.config);
+  let restart = deployment.config.restart;
+
+  match periphery
+    .request(api::container::Deploy {
+      deployment,
+      stop_signal,
+      stop_time,
+      registry_token,
+      replacers,
+    })
+    .await
+  {
+    Ok(log) => {
+      let success = log.success;
+      update.logs.push(log);
+      if success && healthy_timeout > 0 {
+        update_update(update.clone()).await?;
+        update.logs.push(
+          verify_deploy(
+            periphery,
+            &container_name,
+            healthy_timeout,
+            restart,
+          )
+          .await,
+        );
+      }
+    }
+    Err(e) => {
+      update
+        .push_error_log("Deploy Container", format_serror(&e.into()));
+    }
+  };
+
+  Ok(true)
+}
 
-    match periphery_client(&server)?
-      .request(api::container::Deploy {
-        deployment,
-        stop_signal,
-        stop_time,
-        registry_token,
-        replacers: secret_replacers.into_iter().collect(),
+/// Used when the deployment has no healthy timeout, but
+/// has a healthcheck command or uses BlueGreen deploys.
+const DEFAULT_HEALTHY_TIMEOUT: i32 = 60;
+
+/// The seconds to verify the deploy for, where 0 skips verification.
+/// Deployments with a healthcheck command are always verified.
+fn healthy_timeout(config: &DeploymentConfig) -> i32 {
+  if config.healthy_timeout > 0 {
+    config.healthy_timeout
+  } else if !config.healthcheck_command.trim().is_empty() {
+    DEFAULT_HEALTHY_TIMEOUT
+  } else {
+    0
+  }
+}
+
+/// Appended to the deployment name while the new container
+/// runs alongside the old one.
+const BLUE_GREEN_SUFFIX: &str = "-komodo-next";
+/// Appended to the old container name while the new one takes its name.
+const BLUE_GREEN_PREV_SUFFIX: &str = "-komodo-prev";
+
+/// Starts the new container alongside the old one,
+/// and only replaces the old container once the new one is healthy
+/// and the cutover command succeeds. The old container is then
+/// stopped and renamed aside while the new one takes its name,
+/// and only removed after. Any earlier failure removes the new
+/// container and restores the old one.
+/// Returns whether the old container was removed.
+async fn blue_green_deploy(
+  mut deployment: Deployment,
+  registry_token: Option<String>,
+  replacers: Vec<(String, String)>,
+  periphery: &PeripheryClient,
+  stop_signal: Option<TerminationSignal>,
+  stop_time: Option<i32>,
+  update: &mut Update,
+) -> anyhow::Result<bool> {
+  // Checked on update too, but the config may predate the checks.
+  if deployment.config.network == "host" {
+    update.push_error_log(
+      "Deploy Container",
+      String::from(
+        "BlueGreen deploys can't use the host network, as both containers would bind the same ports",
+      ),
+    );
+    return Ok(false);
+  }
+  match fixed_host_port_mappings(&deployment.config.ports) {
+    Ok(fixed) if fixed.is_empty() => {}
+    Ok(fixed) => {
+      update.push_error_log(
+        "Deploy Container",
+        format!(
+          "BlueGreen deploys can't publish fixed host ports ({}), as both containers would bind them",
+          fixed.join(", ")
+        ),
+      );
+      return Ok(false);
+    }
+    Err(e) => {
+      update.push_error_log(
+        "Deploy Container",
+        format_serror(&e.context("Invalid ports").into()),
+      );
+      return Ok(false);
+    }
+  }
+  let container_name = deployment.name.clone();
+  let next_name = format!("{container_name}{BLUE_GREEN_SUFFIX}");
+  // BlueGreen deploys always wait for the new container to be healthy.
+  let healthy_timeout = match healthy_timeout(&deployment.config) {
+    0 => DEFAULT_HEALTHY_TIMEOUT,
+    timeout => timeout,
+  };
+  let restart = deployment.config.restart;
+  let cutover_command =
+    std::mem::take(&mut deployment.config.cutover_command);
+  deployment.name.clone_from(&next_name);
+
+  // Also removes any new container left from an earlier attempt.
+  let log = periphery
+    .request(api::container::Deploy {
+      deployment,
+      stop_signal: None,
+      stop_time: None,
+      registry_token,
+      replacers: replacers.clone(),
+    })
+    .await
+    .unwrap_or_else(|e| {
+      Log::error("Deploy Container", format_serror(&e.into()))
+    });
+  if !push_blue_green_log(update, log).await? {
+    remove_next_container(periphery, &next_name, update).await?;
+    return Ok(false);
+  }
+
+  let log =
+    verify_deploy(periphery, &next_name, healthy_timeout, restart)
+      .await;
+  if !push_blue_green_log(update, log).await? {
+    remove_next_container(periphery, &next_name, update).await?;
+    return Ok(false);
+  }
+
+  if !cutover_command.is_empty() {
+    let mut log = periphery
+      .request(api::RunCommand {
+        command: SystemCommand {
+          path: String::new(),
+          command: cutover_command,
+        },
       })
       .await
-    {
-      Ok(log) => update.logs.push(log),
-      Err(e) => {
-        update.push_error_log(
-          "Deploy Container",
+      .unwrap_or_else(|e| {
+        Log::error("Cutover", format_serror(&e.into()))
+      });
+    log.stage = String::from("Cutover");
+    log.command = svi::replace_in_string(&log.command, &replacers);
+    log.stdout = svi::replace_in_string(&log.stdout, &replacers);
+    log.stderr = svi::replace_in_string(&log.stderr, &replacers);
+    if !push_blue_green_log(update, log).await? {
+      remove_next_container(periphery, &next_name, update).await?;
+      return Ok(false);
+    }
+  }
+
+  // Inspect fails if there is no old container, ie the first deploy.
+  let old_exists = periphery
+    .request(api::container::InspectContainer {
+      name: container_name.clone(),
+    })
+    .await
+    .is_ok();
+  if !old_exists {
+    let log =
+      rename_container(periphery, &next_name, &container_name).await;
+    update.logs.push(log);
+    return Ok(false);
+  }
+
+  // The old container is stopped and moved aside rather than removed,
+  // so it can be restored if the new one can't take its name.
+  let prev_name = format!("{container_name}{BLUE_GREEN_PREV_SUFFIX}");
+  // Remove any old container left from an earlier attempt.
+  if periphery
+    .request(api::container::InspectContainer {
+      name: prev_name.clone(),
+    })
+    .await
+    .is_ok()
+  {
+    let log = periphery
+      .request(api::container::RemoveContainer {
+        name: prev_name.clone(),
+        signal: None,
+        time: None,
+      })
+      .await
+      .unwrap_or_else(|e| {
+        Log::error(
+          "Remove Previous Container",
           format_serror(&e.into()),
-        );
+        )
+      });
+    if !push_blue_green_log(update, log).await? {
+      remove_next_container(periphery, &next_name, update).await?;
+      return Ok(false);
+    }
+  }
+
+  let log = periphery
+    .request(api::container::StopContainer {
+      name: container_name.clone(),
+      signal: stop_signal,
+      time: stop_time,
+    })
+    .await
+    .unwrap_or_else(|e| {
+      Log::error("Stop Old Container", format_serror(&e.into()))
+    });
+  if !push_blue_green_log(update, log).await? {
+    start_old_container(periphery, &container_name, update).await?;
+    remove_next_container(periphery, &next_name, update).await?;
+    return Ok(false);
+  }
+
+  let log =
+    rename_container(periphery, &container_name, &prev_name).await;
+  if !push_blue_green_log(update, log).await? {
+    start_old_container(periphery, &container_name, update).await?;
+    remove_next_container(periphery, &next_name, update).await?;
+    return Ok(false);
+  }
+
+  let log =
+    rename_container(periphery, &next_name, &container_name).await;
+  if !push_blue_green_log(update, log).await? {
+    let log =
+      rename_container(periphery, &prev_name, &container_name).await;
+    if push_blue_green_log(update, log).await? {
+      start_old_container(periphery, &container_name, update).await?;
+    }
+    remove_next_container(periphery, &next_name, update).await?;
+    return Ok(false);
+  }
+
+  let log = periphery
+    .request(api::container::RemoveContainer {
+      name: prev_name,
+      signal: None,
+      time: None,
+    })
+    .await
+    .unwrap_or_else(|e| {
+      Log::error("Remove Old Container", format_serror(&e.into()))
+    });
+  update.logs.push(log);
+
+  Ok(true)
+}
+
+async fn rename_container(
+  periphery: &PeripheryClient,
+  curr_name: &str,
+  new_name: &str,
+) -> Log {
+  periphery
+    .request(api::container::RenameContainer {
+      curr_name: curr_name.to_string(),
+      new_name: new_name.to_string(),
+    })
+    .await
+    .unwrap_or_else(|e| {
+      Log::error("Rename Container", format_serror(&e.into()))
+    })
+}
+
+/// Restarts the old container after a failed cutover.
+async fn start_old_container(
+  periphery: &PeripheryClient,
+  container_name: &str,
+  update: &mut Update,
+) -> anyhow::Result<()> {
+  let log = periphery
+    .request(api::container::StartContainer {
+      name: container_name.to_string(),
+    })
+    .await
+    .unwrap_or_else(|e| {
+      Log::error("Start Old Container", format_serror(&e.into()))
+    });
+  update.logs.push(log);
+  update_update(update.clone()).await?;
+  Ok(())
+}
+
+/// Pushes the log to the update, returning whether it succeeded.
+async fn push_blue_green_log(
+  update: &mut Update,
+  log: Log,
+) -> anyhow::Result<bool> {
+  let success = log.success;
+  update.logs.push(log);
+  update_update(update.clone()).await?;
+  Ok(success)
+}
+
+async fn remove_next_container(
+  periphery: &PeripheryClient,
+  next_name: &str,
+  update: &mut Update,
+) -> anyhow::Result<()> {
+  let log = periphery
+    .request(api::container::RemoveContainer {
+      name: next_name.to_string(),
+      signal: None,
+      time: None,
+    })
+    .await
+    .unwrap_or_else(|e| {
+      Log::error("Remove New Container", format_serror(&e.into()))
+    });
+  update.logs.push(log);
+  update_update(update.clone()).await?;
+  Ok(())
+}
+
+/// The latest successful `Deploy` / `RollbackDeployment`
+/// which started before the given time.
+async fn latest_known_good_deploy(
+  deployment_id: &str,
+  before: I64,
+) -> anyhow::Result<Option<Update>> {
+  db_client()
+    .updates
+    .find_one(doc! {
+      "target.type": "Deployment",
+      "target.id": deployment_id,
+      "operation": { "$in": ["Deploy", "RollbackDeployment"] },
+      "success": true,
+      "other_data": { "$exists": true, "$ne": "" },
+      "start_ts": { "$lt": before },
+    })
+    .with_options(
+      FindOneOptions::builder()
+        .sort(doc! { "start_ts": -1 })
+        .build(),
+    )
+    .await
+    .context("failed to query db for last successful deploy")
+}
+
+/// The config stored on a successful deploy update.
+/// The deployment stays on its current server.
+fn known_good_config(
+  deployment: &Deployment,
+  known_good: &Update,
+) -> anyhow::Result<DeploymentConfig> {
+  let mut config =
+    serde_json::from_str::<DeploymentConfig>(&known_good.other_data)
+      .context("failed to parse deployment config on update")?;
+  config.server_id.clone_from(&deployment.config.server_id);
+  Ok(config)
+}
+
+fn describe_known_good(
+  known_good: &Update,
+  deployment: &Deployment,
+) -> String {
+  let image = match &deployment.config.image {
+    DeploymentImage::Image { image } => image.as_str(),
+    DeploymentImage::Build { .. } => "",
+  };
+  format!("the deploy of {image} (update {})", known_good.id)
+}
+
+impl Resolve<RollbackDeployment, (User, Update)> for State {
+  #[instrument(name = "RollbackDeployment", skip(self, user, update), fields(user_id = user.id, update_id = update.id))]
+  async fn resolve(
+    &self,
+    RollbackDeployment {
+      deployment,
+      update_id,
+    }: RollbackDeployment,
+    (user, mut update): (User, Update),
+  ) -> anyhow::Result<Update> {
+    let (mut deployment, server) =
+      setup_deployment_execution(&deployment, &user).await?;
+
+    // get the action state for the deployment (or insert default).
+    let action_state = action_states()
+      .deployment
+      .get_or_insert_default(&deployment.id)
+      .await;
+
+    // Will check to ensure deployment not already busy before updating, and return Err if so.
+    // The returned guard will set the action state back to default when dropped.
+    let _action_guard =
+      action_state.update(|state| state.deploying = true)?;
+
+    // Send update after setting action state, this way frontend gets correct state.
+    update_update(update.clone()).await?;
+
+    let known_good = match update_id {
+      Some(update_id) => {
+        let known_good =
+          find_one_by_id(&db_client().updates, &update_id)
+            .await
+            .context("failed to query db for update")?
+            .with_context(|| {
+              format!("no update found with id {update_id}")
+            })?;
+        if known_good.target
+          != ResourceTarget::Deployment(deployment.id.clone())
+          || !matches!(
+            known_good.operation,
+            Operation::Deploy | Operation::RollbackDeployment
+          )
+          || !known_good.success
+          || known_good.other_data.is_empty()
+        {
+          return Err(anyhow!(
+            "Update {update_id} is not a successful deploy of this deployment"
+          ));
+        }
+        known_good
+      }
+      None => {
+        // Roll back from the latest deploy, whether it succeeded or not.
+        let latest = db_client()
+          .updates
+          .find_one(doc! {
+            "target.type": "Deployment",
+            "target.id": &deployment.id,
+            "operation": { "$in": ["Deploy", "RollbackDeployment"] },
+            "status": "Complete",
+          })
+          .with_options(
+            FindOneOptions::builder()
+              .sort(doc! { "start_ts": -1 })
+              .build(),
+          )
+          .await
+          .context("failed to query db for latest deploy")?
+          .context("deployment has not been deployed")?;
+        latest_known_good_deploy(&deployment.id, latest.start_ts)
+          .await?
+          .context("no earlier successful deploy to roll back to")?
       }
     };
 
+    deployment.config = known_good_config(&deployment, &known_good)?;
+    update.push_simple_log(
+      "Rollback",
+      format!(
+        "Rolling back to {}",
+        describe_known_good(&known_good, &deployment)
+      ),
+    );
+
+    let (_, registry_token) =
+      resolve_deployment_image(&mut deployment).await?;
+    update.version = known_good.version;
+    update_update(update.clone()).await?;
+
+    let periphery = periphery_client(&server)?;
+
+    deploy_container(
+      deployment,
+      registry_token,
+      &periphery,
+      None,
+      None,
+      &mut update,
+    )
+    .await?;
+
+    if all_logs_success(&update.logs) {
+      update.other_data = known_good.other_data;
+    }
+
     update_cache_for_server(&server).await;
 
     update.finalize();
@@ -262,6 +1018,131 @@ impl Resolve<Deploy, (User, Update)> for State {
   }
 }
 
+/// How often the container is inspected while verifying a deploy.
+const VERIFY_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Containers without a healthcheck must stay running this long.
+const VERIFY_RUNNING_FOR: Duration = Duration::from_secs(10);
+
+/// Waits for the deployed container to become healthy,
+/// or to stay running if it has no healthcheck.
+/// Containers which aren't restarted may instead exit with code 0,
+/// as one-off jobs do.
+/// The returned log fails the deploy if the container doesn't.
+pub async fn verify_deploy(
+  periphery: &PeripheryClient,
+  container_name: &str,
+  healthy_timeout: i32,
+  restart: RestartMode,
+) -> Log {
+  const STAGE: &str = "Verify Deploy";
+  let timeout = Duration::from_secs(healthy_timeout.max(0) as u64);
+  let running_for = timeout.min(VERIFY_RUNNING_FOR);
+  let start = Instant::now();
+  loop {
+    let container = match periphery
+      .request(api::container::InspectContainer {
+        name: container_name.to_string(),
+      })
+      .await
+    {
+      Ok(container) => container,
+      Err(e) => {
+        return Log::error(
+          STAGE,
+          format_serror(
+            &e.context("failed to inspect container").into(),
+          ),
+        )
+      }
+    };
+    let state = container.state.unwrap_or_default();
+    let restarts = container.restart_count.unwrap_or_default();
+    let elapsed = start.elapsed().as_secs();
+
+    let failure = match state.status {
+      ContainerStateStatusEnum::Exited
+        if state.exit_code == Some(0)
+          && matches!(
+            restart,
+            RestartMode::NoRestart | RestartMode::OnFailure
+          ) =>
+      {
+        return Log::simple(
+          STAGE,
+          format!("container exited with code 0 after {elapsed}s"),
+        )
+      }
+      ContainerStateStatusEnum::Exited
+      | ContainerStateStatusEnum::Dead => Some(format!(
+        "container exited with code {}{}",
+        state.exit_code.unwrap_or_default(),
+        state
+          .error
+          .filter(|e| !e.is_empty())
+          .map(|e| format!(" | {e}"))
+          .unwrap_or_default()
+      )),
+      ContainerStateStatusEnum::Restarting => {
+        Some(String::from("container is restarting"))
+      }
+      _ if restarts > 0 => {
+        Some(format!("container restarted {restarts} times"))
+      }
+      ContainerStateStatusEnum::Running => {
+        match state.health.map(|health| health.status) {
+          Some(HealthStatusEnum::Healthy) => {
+            return Log::simple(
+              STAGE,
+              format!("container is healthy after {elapsed}s"),
+            )
+          }
+          Some(HealthStatusEnum::Unhealthy) => {
+            Some(String::from("container is unhealthy"))
+          }
+          Some(HealthStatusEnum::Starting) => None,
+          // No healthcheck
+          _ if start.elapsed() >= running_for => {
+            return Log::simple(
+              STAGE,
+              format!(
+                "container has no healthcheck, and stayed running for {elapsed}s"
+              ),
+            )
+          }
+          _ => None,
+        }
+      }
+      _ => None,
+    };
+
+    let failure = failure.or_else(|| {
+      (start.elapsed() >= timeout).then(|| {
+        format!("container was not healthy after {healthy_timeout}s")
+      })
+    });
+
+    if let Some(failure) = failure {
+      let mut log = Log::error(STAGE, failure);
+      // Include the container log to show why.
+      if let Ok(container_log) = periphery
+        .request(api::container::GetContainerLog {
+          name: container_name.to_string(),
+          tail: 20,
+          timestamps: false,
+        })
+        .await
+      {
+        log.command = container_log.command;
+        log.stdout =
+          [container_log.stdout, container_log.stderr].join("\n");
+      }
+      return log;
+    }
+
+    tokio::time::sleep(VERIFY_POLL_INTERVAL).await;
+  }
+}
+
 /// Wait this long after a pull to allow another pull through
 const PULL_TIMEOUT: i64 = 5_000;
 type ServerId = String;
@@ -435,20 +1316,22 @@ impl Resolve<StartDeployment, (User, Update)> for State {
     // Send update after setting action state, this way frontend gets correct state.
     update_update(update.clone()).await?;
 
-    let log = match periphery_client(&server)?
-      .request(api::container::StartContainer {
-        name: deployment.name,
-      })
-      .await
-    {
-      Ok(log) => log,
-      Err(e) => Log::error(
-        "start container",
-        format_serror(&e.context("failed to start container").into()),
-      ),
-    };
-
-    update.logs.push(log);
+    let periphery = periphery_client(&server)?;
+    for name in deployment.container_names() {
+      let log = match periphery
+        .request(api::container::StartContainer { name })
+        .await
+      {
+        Ok(log) => log,
+        Err(e) => Log::error(
+          "start container",
+          format_serror(
+            &e.context("failed to start container").into(),
+          ),
+        ),
+      };
+      update.logs.push(log);
+    }
     update_cache_for_server(&server).await;
     update.finalize();
     update_update(update.clone()).await?;
@@ -481,22 +1364,22 @@ impl Resolve<RestartDeployment, (User, Update)> for State {
     // Send update after setting action state, this way frontend gets correct state.
     update_update(update.clone()).await?;
 
-    let log = match periphery_client(&server)?
-      .request(api::container::RestartContainer {
-        name: deployment.name,
-      })
-      .await
-    {
-      Ok(log) => log,
-      Err(e) => Log::error(
-        "restart container",
-        format_serror(
-          &e.context("failed to restart container").into(),
+    let periphery = periphery_client(&server)?;
+    for name in deployment.container_names() {
+      let log = match periphery
+        .request(api::container::RestartContainer { name })
+        .await
+      {
+        Ok(log) => log,
+        Err(e) => Log::error(
+          "restart container",
+          format_serror(
+            &e.context("failed to restart container").into(),
+          ),
         ),
-      ),
-    };
-
-    update.logs.push(log);
+      };
+      update.logs.push(log);
+    }
     update_cache_for_server(&server).await;
     update.finalize();
     update_update(update.clone()).await?;
@@ -529,20 +1412,22 @@ impl Resolve<PauseDeployment, (User, Update)> for State {
     // Send update after setting action state, this way frontend gets correct state.
     update_update(update.clone()).await?;
 
-    let log = match periphery_client(&server)?
-      .request(api::container::PauseContainer {
-        name: deployment.name,
-      })
-      .await
-    {
-      Ok(log) => log,
-      Err(e) => Log::error(
-        "pause container",
-        format_serror(&e.context("failed to pause container").into()),
-      ),
-    };
-
-    update.logs.push(log);
+    let periphery = periphery_client(&server)?;
+    for name in deployment.container_names() {
+      let log = match periphery
+        .request(api::container::PauseContainer { name })
+        .await
+      {
+        Ok(log) => log,
+        Err(e) => Log::error(
+          "pause container",
+          format_serror(
+            &e.context("failed to pause container").into(),
+          ),
+        ),
+      };
+      update.logs.push(log);
+    }
     update_cache_for_server(&server).await;
     update.finalize();
     update_update(update.clone()).await?;
@@ -575,22 +1460,22 @@ impl Resolve<UnpauseDeployment, (User, Update)> for State {
     // Send update after setting action state, this way frontend gets correct state.
     update_update(update.clone()).await?;
 
-    let log = match periphery_client(&server)?
-      .request(api::container::UnpauseContainer {
-        name: deployment.name,
-      })
-      .await
-    {
-      Ok(log) => log,
-      Err(e) => Log::error(
-        "unpause container",
-        format_serror(
-          &e.context("failed to unpause container").into(),
+    let periphery = periphery_client(&server)?;
+    for name in deployment.container_names() {
+      let log = match periphery
+        .request(api::container::UnpauseContainer { name })
+        .await
+      {
+        Ok(log) => log,
+        Err(e) => Log::error(
+          "unpause container",
+          format_serror(
+            &e.context("failed to unpause container").into(),
+          ),
         ),
-      ),
-    };
-
-    update.logs.push(log);
+      };
+      update.logs.push(log);
+    }
     update_cache_for_server(&server).await;
     update.finalize();
     update_update(update.clone()).await?;
@@ -627,26 +1512,29 @@ impl Resolve<StopDeployment, (User, Update)> for State {
     // Send update after setting action state, this way frontend gets correct state.
     update_update(update.clone()).await?;
 
-    let log = match periphery_client(&server)?
-      .request(api::container::StopContainer {
-        name: deployment.name,
-        signal: signal
-          .unwrap_or(deployment.config.termination_signal)
-          .into(),
-        time: time
-          .unwrap_or(deployment.config.termination_timeout)
-          .into(),
-      })
-      .await
-    {
-      Ok(log) => log,
-      Err(e) => Log::error(
-        "stop container",
-        format_serror(&e.context("failed to stop container").into()),
-      ),
-    };
-
-    update.logs.push(log);
+    let periphery = periphery_client(&server)?;
+    let signal =
+      signal.unwrap_or(deployment.config.termination_signal);
+    let time = time.unwrap_or(deployment.config.termination_timeout);
+    for name in deployment.container_names() {
+      let log = match periphery
+        .request(api::container::StopContainer {
+          name,
+          signal: signal.into(),
+          time: time.into(),
+        })
+        .await
+      {
+        Ok(log) => log,
+        Err(e) => Log::error(
+          "stop container",
+          format_serror(
+            &e.context("failed to stop container").into(),
+          ),
+        ),
+      };
+      update.logs.push(log);
+    }
     update_cache_for_server(&server).await;
     update.finalize();
     update_update(update.clone()).await?;
@@ -706,26 +1594,29 @@ impl Resolve<DestroyDeployment, (User, Update)> for State {
     // Send update after setting action state, this way frontend gets correct state.
     update_update(update.clone()).await?;
 
-    let log = match periphery_client(&server)?
-      .request(api::container::RemoveContainer {
-        name: deployment.name,
-        signal: signal
-          .unwrap_or(deployment.config.termination_signal)
-          .into(),
-        time: time
-          .unwrap_or(deployment.config.termination_timeout)
-          .into(),
-      })
-      .await
-    {
-      Ok(log) => log,
-      Err(e) => Log::error(
-        "stop container",
-        format_serror(&e.context("failed to stop container").into()),
-      ),
-    };
-
-    update.logs.push(log);
+    let periphery = periphery_client(&server)?;
+    let signal =
+      signal.unwrap_or(deployment.config.termination_signal);
+    let time = time.unwrap_or(deployment.config.termination_timeout);
+    for name in deployment.container_names() {
+      let log = match periphery
+        .request(api::container::RemoveContainer {
+          name,
+          signal: signal.into(),
+          time: time.into(),
+        })
+        .await
+      {
+        Ok(log) => log,
+        Err(e) => Log::error(
+          "stop container",
+          format_serror(
+            &e.context("failed to stop container").into(),
+          ),
+        ),
+      };
+      update.logs.push(log);
+    }
     update.finalize();
     update_cache_for_server(&server).await;
     update_update(update.clone()).await?;
@@ -78,6 +78,7 @@ pub enum ExecuteRequest {
   Deploy(Deploy),
   BatchDeploy(BatchDeploy),
   PullDeployment(PullDeployment),
+  RollbackDeployment(RollbackDeployment),
   StartDeployment(StartDeployment),
   RestartDeployment(RestartDeployment),
   PauseDeployment(PauseDeployment),
@@ -6,8 +6,12 @@ use komodo_client::{
     BatchExecutionResponse, BatchRunProcedure, RunProcedure,
   },
   entities::{
-    permission::PermissionLevel, procedure::Procedure,
-    update::Update, user::User,
+    alert::{Alert, AlertData, SeverityLevel},
+    komodo_timestamp,
+    permission::PermissionLevel,
+    procedure::Procedure,
+    update::Update,
+    user::User,
   },
 };
 use mungos::{by_id::update_one_by_id, mongodb::bson::to_document};
@@ -15,7 +19,11 @@ use resolver_api::Resolve;
 use tokio::sync::Mutex;
 
 use crate::{
-  helpers::{procedure::execute_procedure, update::update_update},
+  alert::{failed_log_excerpt, send_alerts},
+  helpers::{
+    procedure::{execute_procedure, FailedStage},
+    update::update_update,
+  },
   resource::{self, refresh_procedure_state_cache},
   state::{action_states, db_client, State},
 };
@@ -99,6 +107,11 @@ fn resolve_inner(
 
     let mut update = update.into_inner();
 
+    let failed_stage = res.as_ref().err().and_then(|e| {
+      e.downcast_ref::<FailedStage>()
+        .map(|stage| stage.name.clone())
+    });
+
     match res {
       Ok(_) => {
         update.push_simple_log(
@@ -133,6 +146,35 @@ fn resolve_inner(
 
     update_update(update.clone()).await?;
 
+    if !update.success {
+      warn!("procedure unsuccessful, alerting...");
+      let target = update.target.clone();
+      let (stage, error) =
+        failed_log_excerpt(&update).unwrap_or_default();
+      let stage = failed_stage.unwrap_or(stage);
+      tokio::spawn(async move {
+        let alert = Alert {
+          id: Default::default(),
+          target,
+          ts: komodo_timestamp(),
+          resolved_ts: Some(komodo_timestamp()),
+          silenced: false,
+          acknowledged: false,
+          acknowledged_by: None,
+          acknowledged_ts: None,
+          resolved: true,
+          level: SeverityLevel::Warning,
+          data: AlertData::ProcedureFailed {
+            id: procedure.id,
+            name: procedure.name,
+            stage,
+            error,
+          },
+        };
+        send_alerts(&[alert]).await
+      });
+    }
+
     Ok(update)
   })
 }
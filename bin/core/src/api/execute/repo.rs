@@ -526,6 +526,10 @@ impl Resolve<BuildRepo, (User, Update)> for State {
           target,
           ts: komodo_timestamp(),
           resolved_ts: Some(komodo_timestamp()),
+          silenced: false,
+          acknowledged: false,
+          acknowledged_by: None,
+          acknowledged_ts: None,
           resolved: true,
           level: SeverityLevel::Warning,
           data: AlertData::RepoBuildFailed {
@@ -573,6 +577,10 @@ async fn handle_builder_early_return(
         target,
         ts: komodo_timestamp(),
         resolved_ts: Some(komodo_timestamp()),
+        silenced: false,
+        acknowledged: false,
+        acknowledged_by: None,
+        acknowledged_ts: None,
         resolved: true,
         level: SeverityLevel::Warning,
         data: AlertData::RepoBuildFailed {
@@ -0,0 +1,47 @@
+use anyhow::Context;
+use komodo_client::{
+  api::read::{
+    GetAlertSilence, GetAlertSilenceResponse, ListAlertSilences,
+    ListAlertSilencesResponse,
+  },
+  entities::{komodo_timestamp, user::User},
+};
+use mungos::{
+  by_id::find_one_by_id,
+  find::find_collect,
+  mongodb::{bson::doc, options::FindOptions},
+};
+use resolver_api::Resolve;
+
+use crate::state::{db_client, State};
+
+impl Resolve<ListAlertSilences, User> for State {
+  async fn resolve(
+    &self,
+    ListAlertSilences { active_only }: ListAlertSilences,
+    _: User,
+  ) -> anyhow::Result<ListAlertSilencesResponse> {
+    let query = active_only
+      .then(|| doc! { "end_ts": { "$gt": komodo_timestamp() } });
+    find_collect(
+      &db_client().alert_silences,
+      query,
+      FindOptions::builder().sort(doc! { "end_ts": -1 }).build(),
+    )
+    .await
+    .context("failed to query db for alert silences")
+  }
+}
+
+impl Resolve<GetAlertSilence, User> for State {
+  async fn resolve(
+    &self,
+    GetAlertSilence { id }: GetAlertSilence,
+    _: User,
+  ) -> anyhow::Result<GetAlertSilenceResponse> {
+    find_one_by_id(&db_client().alert_silences, &id)
+      .await
+      .context("failed to query db for alert silence")?
+      .with_context(|| format!("no alert silence found with id {id}"))
+  }
+}
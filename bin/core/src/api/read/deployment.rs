@@ -1,29 +1,38 @@
 use std::{cmp, collections::HashSet};
 
 use anyhow::{anyhow, Context};
+use async_timing_util::{get_timelength_in_ms, unix_timestamp_ms};
 use komodo_client::{
   api::read::*,
   entities::{
     deployment::{
-      Deployment, DeploymentActionState, DeploymentConfig,
-      DeploymentListItem, DeploymentState,
+      Deployment, DeploymentActionState, DeploymentListItem,
+      DeploymentState,
     },
     docker::container::ContainerStats,
     permission::PermissionLevel,
     server::Server,
+    stack::Stack,
     update::Log,
     user::User,
+    ResourceTarget,
   },
 };
+use mungos::{
+  find::find_collect,
+  mongodb::{bson::doc, options::FindOptions},
+};
 use periphery_client::api;
 use resolver_api::Resolve;
 
 use crate::{
   helpers::{periphery_client, query::get_all_tags},
   resource,
-  state::{action_states, deployment_status_cache, State},
+  state::{action_states, db_client, deployment_status_cache, State},
 };
 
+use super::server::STATS_PER_PAGE;
+
 impl Resolve<GetDeployment, User> for State {
   async fn resolve(
     &self,
@@ -109,16 +118,14 @@ impl Resolve<GetDeploymentLog, User> for State {
     }: GetDeploymentLog,
     user: User,
   ) -> anyhow::Result<Log> {
-    let Deployment {
-      name,
-      config: DeploymentConfig { server_id, .. },
-      ..
-    } = resource::get_check_permissions::<Deployment>(
+    let deployment = resource::get_check_permissions::<Deployment>(
       &deployment,
       &user,
       PermissionLevel::Read,
     )
     .await?;
+    let name = deployment.container_name();
+    let server_id = deployment.config.server_id;
     if server_id.is_empty() {
       return Ok(Log::default());
     }
@@ -146,16 +153,14 @@ impl Resolve<SearchDeploymentLog, User> for State {
     }: SearchDeploymentLog,
     user: User,
   ) -> anyhow::Result<Log> {
-    let Deployment {
-      name,
-      config: DeploymentConfig { server_id, .. },
-      ..
-    } = resource::get_check_permissions::<Deployment>(
+    let deployment = resource::get_check_permissions::<Deployment>(
       &deployment,
       &user,
       PermissionLevel::Read,
     )
     .await?;
+    let name = deployment.container_name();
+    let server_id = deployment.config.server_id;
     if server_id.is_empty() {
       return Ok(Log::default());
     }
@@ -179,16 +184,14 @@ impl Resolve<GetDeploymentStats, User> for State {
     GetDeploymentStats { deployment }: GetDeploymentStats,
     user: User,
   ) -> anyhow::Result<ContainerStats> {
-    let Deployment {
-      name,
-      config: DeploymentConfig { server_id, .. },
-      ..
-    } = resource::get_check_permissions::<Deployment>(
+    let deployment = resource::get_check_permissions::<Deployment>(
       &deployment,
       &user,
       PermissionLevel::Read,
     )
     .await?;
+    let name = deployment.container_name();
+    let server_id = deployment.config.server_id;
     if server_id.is_empty() {
       return Err(anyhow!("deployment has no server attached"));
     }
@@ -200,6 +203,86 @@ impl Resolve<GetDeploymentStats, User> for State {
   }
 }
 
+impl Resolve<GetHistoricalContainerStats, User> for State {
+  async fn resolve(
+    &self,
+    GetHistoricalContainerStats {
+      target,
+      service,
+      granularity,
+      page,
+    }: GetHistoricalContainerStats,
+    user: User,
+  ) -> anyhow::Result<GetHistoricalContainerStatsResponse> {
+    let (target, service) = match target {
+      ResourceTarget::Deployment(deployment) => {
+        let deployment =
+          resource::get_check_permissions::<Deployment>(
+            &deployment,
+            &user,
+            PermissionLevel::Read,
+          )
+          .await?;
+        (ResourceTarget::Deployment(deployment.id), None)
+      }
+      ResourceTarget::Stack(stack) => {
+        let stack = resource::get_check_permissions::<Stack>(
+          &stack,
+          &user,
+          PermissionLevel::Read,
+        )
+        .await?;
+        let service = service.context(
+          "must pass the stack service to get container stats",
+        )?;
+        (ResourceTarget::Stack(stack.id), Some(service))
+      }
+      _ => {
+        return Err(anyhow!(
+          "container stats are only available for Deployments and Stacks"
+        ))
+      }
+    };
+    let (variant, id) = target.extract_variant_id();
+    let granularity =
+      get_timelength_in_ms(granularity.to_string().parse().unwrap())
+        as i64;
+    let mut ts_vec = Vec::<i64>::new();
+    let curr_ts = unix_timestamp_ms() as i64;
+    let mut curr_ts = curr_ts
+      - curr_ts % granularity
+      - granularity * STATS_PER_PAGE * page as i64;
+    for _ in 0..STATS_PER_PAGE {
+      ts_vec.push(curr_ts);
+      curr_ts -= granularity;
+    }
+
+    let stats = find_collect(
+      &db_client().container_stats,
+      doc! {
+        "target.type": variant.to_string(),
+        "target.id": id,
+        "service": service,
+        "ts": { "$in": ts_vec },
+      },
+      FindOptions::builder()
+        .sort(doc! { "ts": -1 })
+        .limit(STATS_PER_PAGE)
+        .build(),
+    )
+    .await
+    .context("failed to pull container stats from db")?;
+    let next_page = if stats.len() == STATS_PER_PAGE as usize {
+      Some(page + 1)
+    } else {
+      None
+    };
+    let res =
+      GetHistoricalContainerStatsResponse { stats, next_page };
+    Ok(res)
+  }
+}
+
 impl Resolve<GetDeploymentActionState, User> for State {
   async fn resolve(
     &self,
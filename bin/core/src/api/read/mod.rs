@@ -31,6 +31,7 @@ use crate::{
 
 mod action;
 mod alert;
+mod alert_silence;
 mod alerter;
 mod build;
 mod builder;
@@ -137,6 +138,7 @@ enum ReadRequest {
   GetDeploymentContainer(GetDeploymentContainer),
   GetDeploymentActionState(GetDeploymentActionState),
   GetDeploymentStats(GetDeploymentStats),
+  GetHistoricalContainerStats(GetHistoricalContainerStats),
   GetDeploymentLog(GetDeploymentLog),
   SearchDeploymentLog(SearchDeploymentLog),
   ListDeployments(ListDeployments),
@@ -211,6 +213,10 @@ enum ReadRequest {
   ListAlerts(ListAlerts),
   GetAlert(GetAlert),
 
+  // ==== ALERT SILENCE ====
+  ListAlertSilences(ListAlertSilences),
+  GetAlertSilence(GetAlertSilence),
+
   // ==== SERVER STATS ====
   #[to_string_resolver]
   GetSystemInformation(GetSystemInformation),
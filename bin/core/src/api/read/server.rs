@@ -7,6 +7,7 @@ use std::{
 use anyhow::{anyhow, Context};
 use async_timing_util::{
   get_timelength_in_ms, unix_timestamp_ms, FIFTEEN_SECONDS_MS,
+  ONE_DAY_MS, ONE_HOUR_MS,
 };
 use komodo_client::{
   api::read::*,
@@ -23,6 +24,10 @@ use komodo_client::{
       Server, ServerActionState, ServerListItem, ServerState,
     },
     stack::{Stack, StackServiceNames},
+    stats::{
+      LoadAverage, StatsResolution, SystemStatsRecord,
+      SystemStatsRollup,
+    },
     update::Log,
     user::User,
     ResourceTarget,
@@ -107,12 +112,14 @@ impl Resolve<GetServer, User> for State {
     req: GetServer,
     user: User,
   ) -> anyhow::Result<Server> {
-    resource::get_check_permissions::<Server>(
+    let mut server = resource::get_check_permissions::<Server>(
       &req.server,
       &user,
       PermissionLevel::Read,
     )
-    .await
+    .await?;
+    resource::redact_enrollment_token(&mut server);
+    Ok(server)
   }
 }
 
@@ -142,8 +149,13 @@ impl Resolve<ListFullServers, User> for State {
     } else {
       get_all_tags(None).await?
     };
-    resource::list_full_for_user::<Server>(query, &user, &all_tags)
-      .await
+    let mut servers =
+      resource::list_full_for_user::<Server>(query, &user, &all_tags)
+        .await?;
+    servers
+      .iter_mut()
+      .for_each(resource::redact_enrollment_token);
+    Ok(servers)
   }
 }
 
@@ -303,7 +315,7 @@ impl ResolveToString<ListSystemProcesses, User> for State {
   }
 }
 
-const STATS_PER_PAGE: i64 = 200;
+pub(super) const STATS_PER_PAGE: i64 = 200;
 
 impl Resolve<GetHistoricalServerStats, User> for State {
   async fn resolve(
@@ -334,30 +346,89 @@ impl Resolve<GetHistoricalServerStats, User> for State {
       curr_ts -= granularity;
     }
 
-    let stats = find_collect(
-      &db_client().stats,
-      doc! {
-        "sid": server.id,
-        "ts": { "$in": ts_vec },
-      },
-      FindOptions::builder()
-        .sort(doc! { "ts": -1 })
-        .skip(page as u64 * STATS_PER_PAGE as u64)
-        .limit(STATS_PER_PAGE)
-        .build(),
-    )
-    .await
-    .context("failed to pull stats from db")?;
+    let resolution = stats_resolution(granularity);
+    let (stats, rollups) = if resolution == StatsResolution::Raw {
+      let stats = find_collect(
+        &db_client().stats,
+        doc! {
+          "sid": server.id,
+          "ts": { "$in": ts_vec },
+        },
+        FindOptions::builder()
+          .sort(doc! { "ts": -1 })
+          .skip(page as u64 * STATS_PER_PAGE as u64)
+          .limit(STATS_PER_PAGE)
+          .build(),
+      )
+      .await
+      .context("failed to pull stats from db")?;
+      (stats, Vec::new())
+    } else {
+      let rollups = find_collect(
+        &db_client().stats_rollups,
+        doc! {
+          "sid": server.id,
+          "resolution": resolution.to_string(),
+          "ts": { "$in": ts_vec },
+        },
+        FindOptions::builder()
+          .sort(doc! { "ts": -1 })
+          .limit(STATS_PER_PAGE)
+          .build(),
+      )
+      .await
+      .context("failed to pull stats rollups from db")?;
+      let stats = rollups.iter().map(rollup_averages).collect();
+      (stats, rollups)
+    };
     let next_page = if stats.len() == STATS_PER_PAGE as usize {
       Some(page + 1)
     } else {
       None
     };
-    let res = GetHistoricalServerStatsResponse { stats, next_page };
+    let res = GetHistoricalServerStatsResponse {
+      stats,
+      next_page,
+      resolution,
+      rollups,
+    };
     Ok(res)
   }
 }
 
+/// Each page covers `granularity` * [STATS_PER_PAGE].
+/// Longer ranges are served from the coarsest rollup
+/// which still has a data point for every timestamp.
+fn stats_resolution(granularity: i64) -> StatsResolution {
+  if granularity >= ONE_DAY_MS as i64 {
+    StatsResolution::Day
+  } else if granularity >= ONE_HOUR_MS as i64 {
+    StatsResolution::Hour
+  } else {
+    StatsResolution::Raw
+  }
+}
+
+fn rollup_averages(rollup: &SystemStatsRollup) -> SystemStatsRecord {
+  SystemStatsRecord {
+    ts: rollup.ts,
+    sid: rollup.sid.clone(),
+    cpu_perc: rollup.cpu_perc.avg as f32,
+    mem_used_gb: rollup.mem_used_gb.avg,
+    mem_total_gb: rollup.mem_total_gb,
+    disk_used_gb: rollup.disk_used_gb.avg,
+    disk_total_gb: rollup.disk_total_gb,
+    disks: Vec::new(),
+    net_rx_bytes_per_sec: rollup.net_rx_bytes_per_sec.avg,
+    net_tx_bytes_per_sec: rollup.net_tx_bytes_per_sec.avg,
+    networks: Vec::new(),
+    load_average: LoadAverage {
+      five: rollup.load_average.avg,
+      ..Default::default()
+    },
+  }
+}
+
 impl ResolveToString<ListDockerContainers, User> for State {
   async fn resolve_to_string(
     &self,
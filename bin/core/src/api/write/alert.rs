@@ -0,0 +1,95 @@
+use anyhow::{anyhow, Context};
+use derive_variants::ExtractVariant;
+use komodo_client::{
+  api::write::{AcknowledgeAlert, AcknowledgeAlertResponse},
+  entities::{
+    komodo_timestamp, permission::PermissionLevel, user::User,
+    Operation,
+  },
+};
+use mungos::{
+  by_id::{find_one_by_id, update_one_by_id},
+  mongodb::bson::doc,
+};
+use resolver_api::Resolve;
+
+use crate::{
+  helpers::{
+    query::get_user_permission_on_target,
+    update::{add_update, make_update},
+  },
+  state::{db_client, State},
+};
+
+impl Resolve<AcknowledgeAlert, User> for State {
+  #[instrument(name = "AcknowledgeAlert", skip(self, user))]
+  async fn resolve(
+    &self,
+    AcknowledgeAlert { id }: AcknowledgeAlert,
+    user: User,
+  ) -> anyhow::Result<AcknowledgeAlertResponse> {
+    let mut alert = find_one_by_id(&db_client().alerts, &id)
+      .await
+      .context("failed to query db for alert")?
+      .with_context(|| format!("no alert found with id {id}"))?;
+
+    if !user.admin {
+      let permission =
+        get_user_permission_on_target(&user, &alert.target).await?;
+      if permission < PermissionLevel::Execute {
+        return Err(anyhow!(
+          "user does not have permission to acknowledge this alert"
+        ));
+      }
+    }
+
+    if alert.resolved {
+      return Err(anyhow!("alert is already resolved"));
+    }
+
+    if alert.acknowledged {
+      return Ok(alert);
+    }
+
+    let ts = komodo_timestamp();
+
+    update_one_by_id(
+      &db_client().alerts,
+      &id,
+      doc! {
+        "$set": {
+          "acknowledged": true,
+          "acknowledged_by": &user.id,
+          "acknowledged_ts": ts,
+        }
+      },
+      None,
+    )
+    .await
+    .context("failed to acknowledge alert on db")?;
+
+    alert.acknowledged = true;
+    alert.acknowledged_by = Some(user.id.clone());
+    alert.acknowledged_ts = Some(ts);
+
+    let mut update = make_update(
+      alert.target.clone(),
+      Operation::AcknowledgeAlert,
+      &user,
+    );
+
+    update.push_simple_log(
+      "acknowledge alert",
+      format!(
+        "acknowledged {:?} alert {id} ({})",
+        alert.data.extract_variant(),
+        alert.level
+      ),
+    );
+    update.finalize();
+
+    add_update(update).await?;
+
+    Ok(alert)
+  }
+}
@@ -0,0 +1,219 @@
+use anyhow::{anyhow, Context};
+use futures::future::try_join_all;
+use komodo_client::{
+  api::write::{
+    CreateAlertSilence, CreateAlertSilenceResponse,
+    DeleteAlertSilence, DeleteAlertSilenceResponse,
+    UpdateAlertSilence, UpdateAlertSilenceResponse,
+  },
+  entities::{
+    alert_silence::AlertSilence, komodo_timestamp, user::User,
+    Operation, ResourceTarget,
+  },
+};
+use mungos::{
+  by_id::{delete_one_by_id, find_one_by_id, update_one_by_id},
+  mongodb::bson::{doc, to_bson},
+};
+use resolver_api::Resolve;
+
+use crate::{
+  helpers::{
+    query::get_tag,
+    update::{add_update, make_update},
+  },
+  state::{db_client, State},
+};
+
+impl Resolve<CreateAlertSilence, User> for State {
+  #[instrument(name = "CreateAlertSilence", skip(self, user))]
+  async fn resolve(
+    &self,
+    CreateAlertSilence {
+      comment,
+      targets,
+      tags,
+      alert_types,
+      levels,
+      start_ts,
+      end_ts,
+    }: CreateAlertSilence,
+    user: User,
+  ) -> anyhow::Result<CreateAlertSilenceResponse> {
+    if !user.admin {
+      return Err(anyhow!("only admins can create alert silences"));
+    }
+
+    let now = komodo_timestamp();
+    let start_ts = if start_ts == 0 { now } else { start_ts };
+    validate_window(start_ts, end_ts)?;
+
+    let mut silence = AlertSilence {
+      id: Default::default(),
+      comment,
+      targets,
+      tags: get_tag_ids(tags).await?,
+      alert_types,
+      levels,
+      start_ts,
+      end_ts,
+      created_by: user.id.clone(),
+      created_at: now,
+    };
+
+    silence.id = db_client()
+      .alert_silences
+      .insert_one(&silence)
+      .await
+      .context("failed to create alert silence on db")?
+      .inserted_id
+      .as_object_id()
+      .context("inserted_id is not ObjectId")?
+      .to_string();
+
+    let mut update = make_update(
+      ResourceTarget::system(),
+      Operation::CreateAlertSilence,
+      &user,
+    );
+
+    update.push_simple_log(
+      "create alert silence",
+      format!("{silence:#?}"),
+    );
+    update.finalize();
+
+    add_update(update).await?;
+
+    Ok(silence)
+  }
+}
+
+impl Resolve<UpdateAlertSilence, User> for State {
+  #[instrument(name = "UpdateAlertSilence", skip(self, user))]
+  async fn resolve(
+    &self,
+    UpdateAlertSilence {
+      id,
+      comment,
+      targets,
+      tags,
+      alert_types,
+      levels,
+      start_ts,
+      end_ts,
+    }: UpdateAlertSilence,
+    user: User,
+  ) -> anyhow::Result<UpdateAlertSilenceResponse> {
+    if !user.admin {
+      return Err(anyhow!("only admins can update alert silences"));
+    }
+
+    validate_window(start_ts, end_ts)?;
+
+    let tags = get_tag_ids(tags).await?;
+
+    update_one_by_id(
+      &db_client().alert_silences,
+      &id,
+      doc! {
+        "$set": {
+          "comment": &comment,
+          "targets": to_bson(&targets)
+            .context("failed to serialize targets")?,
+          "tags": &tags,
+          "alert_types": to_bson(&alert_types)
+            .context("failed to serialize alert types")?,
+          "levels": to_bson(&levels)
+            .context("failed to serialize levels")?,
+          "start_ts": start_ts,
+          "end_ts": end_ts,
+        }
+      },
+      None,
+    )
+    .await
+    .context("failed to update alert silence on db")?;
+
+    let silence = find_one_by_id(&db_client().alert_silences, &id)
+      .await
+      .context("failed to query db for alert silence")?
+      .with_context(|| {
+        format!("no alert silence found with id {id}")
+      })?;
+
+    let mut update = make_update(
+      ResourceTarget::system(),
+      Operation::UpdateAlertSilence,
+      &user,
+    );
+
+    update.push_simple_log(
+      "update alert silence",
+      format!("{silence:#?}"),
+    );
+    update.finalize();
+
+    add_update(update).await?;
+
+    Ok(silence)
+  }
+}
+
+impl Resolve<DeleteAlertSilence, User> for State {
+  #[instrument(name = "DeleteAlertSilence", skip(self, user))]
+  async fn resolve(
+    &self,
+    DeleteAlertSilence { id }: DeleteAlertSilence,
+    user: User,
+  ) -> anyhow::Result<DeleteAlertSilenceResponse> {
+    if !user.admin {
+      return Err(anyhow!("only admins can delete alert silences"));
+    }
+
+    let silence = find_one_by_id(&db_client().alert_silences, &id)
+      .await
+      .context("failed to query db for alert silence")?
+      .with_context(|| {
+        format!("no alert silence found with id {id}")
+      })?;
+
+    delete_one_by_id(&db_client().alert_silences, &id, None)
+      .await
+      .context("failed to delete alert silence on db")?;
+
+    let mut update = make_update(
+      ResourceTarget::system(),
+      Operation::DeleteAlertSilence,
+      &user,
+    );
+
+    update.push_simple_log(
+      "delete alert silence",
+      format!("{silence:#?}"),
+    );
+    update.finalize();
+
+    add_update(update).await?;
+
+    Ok(silence)
+  }
+}
+
+fn validate_window(start_ts: i64, end_ts: i64) -> anyhow::Result<()> {
+  if end_ts <= start_ts {
+    return Err(anyhow!(
+      "alert silence must end after it starts (end_ts > start_ts)"
+    ));
+  }
+  Ok(())
+}
+
+/// Resolves tag ids or names to tag ids.
+async fn get_tag_ids(
+  tags: Vec<String>,
+) -> anyhow::Result<Vec<String>> {
+  let tags =
+    try_join_all(tags.iter().map(|tag| get_tag(tag))).await?;
+  Ok(tags.into_iter().map(|tag| tag.id).collect())
+}
@@ -37,6 +37,12 @@ impl Resolve<CreateDeployment, User> for State {
     CreateDeployment { name, config }: CreateDeployment,
     user: User,
   ) -> anyhow::Result<Deployment> {
+    resource::check_container_name_collisions(
+      "",
+      &to_komodo_name(&name),
+      config.replicas.unwrap_or(1),
+    )
+    .await?;
     resource::create::<Deployment>(&name, config, &user).await
   }
 }
@@ -55,6 +61,12 @@ impl Resolve<CopyDeployment, User> for State {
         PermissionLevel::Write,
       )
       .await?;
+    resource::check_container_name_collisions(
+      "",
+      &to_komodo_name(&name),
+      config.replicas,
+    )
+    .await?;
     resource::create::<Deployment>(&name, config.into(), &user).await
   }
 }
@@ -146,6 +158,12 @@ impl Resolve<CreateDeploymentFromContainer, User> for State {
       });
     }
 
+    resource::check_container_name_collisions(
+      "",
+      &to_komodo_name(&name),
+      config.replicas.unwrap_or(1),
+    )
+    .await?;
     resource::create::<Deployment>(&name, config, &user).await
   }
 }
@@ -199,6 +217,13 @@ impl Resolve<RenameDeployment, User> for State {
 
     let name = to_komodo_name(&name);
 
+    resource::check_container_name_collisions(
+      &deployment.id,
+      &name,
+      deployment.config.replicas,
+    )
+    .await?;
+
     let container_state = get_deployment_state(&deployment).await?;
 
     if container_state == DeploymentState::Unknown {
@@ -224,14 +249,25 @@ impl Resolve<RenameDeployment, User> for State {
     if container_state != DeploymentState::NotDeployed {
       let server =
         resource::get::<Server>(&deployment.config.server_id).await?;
-      let log = periphery_client(&server)?
-        .request(api::container::RenameContainer {
-          curr_name: deployment.name.clone(),
-          new_name: name.clone(),
-        })
-        .await
-        .context("Failed to rename container on server")?;
-      update.logs.push(log);
+      let periphery = periphery_client(&server)?;
+      let renamed = Deployment {
+        name: name.clone(),
+        ..deployment.clone()
+      };
+      for (curr_name, new_name) in deployment
+        .container_names()
+        .into_iter()
+        .zip(renamed.container_names())
+      {
+        let log = periphery
+          .request(api::container::RenameContainer {
+            curr_name,
+            new_name,
+          })
+          .await
+          .context("Failed to rename container on server")?;
+        update.logs.push(log);
+      }
     }
 
     update.push_simple_log(
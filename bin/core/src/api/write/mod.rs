@@ -14,6 +14,8 @@ use uuid::Uuid;
 use crate::{auth::auth_request, state::State};
 
 mod action;
+mod alert;
+mod alert_silence;
 mod alerter;
 mod build;
 mod builder;
@@ -75,6 +77,9 @@ pub enum WriteRequest {
   DeleteServer(DeleteServer),
   UpdateServer(UpdateServer),
   RenameServer(RenameServer),
+  UnpinServerCertificate(UnpinServerCertificate),
+  CreateServerEnrollmentToken(CreateServerEnrollmentToken),
+  DeleteServerEnrollmentToken(DeleteServerEnrollmentToken),
   CreateNetwork(CreateNetwork),
 
   // ==== DEPLOYMENT ====
@@ -169,6 +174,14 @@ pub enum WriteRequest {
   RenameTag(RenameTag),
   UpdateTagsOnResource(UpdateTagsOnResource),
 
+  // ==== ALERT ====
+  AcknowledgeAlert(AcknowledgeAlert),
+
+  // ==== ALERT SILENCE ====
+  CreateAlertSilence(CreateAlertSilence),
+  UpdateAlertSilence(UpdateAlertSilence),
+  DeleteAlertSilence(DeleteAlertSilence),
+
   // ==== VARIABLE ====
   CreateVariable(CreateVariable),
   UpdateVariableValue(UpdateVariableValue),
@@ -1,3 +1,4 @@
+use anyhow::Context;
 use formatting::format_serror;
 use komodo_client::{
   api::write::*,
@@ -9,18 +10,22 @@ use komodo_client::{
     Operation,
   },
 };
+use mungos::{by_id::update_one_by_id, mongodb::bson::doc};
 use periphery_client::api;
 use resolver_api::Resolve;
 
 use crate::{
   helpers::{
-    periphery_client,
+    periphery_client, random_string,
+    tunnel::{close_stale_periphery_tunnel, hash_enrollment_token},
     update::{add_update, make_update, update_update},
   },
-  resource,
-  state::State,
+  resource::{self, redact_enrollment_token},
+  state::{db_client, State},
 };
 
+const ENROLLMENT_TOKEN_LENGTH: usize = 40;
+
 impl Resolve<CreateServer, User> for State {
   #[instrument(name = "CreateServer", skip(self, user))]
   async fn resolve(
@@ -50,7 +55,10 @@ impl Resolve<UpdateServer, User> for State {
     UpdateServer { id, config }: UpdateServer,
     user: User,
   ) -> anyhow::Result<Server> {
-    resource::update::<Server>(&id, config, &user).await
+    let mut server =
+      resource::update::<Server>(&id, config, &user).await?;
+    redact_enrollment_token(&mut server);
+    Ok(server)
   }
 }
 
@@ -65,6 +73,144 @@ impl Resolve<RenameServer, User> for State {
   }
 }
 
+impl Resolve<UnpinServerCertificate, User> for State {
+  #[instrument(name = "UnpinServerCertificate", skip(self, user))]
+  async fn resolve(
+    &self,
+    UnpinServerCertificate { server }: UnpinServerCertificate,
+    user: User,
+  ) -> anyhow::Result<Update> {
+    let server = resource::get_check_permissions::<Server>(
+      &server,
+      &user,
+      PermissionLevel::Write,
+    )
+    .await?;
+
+    let mut update =
+      make_update(&server, Operation::UnpinServerCertificate, &user);
+
+    update_one_by_id(
+      &db_client().servers,
+      &server.id,
+      doc! { "$set": { "info.certificate_fingerprint": "" } },
+      None,
+    )
+    .await
+    .context("failed to unpin server certificate")?;
+
+    update.push_simple_log(
+      "Unpin Certificate",
+      format!(
+        "Unpinned certificate fingerprint: {}",
+        server.info.certificate_fingerprint
+      ),
+    );
+    update.finalize();
+    update.id = add_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
+impl Resolve<CreateServerEnrollmentToken, User> for State {
+  #[instrument(
+    name = "CreateServerEnrollmentToken",
+    skip(self, user)
+  )]
+  async fn resolve(
+    &self,
+    CreateServerEnrollmentToken { server }: CreateServerEnrollmentToken,
+    user: User,
+  ) -> anyhow::Result<CreateServerEnrollmentTokenResponse> {
+    let server = resource::get_check_permissions::<Server>(
+      &server,
+      &user,
+      PermissionLevel::Write,
+    )
+    .await?;
+
+    let mut update = make_update(
+      &server,
+      Operation::CreateServerEnrollmentToken,
+      &user,
+    );
+
+    let token = random_string(ENROLLMENT_TOKEN_LENGTH);
+    let token_hash = hash_enrollment_token(&token);
+
+    update_one_by_id(
+      &db_client().servers,
+      &server.id,
+      doc! { "$set": { "info.enrollment_token_hash": &token_hash } },
+      None,
+    )
+    .await
+    .context("failed to set server enrollment token")?;
+
+    // Connections made with a previous token are closed.
+    close_stale_periphery_tunnel(&server.id, &token_hash);
+
+    update.push_simple_log(
+      "Create Enrollment Token",
+      if server.info.enrollment_token_hash.is_empty() {
+        "Created enrollment token"
+      } else {
+        "Replaced enrollment token"
+      },
+    );
+    update.finalize();
+    add_update(update).await?;
+
+    Ok(CreateServerEnrollmentTokenResponse { token })
+  }
+}
+
+impl Resolve<DeleteServerEnrollmentToken, User> for State {
+  #[instrument(
+    name = "DeleteServerEnrollmentToken",
+    skip(self, user)
+  )]
+  async fn resolve(
+    &self,
+    DeleteServerEnrollmentToken { server }: DeleteServerEnrollmentToken,
+    user: User,
+  ) -> anyhow::Result<Update> {
+    let server = resource::get_check_permissions::<Server>(
+      &server,
+      &user,
+      PermissionLevel::Write,
+    )
+    .await?;
+
+    let mut update = make_update(
+      &server,
+      Operation::DeleteServerEnrollmentToken,
+      &user,
+    );
+
+    update_one_by_id(
+      &db_client().servers,
+      &server.id,
+      doc! { "$set": { "info.enrollment_token_hash": "" } },
+      None,
+    )
+    .await
+    .context("failed to delete server enrollment token")?;
+
+    close_stale_periphery_tunnel(&server.id, "");
+
+    update.push_simple_log(
+      "Delete Enrollment Token",
+      "Deleted enrollment token",
+    );
+    update.finalize();
+    update.id = add_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
 impl Resolve<CreateNetwork, User> for State {
   #[instrument(name = "CreateNetwork", skip(self, user))]
   async fn resolve(
@@ -41,7 +41,7 @@ use resolver_api::Resolve;
 use tokio::fs;
 
 use crate::{
-  alert::send_alerts,
+  alert::{apply_silences, send_alerts},
   config::core_config,
   helpers::{
     query::get_id_to_tags,
@@ -704,7 +704,7 @@ impl Resolve<RefreshResourceSyncPending, User> for State {
       match (existing, has_updates) {
         // OPEN A NEW ALERT
         (None, true) => {
-          let alert = Alert {
+          let mut alert = Alert {
             id: Default::default(),
             ts: komodo_timestamp(),
             resolved: false,
@@ -712,7 +712,12 @@ impl Resolve<RefreshResourceSyncPending, User> for State {
             target: ResourceTarget::ResourceSync(id.clone()),
             data: AlertData::ResourceSyncPendingUpdates { id, name },
             resolved_ts: None,
+            silenced: false,
+            acknowledged: false,
+            acknowledged_by: None,
+            acknowledged_ts: None,
           };
+          apply_silences(vec![&mut alert]).await;
           db.alerts
             .insert_one(&alert)
             .await
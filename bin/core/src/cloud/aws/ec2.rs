@@ -180,6 +180,10 @@ pub async fn terminate_ec2_instance_with_retry(
               message: format!("{e:#}"),
             },
             resolved_ts: None,
+            silenced: false,
+            acknowledged: false,
+            acknowledged_by: None,
+            acknowledged_ts: None,
           };
           send_alerts(&[alert]).await;
           return Err(e);
@@ -58,8 +58,12 @@ pub fn core_config() -> &'static CoreConfig {
       jwt_secret: maybe_read_item_from_file(env.komodo_jwt_secret_file, env.komodo_jwt_secret).unwrap_or(config.jwt_secret),
       passkey: maybe_read_item_from_file(env.komodo_passkey_file, env.komodo_passkey)
         .unwrap_or(config.passkey),
+      disable_periphery_passkey_header: env.komodo_disable_periphery_passkey_header
+        .unwrap_or(config.disable_periphery_passkey_header),
       webhook_secret: maybe_read_item_from_file(env.komodo_webhook_secret_file, env.komodo_webhook_secret)
         .unwrap_or(config.webhook_secret),
+      metrics_token: maybe_read_item_from_file(env.komodo_metrics_token_file, env.komodo_metrics_token)
+        .unwrap_or(config.metrics_token),
       database: DatabaseConfig {
         uri: maybe_read_item_from_file(env.komodo_database_uri_file,env.komodo_database_uri).unwrap_or(config.database.uri),
         address: env.komodo_database_address.unwrap_or(config.database.address),
@@ -162,6 +166,12 @@ pub fn core_config() -> &'static CoreConfig {
       keep_stats_for_days: env
         .komodo_keep_stats_for_days
         .unwrap_or(config.keep_stats_for_days),
+      keep_hourly_stats_for_days: env
+        .komodo_keep_hourly_stats_for_days
+        .unwrap_or(config.keep_hourly_stats_for_days),
+      keep_daily_stats_for_days: env
+        .komodo_keep_daily_stats_for_days
+        .unwrap_or(config.keep_daily_stats_for_days),
       keep_alerts_for_days: env
         .komodo_keep_alerts_for_days
         .unwrap_or(config.keep_alerts_for_days),
@@ -201,6 +211,8 @@ pub fn core_config() -> &'static CoreConfig {
       ssl_enabled: env.komodo_ssl_enabled.unwrap_or(config.ssl_enabled),
       ssl_key_file: env.komodo_ssl_key_file.unwrap_or(config.ssl_key_file),
       ssl_cert_file: env.komodo_ssl_cert_file.unwrap_or(config.ssl_cert_file),
+      periphery_client_cert_file: env.komodo_periphery_client_cert_file.or(config.periphery_client_cert_file),
+      periphery_client_key_file: env.komodo_periphery_client_key_file.or(config.periphery_client_key_file),
 
       // These can't be overridden on env
       secrets: config.secrets,
@@ -1,6 +1,7 @@
 use komodo_client::entities::{
   action::Action,
   alert::Alert,
+  alert_silence::AlertSilence,
   alerter::Alerter,
   api_key::ApiKey,
   build::Build,
@@ -14,7 +15,9 @@ use komodo_client::entities::{
   server::Server,
   server_template::ServerTemplate,
   stack::Stack,
-  stats::SystemStatsRecord,
+  stats::{
+    ContainerStatsRecord, SystemStatsRecord, SystemStatsRollup,
+  },
   sync::ResourceSync,
   tag::Tag,
   update::Update,
@@ -40,7 +43,10 @@ pub struct DbClient {
   pub registry_accounts: Collection<DockerRegistryAccount>,
   pub updates: Collection<Update>,
   pub alerts: Collection<Alert>,
+  pub alert_silences: Collection<AlertSilence>,
   pub stats: Collection<SystemStatsRecord>,
+  pub stats_rollups: Collection<SystemStatsRollup>,
+  pub container_stats: Collection<ContainerStatsRecord>,
   // RESOURCES
   pub servers: Collection<Server>,
   pub deployments: Collection<Deployment>,
@@ -108,7 +114,10 @@ impl DbClient {
       registry_accounts: mongo_indexed::collection(&db, true).await?,
       updates: mongo_indexed::collection(&db, true).await?,
       alerts: mongo_indexed::collection(&db, true).await?,
+      alert_silences: mongo_indexed::collection(&db, true).await?,
       stats: mongo_indexed::collection(&db, true).await?,
+      stats_rollups: mongo_indexed::collection(&db, true).await?,
+      container_stats: mongo_indexed::collection(&db, true).await?,
       // RESOURCES
       servers: resource_collection(&db, "Server").await?,
       deployments: resource_collection(&db, "Deployment").await?,
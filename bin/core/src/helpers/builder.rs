@@ -55,6 +55,9 @@ pub async fn get_builder_periphery(
         } else {
           config.passkey
         },
+      )
+      .with_passkey_header(
+        !core_config().disable_periphery_passkey_header,
       );
       periphery
         .health_check()
@@ -122,7 +125,10 @@ async fn get_aws_builder(
   let periphery_address =
     format!("{protocol}://{ip}:{}", config.port);
   let periphery =
-    PeripheryClient::new(&periphery_address, &core_config().passkey);
+    PeripheryClient::new(&periphery_address, &core_config().passkey)
+      .with_passkey_header(
+        !core_config().disable_periphery_passkey_header,
+      );
 
   let start_connect_ts = komodo_timestamp();
   let mut res = Ok(GetVersionResponse {
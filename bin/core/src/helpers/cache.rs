@@ -58,6 +58,17 @@ impl<
     handler(cache.entry(key.into()).or_default());
   }
 
+  /// Modifies the entry under the write lock, if it exists,
+  /// returning the handler's result.
+  #[instrument(level = "debug", skip(self, handler))]
+  pub async fn modify<R>(
+    &self,
+    key: &K,
+    handler: impl FnOnce(&mut T) -> R,
+  ) -> Option<R> {
+    self.cache.write().await.get_mut(key).map(handler)
+  }
+
   #[instrument(level = "debug", skip(self))]
   pub async fn clear(&self) {
     self.cache.write().await.clear();
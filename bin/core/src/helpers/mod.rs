@@ -38,6 +38,7 @@ pub mod interpolate;
 pub mod procedure;
 pub mod prune;
 pub mod query;
+pub mod tunnel;
 pub mod update;
 
 // pub mod resource;
@@ -142,14 +143,50 @@ pub fn periphery_client(
     return Err(anyhow!("server not enabled"));
   }
 
+  if !server.info.enrollment_token_hash.is_empty() {
+    let tunnel = tunnel::get_periphery_tunnel(server)
+      .context("periphery has not connected to core")?;
+    return Ok(PeripheryClient::tunneled(tunnel));
+  }
+
   let client = PeripheryClient::new(
     &server.config.address,
     &core_config().passkey,
-  );
+  )
+  .with_passkey_header(
+    !core_config().disable_periphery_passkey_header,
+  )
+  .with_pinned_certificate(&server.info.certificate_fingerprint);
 
   Ok(client)
 }
 
+/// Loads the client certificate presented to periphery, if configured.
+pub fn init_periphery_client_identity() -> anyhow::Result<()> {
+  let config = core_config();
+  let (cert_file, key_file) = match (
+    &config.periphery_client_cert_file,
+    &config.periphery_client_key_file,
+  ) {
+    (Some(cert_file), Some(key_file)) => (cert_file, key_file),
+    (None, None) => return Ok(()),
+    _ => {
+      return Err(anyhow!(
+        "must configure both periphery_client_cert_file and periphery_client_key_file"
+      ))
+    }
+  };
+  let cert = std::fs::read(cert_file).with_context(|| {
+    format!("failed to read periphery client cert at {cert_file:?}")
+  })?;
+  let key = std::fs::read(key_file).with_context(|| {
+    format!("failed to read periphery client key at {key_file:?}")
+  })?;
+  periphery_client::set_client_identity(cert, key)?;
+  info!("🔒 Periphery client certificate loaded");
+  Ok(())
+}
+
 #[instrument]
 pub async fn create_permission<T>(
   user: &User,
@@ -59,12 +59,9 @@ pub async fn execute_procedure(
       update,
     )
     .await
-    .with_context(|| {
-      format!(
-        "Failed stage '{}' execution after {:?}",
-        bold(&stage.name),
-        timer.elapsed(),
-      )
+    .with_context(|| FailedStage {
+      name: stage.name.clone(),
+      elapsed: timer.elapsed(),
     })?;
     add_line_to_update(
       update,
@@ -82,6 +79,26 @@ pub async fn execute_procedure(
   Ok(())
 }
 
+/// Error context attached when a procedure stage fails.
+/// Can be recovered from the error with `downcast_ref`
+/// to find the name of the failed stage.
+#[derive(Debug)]
+pub struct FailedStage {
+  pub name: String,
+  elapsed: Duration,
+}
+
+impl std::fmt::Display for FailedStage {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "Failed stage '{}' execution after {:?}",
+      bold(&self.name),
+      self.elapsed,
+    )
+  }
+}
+
 #[allow(dependency_on_unit_never_type_fallback)]
 #[instrument(skip(update))]
 async fn execute_stage(
@@ -339,6 +356,22 @@ async fn execute_execution(
       )
       .await?
     }
+    Execution::RollbackDeployment(req) => {
+      let req = ExecuteRequest::RollbackDeployment(req);
+      let update = init_execution_update(&req, &user).await?;
+      let ExecuteRequest::RollbackDeployment(req) = req else {
+        unreachable!()
+      };
+      let update_id = update.id.clone();
+      handle_resolve_result(
+        State
+          .resolve(req, (user, update))
+          .await
+          .context("Failed at RollbackDeployment"),
+        &update_id,
+      )
+      .await?
+    }
     Execution::StartDeployment(req) => {
       let req = ExecuteRequest::StartDeployment(req);
       let update = init_execution_update(&req, &user).await?;
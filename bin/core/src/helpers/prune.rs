@@ -1,8 +1,11 @@
+use std::future::IntoFuture;
+
 use anyhow::Context;
 use async_timing_util::{
   unix_timestamp_ms, wait_until_timelength, Timelength, ONE_DAY_MS,
 };
 use futures::future::join_all;
+use komodo_client::entities::stats::StatsResolution;
 use mungos::{find::find_collect, mongodb::bson::doc};
 use periphery_client::api::image::PruneImages;
 
@@ -59,21 +62,69 @@ async fn prune_images() -> anyhow::Result<()> {
 }
 
 async fn prune_stats() -> anyhow::Result<()> {
+  let (raw, rollups) =
+    tokio::join!(prune_raw_stats(), prune_stats_rollups());
+  raw.and(rollups)
+}
+
+async fn prune_raw_stats() -> anyhow::Result<()> {
   if core_config().keep_stats_for_days == 0 {
     return Ok(());
   }
   let delete_before_ts = (unix_timestamp_ms()
     - core_config().keep_stats_for_days as u128 * ONE_DAY_MS)
     as i64;
-  let res = db_client()
-    .stats
-    .delete_many(doc! {
-      "ts": { "$lt": delete_before_ts }
-    })
-    .await?;
+  let db = db_client();
+  let (res, container_res) = tokio::try_join!(
+    db.stats
+      .delete_many(doc! {
+        "ts": { "$lt": delete_before_ts }
+      })
+      .into_future(),
+    db.container_stats
+      .delete_many(doc! {
+        "ts": { "$lt": delete_before_ts }
+      })
+      .into_future(),
+  )?;
   if res.deleted_count > 0 {
     info!("deleted {} stats from db", res.deleted_count);
   }
+  if container_res.deleted_count > 0 {
+    info!(
+      "deleted {} container stats from db",
+      container_res.deleted_count
+    );
+  }
+  Ok(())
+}
+
+async fn prune_stats_rollups() -> anyhow::Result<()> {
+  let config = core_config();
+  for (resolution, keep_for_days) in [
+    (StatsResolution::Hour, config.keep_hourly_stats_for_days),
+    (StatsResolution::Day, config.keep_daily_stats_for_days),
+  ] {
+    if keep_for_days == 0 {
+      continue;
+    }
+    let delete_before_ts = (unix_timestamp_ms()
+      - keep_for_days as u128 * ONE_DAY_MS)
+      as i64;
+    let res = db_client()
+      .stats_rollups
+      .delete_many(doc! {
+        "resolution": resolution.to_string(),
+        "ts": { "$lt": delete_before_ts }
+      })
+      .await?;
+    if res.deleted_count > 0 {
+      info!(
+        "deleted {} {resolution} stats rollups from db",
+        res.deleted_count
+      );
+    }
+  }
   Ok(())
 }
 
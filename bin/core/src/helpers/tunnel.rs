@@ -0,0 +1,93 @@
+use std::{
+  collections::HashMap,
+  sync::{OnceLock, RwLock},
+};
+
+use komodo_client::entities::server::Server;
+use periphery_client::tunnel::PeripheryTunnel;
+use sha2::{Digest, Sha256};
+
+/// Only the SHA-256 hash of enrollment tokens is stored.
+pub fn hash_enrollment_token(token: &str) -> String {
+  hex::encode(Sha256::digest(token))
+}
+
+struct ConnectedTunnel {
+  /// The hash of the enrollment token the connection authenticated with.
+  enrollment_token_hash: String,
+  tunnel: PeripheryTunnel,
+}
+
+/// Periphery reverse connections, keyed by server id.
+/// Dropping a tunnel closes its connection.
+fn periphery_tunnels(
+) -> &'static RwLock<HashMap<String, ConnectedTunnel>> {
+  static PERIPHERY_TUNNELS: OnceLock<
+    RwLock<HashMap<String, ConnectedTunnel>>,
+  > = OnceLock::new();
+  PERIPHERY_TUNNELS.get_or_init(Default::default)
+}
+
+/// Gets the connection for the server,
+/// if it was made with the current enrollment token.
+pub fn get_periphery_tunnel(
+  server: &Server,
+) -> Option<PeripheryTunnel> {
+  let tunnels = periphery_tunnels().read().unwrap();
+  let connected = tunnels.get(&server.id)?;
+  if connected.enrollment_token_hash
+    != server.info.enrollment_token_hash
+    || connected.tunnel.is_closed()
+  {
+    return None;
+  }
+  Some(connected.tunnel.clone())
+}
+
+/// Replaces any existing connection for the server.
+pub fn insert_periphery_tunnel(
+  server: &Server,
+  tunnel: PeripheryTunnel,
+) {
+  periphery_tunnels().write().unwrap().insert(
+    server.id.clone(),
+    ConnectedTunnel {
+      enrollment_token_hash: server
+        .info
+        .enrollment_token_hash
+        .clone(),
+      tunnel,
+    },
+  );
+}
+
+/// Removes the connection, if it hasn't already been replaced.
+pub fn remove_periphery_tunnel(
+  server_id: &str,
+  tunnel: &PeripheryTunnel,
+) {
+  let mut tunnels = periphery_tunnels().write().unwrap();
+  if tunnels
+    .get(server_id)
+    .map(|connected| connected.tunnel.same_tunnel(tunnel))
+    .unwrap_or_default()
+  {
+    tunnels.remove(server_id);
+  }
+}
+
+/// Closes the server connection if the enrollment token has changed,
+/// or the server was deleted.
+pub fn close_stale_periphery_tunnel(
+  server_id: &str,
+  token_hash: &str,
+) {
+  let mut tunnels = periphery_tunnels().write().unwrap();
+  if tunnels
+    .get(server_id)
+    .map(|connected| connected.enrollment_token_hash != token_hash)
+    .unwrap_or_default()
+  {
+    tunnels.remove(server_id);
+  }
+}
@@ -20,7 +20,7 @@ use mungos::{
 };
 
 use crate::{
-  api::execute::ExecuteRequest, resource, state::db_client,
+  api::execute::ExecuteRequest, metrics, resource, state::db_client,
 };
 
 use super::channel::update_channel;
@@ -77,6 +77,7 @@ pub async fn add_update_without_send(
 
 #[instrument(level = "debug")]
 pub async fn update_update(update: Update) -> anyhow::Result<()> {
+  metrics::execution_completed(&update);
   update_one_by_id(&db_client().updates, &update.id, mungos::update::Update::Set(to_document(&update)?), None)
     .await
     .context("failed to update the update on db. the update build process was deleted")?;
@@ -270,6 +271,12 @@ pub async fn init_execution_update(
         resource::get::<Deployment>(&data.deployment).await?.id,
       ),
     ),
+    ExecuteRequest::RollbackDeployment(data) => (
+      Operation::RollbackDeployment,
+      ResourceTarget::Deployment(
+        resource::get::<Deployment>(&data.deployment).await?.id,
+      ),
+    ),
     ExecuteRequest::StartDeployment(data) => (
       Operation::StartDeployment,
       ResourceTarget::Deployment(
@@ -505,6 +512,7 @@ pub async fn init_execution_update(
   if !matches!(&request, ExecuteRequest::DeployStackIfChanged(_)) {
     // Don't actually send it here, let the handlers send it after they can set action state.
     update.id = add_update_without_send(&update).await?;
+    metrics::execution_started(&update.id);
   }
   Ok(update)
 }
@@ -21,8 +21,10 @@ mod config;
 mod db;
 mod helpers;
 mod listener;
+mod metrics;
 mod monitor;
 mod resource;
+mod schedule;
 mod stack;
 mod state;
 mod sync;
@@ -37,6 +39,8 @@ async fn app() -> anyhow::Result<()> {
   info!("Komodo Core version: v{}", env!("CARGO_PKG_VERSION"));
   info!("{:?}", config.sanitized());
 
+  helpers::init_periphery_client_identity()?;
+
   tokio::join!(
     // Init db_client check to crash on db init failure
     state::init_db_client(),
@@ -51,9 +55,12 @@ async fn app() -> anyhow::Result<()> {
   );
   // init jwt client to crash on failure
   state::jwt_client();
+  // Load Procedure / Action schedules
+  schedule::init_schedules().await;
 
   // Spawn tasks
   monitor::spawn_monitor_loop();
+  monitor::rollup::spawn_stats_rollup_loop();
   resource::spawn_resource_refresh_loop();
   resource::spawn_build_state_refresh_loop();
   resource::spawn_repo_state_refresh_loop();
@@ -61,6 +68,7 @@ async fn app() -> anyhow::Result<()> {
   resource::spawn_action_state_refresh_loop();
   resource::spawn_resource_sync_state_refresh_loop();
   helpers::prune::spawn_prune_loop();
+  schedule::spawn_schedule_executor();
 
   // Setup static frontend services
   let frontend_path = &config.frontend_path;
@@ -76,6 +84,7 @@ async fn app() -> anyhow::Result<()> {
     .nest("/write", api::write::router())
     .nest("/execute", api::execute::router())
     .nest("/listener", listener::router())
+    .nest("/metrics", metrics::router())
     .nest("/ws", ws::router())
     .nest("/client", ts_client::router())
     .nest_service("/", serve_dir)
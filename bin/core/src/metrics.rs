@@ -0,0 +1,531 @@
+use std::{
+  collections::{HashMap, HashSet},
+  fmt::Write,
+  str::FromStr,
+  sync::{Mutex, OnceLock},
+};
+
+use anyhow::{anyhow, Context};
+use axum::{
+  http::{header::CONTENT_TYPE, HeaderMap, HeaderValue},
+  routing::get,
+  Router,
+};
+use derive_variants::ExtractVariant;
+use komodo_client::entities::{
+  alert::SeverityLevel,
+  build::Build,
+  repo::Repo,
+  server::{Server, ServerState},
+  update::{Update, UpdateStatus},
+};
+use mungos::{
+  find::find_collect,
+  mongodb::bson::{doc, oid::ObjectId},
+};
+use reqwest::StatusCode;
+use serror::AddStatusCode;
+use sha2::{Digest, Sha256};
+
+use crate::{
+  auth::auth_api_key_check_enabled,
+  config::core_config,
+  state::{
+    build_state_cache, db_client, deployment_status_cache,
+    repo_state_cache, server_status_cache, stack_status_cache,
+  },
+};
+
+const BYTES_PER_GB: f64 = 1073741824.0;
+
+pub fn router() -> Router {
+  Router::new().route("/", get(handler))
+}
+
+async fn handler(
+  headers: HeaderMap,
+) -> serror::Result<(HeaderMap, String)> {
+  authenticate(&headers)
+    .await
+    .status_code(StatusCode::UNAUTHORIZED)?;
+  let metrics = metrics().await?;
+  let mut headers = HeaderMap::new();
+  headers.append(
+    CONTENT_TYPE,
+    HeaderValue::from_static("text/plain; version=0.0.4"),
+  );
+  Ok((headers, metrics))
+}
+
+/// Accepts either `Authorization: Bearer <metrics_token>`,
+/// or the api key and secret of an admin user.
+async fn authenticate(headers: &HeaderMap) -> anyhow::Result<()> {
+  match (
+    headers.get("authorization"),
+    headers.get("x-api-key"),
+    headers.get("x-api-secret"),
+  ) {
+    (Some(token), _, _) => {
+      let token = token
+        .to_str()
+        .context("authorization is not str")?
+        .trim_start_matches("Bearer ");
+      let metrics_token = &core_config().metrics_token;
+      if metrics_token.is_empty() || !tokens_match(token, metrics_token)
+      {
+        return Err(anyhow!("invalid metrics token"));
+      }
+      Ok(())
+    }
+    (None, Some(key), Some(secret)) => {
+      let key = key.to_str().context("key is not str")?;
+      let secret = secret.to_str().context("secret is not str")?;
+      let user = auth_api_key_check_enabled(key, secret)
+        .await
+        .context("failed to authenticate api key")?;
+      if !user.admin {
+        return Err(anyhow!("metrics are only available to admins"));
+      }
+      Ok(())
+    }
+    _ => Err(anyhow!(
+      "must attach either AUTHORIZATION header with metrics token OR pass X-API-KEY and X-API-SECRET"
+    )),
+  }
+}
+
+/// Compares the SHA-256 digests of the tokens without
+/// short circuiting, so the comparison takes constant time.
+fn tokens_match(a: &str, b: &str) -> bool {
+  let (a, b) = (Sha256::digest(a), Sha256::digest(b));
+  a.iter()
+    .zip(b.iter())
+    .fold(0, |diff, (a, b)| diff | (a ^ b))
+    == 0
+}
+
+async fn metrics() -> anyhow::Result<String> {
+  let db = db_client();
+  let (servers, deployments, stacks, builds, repos) =
+    tokio::try_join!(
+      find_collect(&db.servers, None, None),
+      find_collect(&db.deployments, None, None),
+      find_collect(&db.stacks, None, None),
+      find_collect(&db.builds, None, None),
+      find_collect(&db.repos, None, None),
+    )
+    .context("failed to query resources from db")?;
+
+  let server_names = servers
+    .iter()
+    .map(|server| (server.id.as_str(), server.name.as_str()))
+    .collect::<HashMap<_, _>>();
+
+  let mut out = Metrics::default();
+
+  server_metrics(&mut out, &servers).await;
+
+  out.family(
+    "komodo_deployment_state",
+    "gauge",
+    "The current state of the deployment container.",
+  );
+  for deployment in &deployments {
+    let state = deployment_status_cache()
+      .get(&deployment.id)
+      .await
+      .map(|status| status.curr.state)
+      .unwrap_or_default();
+    out.sample(
+      "komodo_deployment_state",
+      &[
+        ("id", &deployment.id),
+        ("name", &deployment.name),
+        (
+          "server",
+          server_names
+            .get(deployment.config.server_id.as_str())
+            .unwrap_or(&""),
+        ),
+        ("state", &state.to_string()),
+      ],
+      1.0,
+    );
+  }
+
+  out.family(
+    "komodo_stack_state",
+    "gauge",
+    "The current state of the stack.",
+  );
+  for stack in &stacks {
+    let state = stack_status_cache()
+      .get(&stack.id)
+      .await
+      .map(|status| status.curr.state)
+      .unwrap_or_default();
+    out.sample(
+      "komodo_stack_state",
+      &[
+        ("id", &stack.id),
+        ("name", &stack.name),
+        (
+          "server",
+          server_names
+            .get(stack.config.server_id.as_str())
+            .unwrap_or(&""),
+        ),
+        ("state", &state.to_string()),
+      ],
+      1.0,
+    );
+  }
+
+  build_repo_metrics(&mut out, &builds, &repos).await;
+  alert_metrics(&mut out).await?;
+  if let Err(e) = prune_executions_in_progress().await {
+    warn!("failed to prune executions in progress | {e:#}");
+  }
+  execution_metrics(&mut out);
+
+  Ok(out.0)
+}
+
+async fn server_metrics(out: &mut Metrics, servers: &[Server]) {
+  let mut statuses = Vec::with_capacity(servers.len());
+  for server in servers {
+    statuses
+      .push((server, server_status_cache().get(&server.id).await));
+  }
+
+  out.family(
+    "komodo_server_up",
+    "gauge",
+    "Whether the server is reachable (1), or not (0).",
+  );
+  for (server, status) in &statuses {
+    let up = status
+      .as_ref()
+      .map(|status| status.state == ServerState::Ok)
+      .unwrap_or_default();
+    out.sample(
+      "komodo_server_up",
+      &server_labels(server),
+      if up { 1.0 } else { 0.0 },
+    );
+  }
+
+  let stats = statuses
+    .iter()
+    .filter_map(|(server, status)| {
+      let stats = status.as_ref()?.stats.as_ref()?;
+      Some((server_labels(server), stats))
+    })
+    .collect::<Vec<_>>();
+
+  out.family(
+    "komodo_server_cpu_usage_percent",
+    "gauge",
+    "The server cpu usage percentage.",
+  );
+  for (labels, stats) in &stats {
+    out.sample(
+      "komodo_server_cpu_usage_percent",
+      labels,
+      stats.cpu_perc as f64,
+    );
+  }
+
+  out.family(
+    "komodo_server_memory_used_bytes",
+    "gauge",
+    "The server memory in use.",
+  );
+  for (labels, stats) in &stats {
+    out.sample(
+      "komodo_server_memory_used_bytes",
+      labels,
+      stats.mem_used_gb * BYTES_PER_GB,
+    );
+  }
+
+  out.family(
+    "komodo_server_memory_total_bytes",
+    "gauge",
+    "The server total memory.",
+  );
+  for (labels, stats) in &stats {
+    out.sample(
+      "komodo_server_memory_total_bytes",
+      labels,
+      stats.mem_total_gb * BYTES_PER_GB,
+    );
+  }
+
+  out.family(
+    "komodo_server_disk_used_bytes",
+    "gauge",
+    "The disk space in use on the server mount.",
+  );
+  for (labels, stats) in &stats {
+    for disk in &stats.disks {
+      let mount = disk.mount.display().to_string();
+      let mut labels = labels.clone();
+      labels.push(("mount", &mount));
+      out.sample(
+        "komodo_server_disk_used_bytes",
+        &labels,
+        disk.used_gb * BYTES_PER_GB,
+      );
+    }
+  }
+
+  out.family(
+    "komodo_server_disk_total_bytes",
+    "gauge",
+    "The total disk space on the server mount.",
+  );
+  for (labels, stats) in &stats {
+    for disk in &stats.disks {
+      let mount = disk.mount.display().to_string();
+      let mut labels = labels.clone();
+      labels.push(("mount", &mount));
+      out.sample(
+        "komodo_server_disk_total_bytes",
+        &labels,
+        disk.total_gb * BYTES_PER_GB,
+      );
+    }
+  }
+}
+
+fn server_labels(server: &Server) -> Vec<(&'static str, &str)> {
+  vec![("id", &server.id), ("name", &server.name)]
+}
+
+async fn build_repo_metrics(
+  out: &mut Metrics,
+  builds: &[Build],
+  repos: &[Repo],
+) {
+  out.family(
+    "komodo_build_state",
+    "gauge",
+    "The current state of the build.",
+  );
+  for build in builds {
+    let state =
+      build_state_cache().get(&build.id).await.unwrap_or_default();
+    out.sample(
+      "komodo_build_state",
+      &[
+        ("id", &build.id),
+        ("name", &build.name),
+        ("state", &state.to_string()),
+      ],
+      1.0,
+    );
+  }
+
+  out.family(
+    "komodo_repo_state",
+    "gauge",
+    "The current state of the repo.",
+  );
+  for repo in repos {
+    let state =
+      repo_state_cache().get(&repo.id).await.unwrap_or_default();
+    out.sample(
+      "komodo_repo_state",
+      &[
+        ("id", &repo.id),
+        ("name", &repo.name),
+        ("state", &state.to_string()),
+      ],
+      1.0,
+    );
+  }
+}
+
+async fn alert_metrics(out: &mut Metrics) -> anyhow::Result<()> {
+  out.family(
+    "komodo_open_alerts",
+    "gauge",
+    "The number of unresolved alerts by severity.",
+  );
+  for level in [SeverityLevel::Warning, SeverityLevel::Critical] {
+    let count = db_client()
+      .alerts
+      .count_documents(doc! {
+        "resolved": false,
+        "level": level.to_string(),
+      })
+      .await
+      .context("failed to count open alerts")?;
+    out.sample(
+      "komodo_open_alerts",
+      &[("severity", &level.to_string())],
+      count as f64,
+    );
+  }
+  Ok(())
+}
+
+/// The totals of completed executions by (operation, resource type, success).
+type ExecutionTotals = HashMap<(String, String, bool), (u64, i64)>;
+
+fn execution_totals() -> &'static Mutex<ExecutionTotals> {
+  static TOTALS: OnceLock<Mutex<ExecutionTotals>> = OnceLock::new();
+  TOTALS.get_or_init(Default::default)
+}
+
+/// The ids of execution updates which haven't completed yet.
+fn executions_in_progress() -> &'static Mutex<HashSet<String>> {
+  static IN_PROGRESS: OnceLock<Mutex<HashSet<String>>> =
+    OnceLock::new();
+  IN_PROGRESS.get_or_init(Default::default)
+}
+
+/// Call when the update for an execution is created.
+pub fn execution_started(update_id: &str) {
+  if update_id.is_empty() {
+    return;
+  }
+  executions_in_progress()
+    .lock()
+    .unwrap()
+    .insert(update_id.to_string());
+}
+
+/// Call when an update is completed. Only counts
+/// the first completion of updates for executions.
+pub fn execution_completed(update: &Update) {
+  if update.status != UpdateStatus::Complete
+    || !executions_in_progress().lock().unwrap().remove(&update.id)
+  {
+    return;
+  }
+  let key = (
+    update.operation.to_string(),
+    update.target.extract_variant().to_string(),
+    update.success,
+  );
+  let duration_ms =
+    update.end_ts.unwrap_or(update.start_ts) - update.start_ts;
+  let mut totals = execution_totals().lock().unwrap();
+  let (count, total_duration_ms) = totals.entry(key).or_default();
+  *count += 1;
+  *total_duration_ms += duration_ms;
+}
+
+/// Forgets executions whose update is no longer in progress
+/// without passing through `execution_completed`,
+/// eg. when the execution returned early on an error.
+async fn prune_executions_in_progress() -> anyhow::Result<()> {
+  let queried = executions_in_progress().lock().unwrap().clone();
+  if queried.is_empty() {
+    return Ok(());
+  }
+  let ids = queried
+    .iter()
+    .filter_map(|id| ObjectId::from_str(id).ok())
+    .collect::<Vec<_>>();
+  let in_progress = db_client()
+    .updates
+    .distinct(
+      "_id",
+      doc! { "_id": { "$in": ids }, "status": "InProgress" },
+    )
+    .await?
+    .into_iter()
+    .filter_map(|id| id.as_object_id().map(|id| id.to_hex()))
+    .collect::<HashSet<_>>();
+  // Executions started since the query are kept.
+  executions_in_progress()
+    .lock()
+    .unwrap()
+    .retain(|id| !queried.contains(id) || in_progress.contains(id));
+  Ok(())
+}
+
+fn execution_metrics(out: &mut Metrics) {
+  let totals = execution_totals()
+    .lock()
+    .unwrap()
+    .iter()
+    .map(|((operation, resource_type, success), totals)| {
+      (operation.clone(), resource_type.clone(), *success, *totals)
+    })
+    .collect::<Vec<_>>();
+
+  out.family(
+    "komodo_executions_total",
+    "counter",
+    "The number of completed executions by operation and outcome.",
+  );
+  for (operation, resource_type, success, (count, _)) in &totals {
+    out.sample(
+      "komodo_executions_total",
+      &execution_labels(operation, resource_type, *success),
+      *count as f64,
+    );
+  }
+
+  out.family(
+    "komodo_execution_duration_seconds_total",
+    "counter",
+    "The total time spent on completed executions by operation and outcome.",
+  );
+  for (operation, resource_type, success, (_, duration_ms)) in &totals
+  {
+    out.sample(
+      "komodo_execution_duration_seconds_total",
+      &execution_labels(operation, resource_type, *success),
+      *duration_ms as f64 / 1000.0,
+    );
+  }
+}
+
+fn execution_labels<'a>(
+  operation: &'a str,
+  resource_type: &'a str,
+  success: bool,
+) -> [(&'static str, &'a str); 3] {
+  [
+    ("operation", operation),
+    ("resource_type", resource_type),
+    ("success", if success { "true" } else { "false" }),
+  ]
+}
+
+/// Prometheus text format output
+#[derive(Default)]
+struct Metrics(String);
+
+impl Metrics {
+  fn family(&mut self, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(self.0, "# HELP {name} {help}");
+    let _ = writeln!(self.0, "# TYPE {name} {kind}");
+  }
+
+  fn sample(
+    &mut self,
+    name: &str,
+    labels: &[(&str, &str)],
+    value: f64,
+  ) {
+    let labels = labels
+      .iter()
+      .map(|(label, value)| format!("{label}=\"{}\"", escape(value)))
+      .collect::<Vec<_>>()
+      .join(",");
+    let _ = writeln!(self.0, "{name}{{{labels}}} {value}");
+  }
+}
+
+fn escape(value: &str) -> String {
+  value
+    .replace('\\', "\\\\")
+    .replace('"', "\\\"")
+    .replace('\n', "\\n")
+}
@@ -0,0 +1,394 @@
+use std::{
+  collections::{HashMap, HashSet, VecDeque},
+  str::FromStr,
+  sync::{Mutex, OnceLock},
+};
+
+use anyhow::Context;
+use komodo_client::entities::{
+  alert::{Alert, AlertData, AlertDataVariant, SeverityLevel},
+  docker::container::{
+    ContainerListItem, ContainerStateStatusEnum, HealthStatusEnum,
+  },
+  komodo_timestamp, ResourceTarget,
+};
+use mungos::{
+  find::find_collect,
+  mongodb::bson::{doc, oid::ObjectId},
+};
+
+use crate::{
+  alert::{apply_silences, send_alerts},
+  state::{db_client, server_status_cache, stack_status_cache},
+};
+
+/// The window in which restarts are counted.
+const RESTART_LOOP_WINDOW_MS: i64 = 5 * 60 * 1000;
+/// The number of restarts within the window
+/// for a container to be considered in a restart loop.
+const RESTART_LOOP_THRESHOLD: i64 = 3;
+
+/// (server id, container name) -> (ts, restart count) samples
+type RestartHistory =
+  Mutex<HashMap<(String, String), VecDeque<(i64, i64)>>>;
+
+fn restart_history() -> &'static RestartHistory {
+  static RESTART_HISTORY: OnceLock<RestartHistory> = OnceLock::new();
+  RESTART_HISTORY.get_or_init(Default::default)
+}
+
+/// A container on a Deployment / Stack
+/// which has opted in to container alerts.
+struct WatchedContainer {
+  target: ResourceTarget,
+  id: String,
+  name: String,
+  server_id: String,
+  container: ContainerListItem,
+  unhealthy_alerts: bool,
+  restart_loop_alerts: bool,
+}
+
+/// Opens / resolves ContainerUnhealthy and ContainerRestartLoop alerts.
+/// Pass `ids` to only check the containers of those Deployments / Stacks.
+#[instrument(level = "debug")]
+pub async fn alert_containers(
+  ts: i64,
+  server_names: &HashMap<String, String>,
+  ids: Option<&[String]>,
+) {
+  let watched = match get_watched_containers(ids).await {
+    Ok(watched) => watched,
+    Err(e) => {
+      error!("failed to get containers to alert on | {e:#}");
+      return;
+    }
+  };
+
+  let restarts = record_restarts(ts, &watched, ids.is_none());
+
+  // The alerts which should currently be open
+  let mut active = Vec::<Alert>::new();
+  for watched in watched {
+    let server_name = server_names
+      .get(&watched.server_id)
+      .cloned()
+      .unwrap_or(String::from("unknown"));
+    let running =
+      watched.container.state == ContainerStateStatusEnum::Running;
+    if watched.unhealthy_alerts
+      && running
+      && watched.container.health == Some(HealthStatusEnum::Unhealthy)
+    {
+      active.push(new_alert(
+        ts,
+        watched.target.clone(),
+        SeverityLevel::Warning,
+        AlertData::ContainerUnhealthy {
+          id: watched.id.clone(),
+          name: watched.name.clone(),
+          server_id: watched.server_id.clone(),
+          server_name: server_name.clone(),
+          container: watched.container.name.clone(),
+        },
+      ));
+    }
+    let restarts = restarts
+      .get(&(
+        watched.server_id.clone(),
+        watched.container.name.clone(),
+      ))
+      .copied()
+      .unwrap_or_default();
+    if watched.restart_loop_alerts
+      && restarts >= RESTART_LOOP_THRESHOLD
+    {
+      active.push(new_alert(
+        ts,
+        watched.target,
+        SeverityLevel::Critical,
+        AlertData::ContainerRestartLoop {
+          id: watched.id,
+          name: watched.name,
+          server_id: watched.server_id,
+          server_name,
+          container: watched.container.name,
+          restarts,
+        },
+      ));
+    }
+  }
+
+  let mut open_query = doc! {
+    "resolved": false,
+    "data.type": {
+      "$in": ["ContainerUnhealthy", "ContainerRestartLoop"]
+    },
+  };
+  if let Some(ids) = ids {
+    open_query.insert("target.id", doc! { "$in": ids });
+  }
+  let open =
+    match find_collect(&db_client().alerts, open_query, None).await {
+      Ok(open) => open,
+      Err(e) => {
+        error!("failed to get open container alerts from db | {e:#}");
+        return;
+      }
+    };
+
+  let active_keys =
+    active.iter().filter_map(alert_key).collect::<HashSet<_>>();
+  let open_keys =
+    open.iter().filter_map(alert_key).collect::<HashSet<_>>();
+
+  let mut to_open = active
+    .into_iter()
+    .filter(|alert| {
+      alert_key(alert).is_some_and(|key| !open_keys.contains(&key))
+    })
+    .collect::<Vec<_>>();
+  let to_resolve = open
+    .into_iter()
+    .filter(|alert| {
+      alert_key(alert).is_none_or(|key| !active_keys.contains(&key))
+    })
+    .collect::<Vec<_>>();
+
+  tokio::join!(open_alerts(&mut to_open), resolve_alerts(to_resolve));
+}
+
+fn new_alert(
+  ts: i64,
+  target: ResourceTarget,
+  level: SeverityLevel,
+  data: AlertData,
+) -> Alert {
+  Alert {
+    id: Default::default(),
+    ts,
+    resolved: false,
+    resolved_ts: None,
+    silenced: false,
+    acknowledged: false,
+    acknowledged_by: None,
+    acknowledged_ts: None,
+    level,
+    target,
+    data,
+  }
+}
+
+/// Identifies the condition an alert is for.
+fn alert_key(
+  alert: &Alert,
+) -> Option<(AlertDataVariant, ResourceTarget, String)> {
+  let (variant, container) = match &alert.data {
+    AlertData::ContainerUnhealthy { container, .. } => {
+      (AlertDataVariant::ContainerUnhealthy, container)
+    }
+    AlertData::ContainerRestartLoop { container, .. } => {
+      (AlertDataVariant::ContainerRestartLoop, container)
+    }
+    _ => return None,
+  };
+  Some((variant, alert.target.clone(), container.clone()))
+}
+
+async fn get_watched_containers(
+  ids: Option<&[String]>,
+) -> anyhow::Result<Vec<WatchedContainer>> {
+  let mut query = doc! {
+    "$or": [
+      { "config.send_unhealthy_alerts": true },
+      { "config.send_restart_loop_alerts": true },
+    ]
+  };
+  if let Some(ids) = ids {
+    let ids = ids
+      .iter()
+      .filter_map(|id| ObjectId::from_str(id).ok())
+      .collect::<Vec<_>>();
+    query.insert("_id", doc! { "$in": ids });
+  }
+  let (deployments, stacks) = tokio::try_join!(
+    async {
+      find_collect(&db_client().deployments, query.clone(), None)
+        .await
+        .context("failed to get deployments from db")
+    },
+    async {
+      find_collect(&db_client().stacks, query.clone(), None)
+        .await
+        .context("failed to get stacks from db")
+    },
+  )?;
+
+  let mut watched = Vec::new();
+
+  for deployment in deployments {
+    let Some(status) = server_status_cache()
+      .get(&deployment.config.server_id)
+      .await
+    else {
+      continue;
+    };
+    let Some(containers) = &status.containers else {
+      continue;
+    };
+    // Each replica is watched on its own, keyed by its container name.
+    for name in deployment.container_names() {
+      let Some(container) =
+        containers.iter().find(|container| container.name == name)
+      else {
+        continue;
+      };
+      watched.push(WatchedContainer {
+        target: ResourceTarget::Deployment(deployment.id.clone()),
+        id: deployment.id.clone(),
+        name: deployment.name.clone(),
+        server_id: deployment.config.server_id.clone(),
+        container: container.clone(),
+        unhealthy_alerts: deployment.config.send_unhealthy_alerts,
+        restart_loop_alerts: deployment
+          .config
+          .send_restart_loop_alerts,
+      });
+    }
+  }
+
+  for stack in stacks {
+    let Some(status) = stack_status_cache().get(&stack.id).await
+    else {
+      continue;
+    };
+    for service in &status.curr.services {
+      let Some(container) = service.container.clone() else {
+        continue;
+      };
+      watched.push(WatchedContainer {
+        target: ResourceTarget::Stack(stack.id.clone()),
+        id: stack.id.clone(),
+        name: stack.name.clone(),
+        server_id: stack.config.server_id.clone(),
+        container,
+        unhealthy_alerts: stack.config.send_unhealthy_alerts,
+        restart_loop_alerts: stack.config.send_restart_loop_alerts,
+      });
+    }
+  }
+
+  Ok(watched)
+}
+
+/// Records the restart counts reported for the watched containers,
+/// and returns the number of restarts within the window for each.
+/// `prune` forgets containers no longer watched,
+/// so is only set when all watched containers are passed.
+fn record_restarts(
+  ts: i64,
+  watched: &[WatchedContainer],
+  prune: bool,
+) -> HashMap<(String, String), i64> {
+  let mut history = restart_history().lock().unwrap();
+
+  if prune {
+    history.retain(|(server_id, container), _| {
+      watched.iter().any(|watched| {
+        &watched.server_id == server_id
+          && &watched.container.name == container
+      })
+    });
+  }
+
+  let mut restarts = HashMap::new();
+
+  for watched in watched {
+    let key =
+      (watched.server_id.clone(), watched.container.name.clone());
+    let samples = history.entry(key.clone()).or_default();
+    if let Some(count) = watched.container.restart_count {
+      // The count resets when the container is recreated.
+      if samples.back().is_some_and(|(_, last)| count < *last) {
+        samples.clear();
+      }
+      samples.push_back((ts, count));
+    }
+    while samples.front().is_some_and(|(sample_ts, _)| {
+      ts - sample_ts > RESTART_LOOP_WINDOW_MS
+    }) {
+      samples.pop_front();
+    }
+    let count = match (samples.front(), samples.back()) {
+      (Some((_, first)), Some((_, last))) => last - first,
+      _ => 0,
+    };
+    restarts.insert(key, count);
+  }
+
+  restarts
+}
+
+async fn open_alerts(alerts: &mut [Alert]) {
+  if alerts.is_empty() {
+    return;
+  }
+
+  apply_silences(alerts.iter_mut().collect()).await;
+
+  match db_client().alerts.insert_many(alerts.iter()).await {
+    Ok(res) => {
+      for (index, id) in res.inserted_ids {
+        if let (Some(alert), Some(id)) =
+          (alerts.get_mut(index), id.as_object_id())
+        {
+          alert.id = id.to_string();
+        }
+      }
+    }
+    Err(e) => {
+      error!("failed to record container alerts to db | {e:#}");
+      return;
+    }
+  }
+
+  send_alerts(alerts).await
+}
+
+async fn resolve_alerts(mut alerts: Vec<Alert>) {
+  if alerts.is_empty() {
+    return;
+  }
+
+  let ids = alerts
+    .iter()
+    .filter_map(|alert| ObjectId::from_str(&alert.id).ok())
+    .collect::<Vec<_>>();
+
+  let ts = komodo_timestamp();
+
+  if let Err(e) = db_client()
+    .alerts
+    .update_many(
+      doc! { "_id": { "$in": &ids } },
+      doc! {
+        "$set": {
+          "resolved": true,
+          "resolved_ts": ts
+        }
+      },
+    )
+    .await
+  {
+    error!("failed to resolve container alerts on db | {e:#}");
+    return;
+  }
+
+  for alert in &mut alerts {
+    alert.resolved = true;
+    alert.resolved_ts = Some(ts);
+    alert.level = SeverityLevel::Ok;
+  }
+
+  send_alerts(&alerts).await
+}
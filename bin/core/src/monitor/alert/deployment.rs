@@ -7,17 +7,24 @@ use komodo_client::entities::{
 };
 
 use crate::{
-  alert::send_alerts, monitor::deployment_status_cache, resource,
+  alert::{apply_silences, send_alerts},
+  monitor::deployment_status_cache,
+  resource,
   state::db_client,
 };
 
 #[instrument(level = "debug")]
+/// Alerts on all deployments, or only those with the given ids.
 pub async fn alert_deployments(
   ts: i64,
   server_names: &HashMap<String, String>,
+  ids: Option<&[String]>,
 ) {
   let mut alerts = Vec::<Alert>::new();
   for status in deployment_status_cache().get_list().await {
+    if ids.is_some_and(|ids| !ids.contains(&status.curr.id)) {
+      continue;
+    }
     // Don't alert if prev None
     let Some(prev) = status.prev else {
       continue;
@@ -62,6 +69,10 @@ pub async fn alert_deployments(
         level: SeverityLevel::Warning,
         resolved: true,
         resolved_ts: ts.into(),
+        silenced: false,
+        acknowledged: false,
+        acknowledged_by: None,
+        acknowledged_ts: None,
         target,
         data,
         ts,
@@ -72,6 +83,7 @@ pub async fn alert_deployments(
   if alerts.is_empty() {
     return;
   }
+  apply_silences(alerts.iter_mut().collect()).await;
   send_alerts(&alerts).await;
   let res = db_client().alerts.insert_many(alerts).await;
   if let Err(e) = res {
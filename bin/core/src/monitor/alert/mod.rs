@@ -7,7 +7,9 @@ use komodo_client::entities::{
 
 use crate::resource;
 
+mod container;
 mod deployment;
+mod renotify;
 mod server;
 mod stack;
 
@@ -24,8 +26,40 @@ pub async fn check_alerts(ts: i64) {
 
   tokio::join!(
     server::alert_servers(ts, servers),
-    deployment::alert_deployments(ts, &server_names),
-    stack::alert_stacks(ts, &server_names)
+    deployment::alert_deployments(ts, &server_names, None),
+    stack::alert_stacks(ts, &server_names, None),
+    container::alert_containers(ts, &server_names, None),
+    renotify::renotify_alerts(ts),
+  );
+}
+
+/// Called after a container event updates the cache.
+/// Only the Deployments / Stacks it updated are alerted on,
+/// as the others' previous state was already alerted on by the poll.
+#[instrument(level = "debug")]
+pub async fn check_container_event_alerts(
+  ts: i64,
+  deployment_ids: &[String],
+  stack_ids: &[String],
+) {
+  let (_, server_names) = match get_all_servers_map().await {
+    Ok(res) => res,
+    Err(e) => {
+      error!("{e:#?}");
+      return;
+    }
+  };
+
+  let ids = [deployment_ids, stack_ids].concat();
+
+  tokio::join!(
+    deployment::alert_deployments(
+      ts,
+      &server_names,
+      Some(deployment_ids)
+    ),
+    stack::alert_stacks(ts, &server_names, Some(stack_ids)),
+    container::alert_containers(ts, &server_names, Some(&ids)),
   );
 }
 
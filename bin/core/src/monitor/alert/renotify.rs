@@ -0,0 +1,95 @@
+use std::{
+  collections::HashMap,
+  sync::{Mutex, OnceLock},
+};
+
+use anyhow::Context;
+use komodo_client::entities::alerter::Alerter;
+use mungos::{find::find_collect, mongodb::bson::doc};
+
+use crate::{alert::send_alerts_to_alerters, state::db_client};
+
+/// (alert id, alerter id) -> last notified timestamp
+type LastNotified = Mutex<HashMap<(String, String), i64>>;
+
+fn last_notified() -> &'static LastNotified {
+  static LAST_NOTIFIED: OnceLock<LastNotified> = OnceLock::new();
+  LAST_NOTIFIED.get_or_init(Default::default)
+}
+
+/// Sends open, unacknowledged alerts again to alerters
+/// which have `renotify_minutes` configured.
+#[instrument(level = "debug")]
+pub async fn renotify_alerts(ts: i64) {
+  if let Err(e) = renotify_alerts_inner(ts).await {
+    error!("failed to renotify alerts | {e:#}");
+  }
+}
+
+async fn renotify_alerts_inner(ts: i64) -> anyhow::Result<()> {
+  let alerters = find_collect(
+    &db_client().alerters,
+    doc! {
+      "config.enabled": true,
+      "config.renotify_minutes": { "$gt": 0 },
+    },
+    None,
+  )
+  .await
+  .context("failed to get alerters from db")?;
+
+  if alerters.is_empty() {
+    last_notified().lock().unwrap().clear();
+    return Ok(());
+  }
+
+  let alerts = find_collect(
+    &db_client().alerts,
+    doc! {
+      "resolved": false,
+      "acknowledged": { "$ne": true },
+      // The stored `silenced` flag goes stale as silences begin and end.
+      // Silences are checked live when sending.
+    },
+    None,
+  )
+  .await
+  .context("failed to get open alerts from db")?;
+
+  let mut to_send = Vec::<(_, Vec<Alerter>)>::new();
+
+  {
+    let mut last_notified = last_notified().lock().unwrap();
+
+    // Forget alerts which are no longer open
+    last_notified.retain(|(alert_id, _), _| {
+      alerts.iter().any(|alert| &alert.id == alert_id)
+    });
+
+    for alert in alerts {
+      let mut due = Vec::new();
+      for alerter in &alerters {
+        let interval_ms =
+          alerter.config.renotify_minutes as i64 * 60_000;
+        let last = last_notified
+          .entry((alert.id.clone(), alerter.id.clone()))
+          // The alert was sent when it opened,
+          // or before Core restarted. Wait a full interval.
+          .or_insert(ts);
+        if ts - *last >= interval_ms {
+          *last = ts;
+          due.push(alerter.clone());
+        }
+      }
+      if !due.is_empty() {
+        to_send.push((alert, due));
+      }
+    }
+  }
+
+  for (alert, alerters) in to_send {
+    send_alerts_to_alerters(&alerters, &[alert]).await;
+  }
+
+  Ok(())
+}
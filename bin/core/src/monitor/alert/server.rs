@@ -6,6 +6,7 @@ use komodo_client::entities::{
   alert::{Alert, AlertData, AlertDataVariant, SeverityLevel},
   komodo_timestamp, optional_string,
   server::{Server, ServerState},
+  stats::busiest_network_interface,
   ResourceTarget,
 };
 use mongo_indexed::Indexed;
@@ -16,7 +17,8 @@ use mungos::{
 };
 
 use crate::{
-  alert::send_alerts,
+  alert::{apply_silences, send_alerts},
+  monitor::helpers::BYTES_PER_MEGABIT,
   state::{db_client, server_status_cache},
 };
 
@@ -65,6 +67,10 @@ pub async fn alert_servers(
           ts,
           resolved: false,
           resolved_ts: None,
+          silenced: false,
+          acknowledged: false,
+          acknowledged_by: None,
+          acknowledged_ts: None,
           level: SeverityLevel::Critical,
           target: ResourceTarget::Server(server_status.id.clone()),
           data: AlertData::ServerUnreachable {
@@ -130,6 +136,10 @@ pub async fn alert_servers(
           ts,
           resolved: false,
           resolved_ts: None,
+          silenced: false,
+          acknowledged: false,
+          acknowledged_by: None,
+          acknowledged_ts: None,
           level: health.cpu.level,
           target: ResourceTarget::Server(server_status.id.clone()),
           data: AlertData::ServerCpu {
@@ -201,6 +211,10 @@ pub async fn alert_servers(
           ts,
           resolved: false,
           resolved_ts: None,
+          silenced: false,
+          acknowledged: false,
+          acknowledged_by: None,
+          acknowledged_ts: None,
           level: health.mem.level,
           target: ResourceTarget::Server(server_status.id.clone()),
           data: AlertData::ServerMem {
@@ -271,6 +285,137 @@ pub async fn alert_servers(
       _ => {}
     }
 
+    // ===================
+    // SERVER NETWORK
+    // ===================
+    let network_data = || {
+      let network = server_status
+        .stats
+        .as_ref()
+        .and_then(|stats| busiest_network_interface(&stats.networks));
+      AlertData::ServerNetwork {
+        id: server_status.id.clone(),
+        name: server.name.clone(),
+        region: optional_string(&server.config.region),
+        interface: network
+          .map(|network| network.name.clone())
+          .unwrap_or_default(),
+        rx_mbps: network
+          .map(|network| network.rx_bytes_per_sec / BYTES_PER_MEGABIT)
+          .unwrap_or_default(),
+        tx_mbps: network
+          .map(|network| network.tx_bytes_per_sec / BYTES_PER_MEGABIT)
+          .unwrap_or_default(),
+      }
+    };
+    let network_alert = server_alerts
+      .as_ref()
+      .and_then(|alerts| alerts.get(&AlertDataVariant::ServerNetwork))
+      .cloned();
+    match (
+      health.network.level,
+      network_alert,
+      health.network.should_close_alert,
+    ) {
+      (SeverityLevel::Warning | SeverityLevel::Critical, None, _) => {
+        // open alert
+        let alert = Alert {
+          id: Default::default(),
+          ts,
+          resolved: false,
+          resolved_ts: None,
+          silenced: false,
+          acknowledged: false,
+          acknowledged_by: None,
+          acknowledged_ts: None,
+          level: health.network.level,
+          target: ResourceTarget::Server(server_status.id.clone()),
+          data: network_data(),
+        };
+        alerts_to_open
+          .push((alert, server.config.send_network_alerts));
+      }
+      (
+        SeverityLevel::Warning | SeverityLevel::Critical,
+        Some(mut alert),
+        _,
+      ) => {
+        // modify alert level only if it has increased
+        if alert.level < health.network.level {
+          alert.level = health.network.level;
+          alert.data = network_data();
+          alerts_to_update
+            .push((alert, server.config.send_network_alerts));
+        }
+      }
+      (SeverityLevel::Ok, Some(mut alert), true) => {
+        alert.data = network_data();
+        alert_ids_to_close
+          .push((alert, server.config.send_network_alerts))
+      }
+      _ => {}
+    }
+
+    // ===================
+    // SERVER LOAD
+    // ===================
+    let load_data = || AlertData::ServerLoad {
+      id: server_status.id.clone(),
+      name: server.name.clone(),
+      region: optional_string(&server.config.region),
+      load_average: server_status
+        .stats
+        .as_ref()
+        .map(|s| s.load_average.five)
+        .unwrap_or_default(),
+    };
+    let load_alert = server_alerts
+      .as_ref()
+      .and_then(|alerts| alerts.get(&AlertDataVariant::ServerLoad))
+      .cloned();
+    match (
+      health.load.level,
+      load_alert,
+      health.load.should_close_alert,
+    ) {
+      (SeverityLevel::Warning | SeverityLevel::Critical, None, _) => {
+        // open alert
+        let alert = Alert {
+          id: Default::default(),
+          ts,
+          resolved: false,
+          resolved_ts: None,
+          silenced: false,
+          acknowledged: false,
+          acknowledged_by: None,
+          acknowledged_ts: None,
+          level: health.load.level,
+          target: ResourceTarget::Server(server_status.id.clone()),
+          data: load_data(),
+        };
+        alerts_to_open.push((alert, server.config.send_load_alerts));
+      }
+      (
+        SeverityLevel::Warning | SeverityLevel::Critical,
+        Some(mut alert),
+        _,
+      ) => {
+        // modify alert level only if it has increased
+        if alert.level < health.load.level {
+          alert.level = health.load.level;
+          alert.data = load_data();
+          alerts_to_update
+            .push((alert, server.config.send_load_alerts));
+        }
+      }
+      (SeverityLevel::Ok, Some(mut alert), true) => {
+        alert.data = load_data();
+        alert_ids_to_close
+          .push((alert, server.config.send_load_alerts))
+      }
+      _ => {}
+    }
+
     // ===================
     // SERVER DISK
     // ===================
@@ -297,6 +442,10 @@ pub async fn alert_servers(
             ts,
             resolved: false,
             resolved_ts: None,
+            silenced: false,
+            acknowledged: false,
+            acknowledged_by: None,
+            acknowledged_ts: None,
             level: health.level,
             target: ResourceTarget::Server(server_status.id.clone()),
             data: AlertData::ServerDisk {
@@ -369,6 +518,15 @@ pub async fn alert_servers(
     }
   }
 
+  apply_silences(
+    alerts_to_open
+      .iter_mut()
+      .chain(alerts_to_update.iter_mut())
+      .map(|(alert, _)| alert)
+      .collect(),
+  )
+  .await;
+
   tokio::join!(
     open_alerts(&alerts_to_open),
     update_alerts(&alerts_to_update),
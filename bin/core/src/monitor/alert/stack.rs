@@ -7,18 +7,23 @@ use komodo_client::entities::{
 };
 
 use crate::{
-  alert::send_alerts,
+  alert::{apply_silences, send_alerts},
   resource,
   state::{db_client, stack_status_cache},
 };
 
 #[instrument(level = "debug")]
+/// Alerts on all stacks, or only those with the given ids.
 pub async fn alert_stacks(
   ts: i64,
   server_names: &HashMap<String, String>,
+  ids: Option<&[String]>,
 ) {
   let mut alerts = Vec::<Alert>::new();
   for status in stack_status_cache().get_list().await {
+    if ids.is_some_and(|ids| !ids.contains(&status.curr.id)) {
+      continue;
+    }
     // Don't alert if prev None
     let Some(prev) = status.prev else {
       continue;
@@ -61,6 +66,10 @@ pub async fn alert_stacks(
         level: SeverityLevel::Warning,
         resolved: true,
         resolved_ts: ts.into(),
+        silenced: false,
+        acknowledged: false,
+        acknowledged_by: None,
+        acknowledged_ts: None,
         target,
         data,
         ts,
@@ -71,6 +80,7 @@ pub async fn alert_stacks(
   if alerts.is_empty() {
     return;
   }
+  apply_silences(alerts.iter_mut().collect()).await;
   send_alerts(&alerts).await;
   let res = db_client().alerts.insert_many(alerts).await;
   if let Err(e) = res {
@@ -0,0 +1,318 @@
+use std::{
+  collections::{HashMap, HashSet},
+  str::FromStr,
+  sync::{Arc, Mutex, OnceLock},
+  time::Duration,
+};
+
+use anyhow::{anyhow, Context};
+use futures::TryStreamExt;
+use komodo_client::entities::{
+  deployment::DeploymentImage,
+  docker::container::{ContainerListItem, ContainerStateStatusEnum},
+  komodo_timestamp,
+  server::Server,
+};
+use mungos::{
+  find::find_collect,
+  mongodb::bson::{doc, oid::ObjectId},
+};
+use periphery_client::{
+  api::stream::{ContainerEvent, ContainerEventAction},
+  tungstenite::Message,
+};
+use tokio::sync::mpsc;
+
+use crate::{
+  helpers::periphery_client,
+  resource,
+  state::{db_client, server_status_cache, stack_status_cache},
+};
+
+use super::{
+  alert::check_container_event_alerts,
+  resources::{update_deployment_cache, update_stack_cache},
+};
+
+/// Servers with an open container event stream.
+fn event_listeners() -> &'static Mutex<HashSet<String>> {
+  static EVENT_LISTENERS: OnceLock<Mutex<HashSet<String>>> =
+    OnceLock::new();
+  EVENT_LISTENERS.get_or_init(Default::default)
+}
+
+/// Opens a container event stream to the server, unless one is already open.
+/// Called on each poll, so dropped streams are reopened.
+/// Servers using the reverse connection push events over it instead.
+pub fn spawn_container_event_listener(server: &Server) {
+  if !server.info.enrollment_token_hash.is_empty()
+    || !event_listeners().lock().unwrap().insert(server.id.clone())
+  {
+    return;
+  }
+  let server = server.clone();
+  tokio::spawn(async move {
+    if let Err(e) = listen_for_container_events(&server).await {
+      warn!(
+        "container event stream for server {} closed | {e:#}",
+        server.name
+      );
+    }
+    event_listeners().lock().unwrap().remove(&server.id);
+  });
+}
+
+async fn listen_for_container_events(
+  server: &Server,
+) -> anyhow::Result<()> {
+  let mut ws = periphery_client(server)?.container_events().await?;
+  while let Some(msg) = ws
+    .try_next()
+    .await
+    .context("failed to recieve container event")?
+  {
+    let Message::Text(msg) = msg else {
+      continue;
+    };
+    let event = serde_json::from_str::<ContainerEvent>(&msg)
+      .context("failed to parse ContainerEvent")?;
+    let current = resource::get::<Server>(&server.id).await?;
+    // The next poll reconnects with the new config.
+    if !current.config.enabled
+      || current.config.address != server.config.address
+      || !current.info.enrollment_token_hash.is_empty()
+    {
+      return Err(anyhow!("server config changed"));
+    }
+    queue_container_event(&current.id, event);
+  }
+  Ok(())
+}
+
+/// Handles the container events periphery pushes over the reverse connection,
+/// in the order they arrive.
+pub fn spawn_tunneled_event_handler(
+  server_id: String,
+) -> mpsc::Sender<ContainerEvent> {
+  let (tx, mut rx) = mpsc::channel(100);
+  tokio::spawn(async move {
+    while let Some(event) = rx.recv().await {
+      queue_container_event(&server_id, event);
+    }
+  });
+  tx
+}
+
+/// Events for the same container within this window are handled together,
+/// so eg. the die / start / health_status of a restart only update once.
+const EVENT_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// (server id, container name) -> events waiting to be handled, in order.
+type PendingEvents =
+  Mutex<HashMap<(String, String), Vec<ContainerEvent>>>;
+
+fn pending_events() -> &'static PendingEvents {
+  static PENDING_EVENTS: OnceLock<PendingEvents> = OnceLock::new();
+  PENDING_EVENTS.get_or_init(Default::default)
+}
+
+/// Debounces the events per container.
+fn queue_container_event(server_id: &str, event: ContainerEvent) {
+  let key = (server_id.to_string(), event.name.clone());
+  {
+    let mut pending = pending_events().lock().unwrap();
+    if let Some(events) = pending.get_mut(&key) {
+      events.push(event);
+      return;
+    }
+    pending.insert(key.clone(), vec![event]);
+  }
+  tokio::spawn(async move {
+    tokio::time::sleep(EVENT_DEBOUNCE).await;
+    let Some(events) = pending_events().lock().unwrap().remove(&key)
+    else {
+      return;
+    };
+    let (server_id, name) = key;
+    match resource::get::<Server>(&server_id).await {
+      Ok(server) => {
+        handle_container_events(&server, &name, &events).await
+      }
+      Err(e) => {
+        warn!("failed to get server for container event | {e:#}")
+      }
+    }
+  });
+}
+
+/// Applies the events to the cached container, then updates the
+/// Deployment / Stack status and alerts without waiting for the next poll.
+async fn handle_container_events(
+  server: &Server,
+  name: &str,
+  events: &[ContainerEvent],
+) {
+  // Applied under the cache lock, so a poll finishing
+  // in the meantime isn't overwritten with the older containers.
+  let Some(Some((containers, images))) = server_status_cache()
+    .modify(&server.id, |status| {
+      let status = Arc::make_mut(status);
+      let lists_ts = status.lists_ts;
+      // New containers are picked up on the next poll.
+      let container = status
+        .containers
+        .as_mut()?
+        .iter_mut()
+        .find(|c| c.name == name)?;
+      let mut changed = false;
+      // Skip events the listed containers already reflect.
+      for event in events.iter().filter(|event| event.ts >= lists_ts)
+      {
+        apply_container_event(container, event);
+        changed = true;
+      }
+      changed.then(|| {
+        (
+          status.containers.clone().unwrap_or_default(),
+          status.images.clone().unwrap_or_default(),
+        )
+      })
+    })
+    .await
+  else {
+    return;
+  };
+
+  // Replica containers are named `{deployment}-{index}`.
+  let mut deployment_names = vec![name];
+  if let Some((name, index)) = name.rsplit_once('-') {
+    if index.parse::<u32>().is_ok() {
+      deployment_names.push(name);
+    }
+  }
+
+  // Only update the stacks with the container,
+  // leaving the others to the poll.
+  let stack_ids = stack_status_cache()
+    .get_list()
+    .await
+    .into_iter()
+    .filter(|status| {
+      status.curr.services.iter().any(|service| {
+        service.container.as_ref().is_some_and(|c| c.name == name)
+      })
+    })
+    .filter_map(|status| ObjectId::from_str(&status.curr.id).ok())
+    .collect::<Vec<_>>();
+
+  let (deployments, stacks) = match tokio::try_join!(
+    async {
+      find_collect(
+        &db_client().deployments,
+        doc! {
+          "name": { "$in": &deployment_names },
+          "config.server_id": &server.id,
+        },
+        None,
+      )
+      .await
+      .context("failed to get deployments from db")
+    },
+    async {
+      if stack_ids.is_empty() {
+        return Ok(Vec::new());
+      }
+      find_collect(
+        &db_client().stacks,
+        doc! {
+          "_id": { "$in": &stack_ids },
+          "config.server_id": &server.id,
+        },
+        None,
+      )
+      .await
+      .context("failed to get stacks from db")
+    },
+  ) {
+    Ok(res) => res,
+    Err(e) => {
+      error!("failed to handle container event | {e:#}");
+      return;
+    }
+  };
+
+  // Only the Builds the Deployments use are needed for their status.
+  let build_ids = deployments
+    .iter()
+    .filter_map(|deployment| match &deployment.config.image {
+      DeploymentImage::Build { build_id, .. } => {
+        ObjectId::from_str(build_id).ok()
+      }
+      DeploymentImage::Image { .. } => None,
+    })
+    .collect::<Vec<_>>();
+  let builds = if build_ids.is_empty() {
+    Vec::new()
+  } else {
+    match find_collect(
+      &db_client().builds,
+      doc! { "_id": { "$in": &build_ids } },
+      None,
+    )
+    .await
+    {
+      Ok(builds) => builds,
+      Err(e) => {
+        error!(
+          "failed to get builds from db (container event) | {e:#}"
+        );
+        return;
+      }
+    }
+  };
+
+  let deployment_ids =
+    deployments.iter().map(|d| d.id.clone()).collect::<Vec<_>>();
+  let stack_ids =
+    stacks.iter().map(|s| s.id.clone()).collect::<Vec<_>>();
+
+  tokio::join!(
+    update_deployment_cache(
+      server.name.clone(),
+      deployments,
+      &containers,
+      &images,
+      &builds,
+    ),
+    update_stack_cache(
+      server.name.clone(),
+      stacks,
+      &containers,
+      &images
+    ),
+  );
+
+  check_container_event_alerts(
+    komodo_timestamp(),
+    &deployment_ids,
+    &stack_ids,
+  )
+  .await;
+}
+
+fn apply_container_event(
+  container: &mut ContainerListItem,
+  event: &ContainerEvent,
+) {
+  match event.action {
+    ContainerEventAction::Start => {
+      container.state = ContainerStateStatusEnum::Running
+    }
+    ContainerEventAction::Stop | ContainerEventAction::Die => {
+      container.state = ContainerStateStatusEnum::Exited
+    }
+    ContainerEventAction::HealthStatus => {
+      container.health = event.health
+    }
+  }
+}
@@ -11,7 +11,7 @@ use komodo_client::entities::{
     ServerState,
   },
   stack::{ComposeProject, Stack, StackState},
-  stats::{SingleDiskUsage, SystemStats},
+  stats::{busiest_network_interface, SingleDiskUsage, SystemStats},
 };
 use serror::Serror;
 
@@ -106,6 +106,7 @@ pub async fn insert_server_status(
   version: String,
   stats: Option<SystemStats>,
   (containers, networks, images, volumes, projects): DockerLists,
+  lists_ts: i64,
   err: impl Into<Option<Serror>>,
 ) {
   let health = stats.as_ref().map(|s| get_server_health(server, s));
@@ -119,6 +120,7 @@ pub async fn insert_server_status(
         stats,
         health,
         containers,
+        lists_ts,
         networks,
         images,
         volumes,
@@ -131,6 +133,11 @@ pub async fn insert_server_status(
 }
 
 const ALERT_PERCENTAGE_THRESHOLD: f32 = 5.0;
+/// Network and load alerts are closed once
+/// below this fraction of the warning threshold.
+const ALERT_RATIO_THRESHOLD: f64 = 0.9;
+/// Converts network rates in bytes per second to Mbps.
+pub const BYTES_PER_MEGABIT: f64 = 125000.0;
 
 fn get_server_health(
   server: &Server,
@@ -139,6 +146,8 @@ fn get_server_health(
     mem_used_gb,
     mem_total_gb,
     disks,
+    networks,
+    load_average,
     ..
   }: &SystemStats,
 ) -> ServerHealth {
@@ -149,6 +158,10 @@ fn get_server_health(
     mem_critical,
     disk_warning,
     disk_critical,
+    network_warning,
+    network_critical,
+    load_warning,
+    load_critical,
     ..
   } = &server.config;
   let mut health = ServerHealth::default();
@@ -193,5 +206,43 @@ fn get_server_health(
     health.disks.insert(mount.clone(), state);
   }
 
+  let network_mbps = busiest_network_interface(networks)
+    .map(|network| {
+      network.rx_bytes_per_sec.max(network.tx_bytes_per_sec)
+        / BYTES_PER_MEGABIT
+    })
+    .unwrap_or_default();
+  health.network = threshold_health(
+    network_mbps,
+    *network_warning,
+    *network_critical,
+  );
+
+  health.load = threshold_health(
+    load_average.five,
+    *load_warning,
+    *load_critical,
+  );
+
   health
 }
+
+/// Health against thresholds which are disabled when 0.
+fn threshold_health(
+  value: f64,
+  warning: f64,
+  critical: f64,
+) -> ServerHealthState {
+  let mut state = ServerHealthState::default();
+  let close_below = if warning > 0.0 { warning } else { critical };
+  if critical > 0.0 && value >= critical {
+    state.level = SeverityLevel::Critical;
+  } else if warning > 0.0 && value >= warning {
+    state.level = SeverityLevel::Warning;
+  } else if close_below <= 0.0
+    || value < close_below * ALERT_RATIO_THRESHOLD
+  {
+    state.should_close_alert = true;
+  }
+  state
+}
@@ -12,14 +12,22 @@ use komodo_client::entities::{
   stack::{ComposeProject, StackService, StackState},
   stats::SystemStats,
 };
-use mungos::{find::find_collect, mongodb::bson::doc};
-use periphery_client::api::{self, git::GetLatestCommit};
+use mungos::{
+  by_id::update_one_by_id, find::find_collect, mongodb::bson::doc,
+};
+use periphery_client::{
+  api::{self, git::GetLatestCommit},
+  PeripheryClient,
+};
 use serror::Serror;
 
 use crate::{
   config::core_config,
   helpers::periphery_client,
-  monitor::{alert::check_alerts, record::record_server_stats},
+  monitor::{
+    alert::check_alerts,
+    record::{record_container_stats, record_server_stats},
+  },
   state::{db_client, deployment_status_cache, repo_status_cache},
 };
 
@@ -29,10 +37,12 @@ use self::helpers::{
 };
 
 mod alert;
+pub mod events;
 mod helpers;
 mod lists;
 mod record;
 mod resources;
+pub mod rollup;
 
 #[derive(Default, Debug)]
 pub struct History<Curr: Default, Prev> {
@@ -48,6 +58,9 @@ pub struct CachedServerStatus {
   pub stats: Option<SystemStats>,
   pub health: Option<ServerHealth>,
   pub containers: Option<Vec<ContainerListItem>>,
+  /// When the docker lists were requested, in unix ms.
+  /// Container events from before this are already reflected.
+  pub lists_ts: i64,
   pub networks: Option<Vec<NetworkListItem>>,
   pub images: Option<Vec<ImageListItem>>,
   pub volumes: Option<Vec<VolumeListItem>>,
@@ -109,11 +122,52 @@ async fn refresh_server_cache(ts: i64) {
         return;
       }
     };
-  let futures = servers.into_iter().map(|server| async move {
-    update_cache_for_server(&server).await;
+  let futures = servers.iter().map(|server| async move {
+    update_cache_for_server(server).await;
   });
   join_all(futures).await;
-  tokio::join!(check_alerts(ts), record_server_stats(ts));
+  tokio::join!(
+    check_alerts(ts),
+    record_server_stats(ts),
+    record_container_stats(ts, &servers)
+  );
+}
+
+/// Pins the certificate periphery first presents,
+/// after which periphery_client refuses any other certificate.
+async fn pin_server_certificate(
+  server: &Server,
+  periphery: &PeripheryClient,
+) {
+  let fingerprint = match periphery.certificate_fingerprint().await {
+    Ok(Some(fingerprint)) => fingerprint,
+    Ok(None) => return,
+    Err(e) => {
+      warn!(
+        "failed to get certificate fingerprint for server {} | {e:#}",
+        server.name
+      );
+      return;
+    }
+  };
+  if let Err(e) = update_one_by_id(
+    &db_client().servers,
+    &server.id,
+    doc! { "$set": { "info.certificate_fingerprint": &fingerprint } },
+    None,
+  )
+  .await
+  {
+    warn!(
+      "failed to pin certificate for server {} | {e:#}",
+      server.name
+    );
+    return;
+  }
+  info!(
+    "pinned certificate for server {} | {fingerprint}",
+    server.name
+  );
 }
 
 #[instrument(level = "debug")]
@@ -153,21 +207,30 @@ pub async fn update_cache_for_server(server: &Server) {
       String::from("unknown"),
       None,
       (None, None, None, None, None),
+      0,
       None,
     )
     .await;
     return;
   }
 
-  let Ok(periphery) = periphery_client(server) else {
-    error!(
-      "somehow periphery not ok to create. should not be reached."
-    );
-    return;
-  };
+  // Creating the client fails for servers using the
+  // reverse connection which haven't connected yet.
+  let res = async {
+    let periphery = periphery_client(server)?;
+    let version = periphery.request(api::GetVersion {}).await?;
+    anyhow::Ok((periphery, version.version))
+  }
+  .await;
 
-  let version = match periphery.request(api::GetVersion {}).await {
-    Ok(version) => version.version,
+  let (periphery, version) = match res {
+    Ok(res) => {
+      if server.info.certificate_fingerprint.is_empty() {
+        pin_server_certificate(server, &res.0).await;
+      }
+      events::spawn_container_event_listener(server);
+      res
+    }
     Err(e) => {
       insert_deployments_status_unknown(deployments).await;
       insert_repos_status_unknown(repos).await;
@@ -178,6 +241,7 @@ pub async fn update_cache_for_server(server: &Server) {
         String::from("unknown"),
         None,
         (None, None, None, None, None),
+        0,
         Serror::from(&e),
       )
       .await;
@@ -198,6 +262,7 @@ pub async fn update_cache_for_server(server: &Server) {
           String::from("unknown"),
           None,
           (None, None, None, None, None),
+          0,
           Serror::from(&e),
         )
         .await;
@@ -208,6 +273,7 @@ pub async fn update_cache_for_server(server: &Server) {
     None
   };
 
+  let lists_ts = komodo_timestamp();
   match lists::get_docker_lists(&periphery).await {
     Ok((mut containers, networks, images, volumes, projects)) => {
       containers.iter_mut().for_each(|container| {
@@ -240,6 +306,7 @@ pub async fn update_cache_for_server(server: &Server) {
           Some(volumes),
           Some(projects),
         ),
+        lists_ts,
         None,
       )
       .await;
@@ -256,6 +323,7 @@ pub async fn update_cache_for_server(server: &Server) {
         version,
         stats,
         (None, None, None, None, None),
+        0,
         Some(e.into()),
       )
       .await;
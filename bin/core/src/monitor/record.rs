@@ -1,8 +1,23 @@
-use komodo_client::entities::stats::{
-  sum_disk_usage, SystemStatsRecord, TotalDiskUsage,
+use std::collections::HashMap;
+
+use anyhow::Context;
+use futures::future::join_all;
+use komodo_client::entities::{
+  docker::container::{ContainerStateStatusEnum, ContainerStats},
+  server::{Server, ServerState},
+  stats::{
+    sum_disk_usage, sum_network_usage, ContainerStatsRecord,
+    SystemStatsRecord, TotalDiskUsage, TotalNetworkUsage,
+  },
+  ResourceTarget,
 };
+use mungos::find::find_collect;
+use periphery_client::api::container::GetContainerStatsList;
 
-use crate::state::{db_client, server_status_cache};
+use crate::{
+  helpers::periphery_client,
+  state::{db_client, server_status_cache, stack_status_cache},
+};
 
 #[instrument(level = "debug")]
 pub async fn record_server_stats(ts: i64) {
@@ -17,6 +32,11 @@ pub async fn record_server_stats(ts: i64) {
         total_gb: disk_total_gb,
       } = sum_disk_usage(&stats.disks);
 
+      let TotalNetworkUsage {
+        rx_bytes_per_sec: net_rx_bytes_per_sec,
+        tx_bytes_per_sec: net_tx_bytes_per_sec,
+      } = sum_network_usage(&stats.networks);
+
       Some(SystemStatsRecord {
         ts,
         sid: status.id.clone(),
@@ -26,6 +46,10 @@ pub async fn record_server_stats(ts: i64) {
         disk_total_gb,
         disk_used_gb,
         disks: stats.disks.clone(),
+        net_rx_bytes_per_sec,
+        net_tx_bytes_per_sec,
+        networks: stats.networks.clone(),
+        load_average: stats.load_average,
       })
     })
     .collect::<Vec<_>>();
@@ -36,3 +60,209 @@ pub async fn record_server_stats(ts: i64) {
     }
   }
 }
+
+/// Server id -> container name -> (target, service)
+type ContainerTargets =
+  HashMap<String, HashMap<String, (ResourceTarget, Option<String>)>>;
+
+#[instrument(level = "debug", skip(servers))]
+pub async fn record_container_stats(ts: i64, servers: &[Server]) {
+  let targets = match container_targets().await {
+    Ok(targets) => targets,
+    Err(e) => {
+      error!("failed to get containers to record stats for | {e:#}");
+      return;
+    }
+  };
+  let futures = servers.iter().filter_map(|server| {
+    if !server.config.enabled
+      || !server.config.stats_monitoring
+      || !server.config.container_stats_monitoring
+    {
+      return None;
+    }
+    let targets = targets.get(&server.id)?;
+    Some(server_container_stats(ts, server, targets))
+  });
+  let records = join_all(futures)
+    .await
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+  if !records.is_empty() {
+    let res = db_client().container_stats.insert_many(records).await;
+    if let Err(e) = res {
+      error!("failed to record container stats | {e:#}");
+    }
+  }
+}
+
+async fn server_container_stats(
+  ts: i64,
+  server: &Server,
+  targets: &HashMap<String, (ResourceTarget, Option<String>)>,
+) -> Vec<ContainerStatsRecord> {
+  let ok = server_status_cache()
+    .get(&server.id)
+    .await
+    .map(|status| status.state == ServerState::Ok)
+    .unwrap_or_default();
+  if !ok {
+    return Vec::new();
+  }
+  let stats = match periphery_client(server) {
+    Ok(periphery) => {
+      periphery.request(GetContainerStatsList {}).await
+    }
+    Err(e) => Err(e),
+  };
+  let stats = match stats {
+    Ok(stats) => stats,
+    Err(e) => {
+      warn!(
+        "failed to get container stats for server {} | {e:#}",
+        server.name
+      );
+      return Vec::new();
+    }
+  };
+  stats
+    .into_iter()
+    .filter_map(|stats| {
+      let (target, service) = targets.get(&stats.name)?.clone();
+      Some(container_stats_record(
+        ts, &server.id, target, service, stats,
+      ))
+    })
+    .collect()
+}
+
+/// Collects the running containers of Deployments,
+/// including every replica, and Stack services.
+async fn container_targets() -> anyhow::Result<ContainerTargets> {
+  let deployments =
+    find_collect(&db_client().deployments, None, None)
+      .await
+      .context("failed to get deployments from db")?;
+  let mut targets = ContainerTargets::new();
+  for deployment in deployments {
+    let Some(status) = server_status_cache()
+      .get(&deployment.config.server_id)
+      .await
+    else {
+      continue;
+    };
+    let Some(containers) = &status.containers else {
+      continue;
+    };
+    for name in deployment.container_names() {
+      let Some(container) =
+        containers.iter().find(|container| container.name == name)
+      else {
+        continue;
+      };
+      if container.state != ContainerStateStatusEnum::Running {
+        continue;
+      }
+      targets
+        .entry(deployment.config.server_id.clone())
+        .or_default()
+        .insert(
+          name,
+          (ResourceTarget::Deployment(deployment.id.clone()), None),
+        );
+    }
+  }
+  for status in stack_status_cache().get_list().await {
+    for service in &status.curr.services {
+      let Some(container) = &service.container else {
+        continue;
+      };
+      let Some(server_id) = &container.server_id else {
+        continue;
+      };
+      if container.state != ContainerStateStatusEnum::Running {
+        continue;
+      }
+      targets.entry(server_id.clone()).or_default().insert(
+        container.name.clone(),
+        (
+          ResourceTarget::Stack(status.curr.id.clone()),
+          Some(service.service.clone()),
+        ),
+      );
+    }
+  }
+  Ok(targets)
+}
+
+fn container_stats_record(
+  ts: i64,
+  sid: &str,
+  target: ResourceTarget,
+  service: Option<String>,
+  stats: ContainerStats,
+) -> ContainerStatsRecord {
+  let (mem_used_mb, mem_limit_mb) = parse_io_pair(&stats.mem_usage);
+  let (net_rx_mb, net_tx_mb) = parse_io_pair(&stats.net_io);
+  let (block_read_mb, block_write_mb) =
+    parse_io_pair(&stats.block_io);
+  ContainerStatsRecord {
+    ts,
+    sid: sid.to_string(),
+    target,
+    service,
+    container: stats.name,
+    cpu_perc: parse_perc(&stats.cpu_perc),
+    mem_perc: parse_perc(&stats.mem_perc),
+    mem_used_mb,
+    mem_limit_mb,
+    net_rx_mb,
+    net_tx_mb,
+    block_read_mb,
+    block_write_mb,
+  }
+}
+
+/// Parses docker stats percentages, eg `0.05%`.
+fn parse_perc(perc: &str) -> f64 {
+  perc
+    .trim()
+    .trim_end_matches('%')
+    .parse()
+    .unwrap_or_default()
+}
+
+/// Parses docker stats pairs, eg `1.2kB / 648B`, into MiB.
+fn parse_io_pair(pair: &str) -> (f64, f64) {
+  let mut split = pair.split('/');
+  let first = split.next().map(parse_size_mb).unwrap_or_default();
+  let second = split.next().map(parse_size_mb).unwrap_or_default();
+  (first, second)
+}
+
+const BYTES_PER_MB: f64 = 1048576.0;
+
+/// Parses docker stats sizes, eg `12.3MiB` or `1.2kB`, into MiB.
+fn parse_size_mb(size: &str) -> f64 {
+  let size = size.trim();
+  let split = size
+    .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+    .unwrap_or(size.len());
+  let (value, unit) = size.split_at(split);
+  let Ok(value) = value.parse::<f64>() else {
+    return 0.0;
+  };
+  let multiplier = match unit.trim() {
+    "kB" | "KB" => 1e3,
+    "KiB" => 1024.0,
+    "MB" => 1e6,
+    "MiB" => BYTES_PER_MB,
+    "GB" => 1e9,
+    "GiB" => 1073741824.0,
+    "TB" => 1e12,
+    "TiB" => 1099511627776.0,
+    _ => 1.0,
+  };
+  value * multiplier / BYTES_PER_MB
+}
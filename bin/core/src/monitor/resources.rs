@@ -22,7 +22,7 @@ use komodo_client::{
 };
 
 use crate::{
-  alert::send_alerts,
+  alert::{apply_silences, send_alerts},
   api::execute::{self, ExecuteRequest},
   helpers::query::get_stack_state_from_containers,
   stack::{
@@ -51,18 +51,30 @@ pub async fn update_deployment_cache(
 ) {
   let deployment_status_cache = deployment_status_cache();
   for deployment in deployments {
-    let container = containers
-      .iter()
-      .find(|container| container.name == deployment.name)
-      .cloned();
+    let replicas = deployment
+      .container_names()
+      .into_iter()
+      .map(|name| {
+        containers.iter().find(|container| container.name == name)
+      })
+      .collect::<Vec<_>>();
+    // The first replica stands in for the rest, eg. for the status and image.
+    let container = replicas.first().copied().flatten().cloned();
     let prev = deployment_status_cache
       .get(&deployment.id)
       .await
       .map(|s| s.curr.state);
-    let state = container
-      .as_ref()
-      .map(|c| c.state.into())
-      .unwrap_or(DeploymentState::NotDeployed);
+    // Running if all replicas are,
+    // otherwise the state of the first replica which isn't.
+    let state = replicas
+      .iter()
+      .map(|container| {
+        container
+          .map(|c| c.state.into())
+          .unwrap_or(DeploymentState::NotDeployed)
+      })
+      .find(|state| *state != DeploymentState::Running)
+      .unwrap_or(DeploymentState::Running);
     let image = match deployment.config.image {
       DeploymentImage::Build { build_id, version } => {
         let (build_name, build_version) = builds
@@ -134,11 +146,15 @@ pub async fn update_deployment_cache(
           .unwrap()
           .insert(deployment.id.clone());
         let ts = komodo_timestamp();
-        let alert = Alert {
+        let mut alert = Alert {
           id: Default::default(),
           ts,
           resolved: true,
           resolved_ts: ts.into(),
+          silenced: false,
+          acknowledged: false,
+          acknowledged_by: None,
+          acknowledged_ts: None,
           level: SeverityLevel::Ok,
           target: ResourceTarget::Deployment(deployment.id.clone()),
           data: AlertData::DeploymentImageUpdateAvailable {
@@ -149,6 +165,7 @@ pub async fn update_deployment_cache(
             image,
           },
         };
+        apply_silences(vec![&mut alert]).await;
         let res = db_client().alerts.insert_one(&alert).await;
         if let Err(e) = res {
           error!(
@@ -237,11 +254,15 @@ pub async fn update_stack_cache(
             .unwrap()
             .insert((stack.id.clone(), service_name.clone()));
           let ts = komodo_timestamp();
-          let alert = Alert {
+          let mut alert = Alert {
             id: Default::default(),
             ts,
             resolved: true,
             resolved_ts: ts.into(),
+            silenced: false,
+            acknowledged: false,
+            acknowledged_by: None,
+            acknowledged_ts: None,
             level: SeverityLevel::Ok,
             target: ResourceTarget::Stack(stack.id.clone()),
             data: AlertData::StackImageUpdateAvailable {
@@ -254,6 +275,7 @@ pub async fn update_stack_cache(
             },
           };
           tokio::spawn(async move {
+            apply_silences(vec![&mut alert]).await;
             let res = db_client().alerts.insert_one(&alert).await;
             if let Err(e) = res {
               error!(
@@ -0,0 +1,149 @@
+use anyhow::Context;
+use async_timing_util::{
+  unix_timestamp_ms, wait_until_timelength, Timelength, ONE_DAY_MS,
+  ONE_HOUR_MS,
+};
+use futures::TryStreamExt;
+use komodo_client::entities::stats::{
+  StatsResolution, SystemStatsRollup,
+};
+use mungos::mongodb::{
+  bson::{doc, Document},
+  options::ReplaceOptions,
+};
+
+use crate::state::db_client;
+
+/// Wait after the period ends so the final records are in.
+const ADDITIONAL_MS: u128 = 60_000;
+/// On startup, rolls up this many of the most recent hours,
+/// to fill in any periods missed while core was down.
+const BACKFILL_HOURS: u128 = 24;
+
+pub fn spawn_stats_rollup_loop() {
+  tokio::spawn(async move {
+    let ts = unix_timestamp_ms();
+    let hour = ts - ts % ONE_HOUR_MS;
+    for i in 1..=BACKFILL_HOURS {
+      rollup(hour - i * ONE_HOUR_MS, StatsResolution::Hour).await;
+    }
+    rollup(ts - ts % ONE_DAY_MS - ONE_DAY_MS, StatsResolution::Day)
+      .await;
+    loop {
+      let ts =
+        wait_until_timelength(Timelength::OneHour, ADDITIONAL_MS)
+          .await
+          - ADDITIONAL_MS;
+      rollup(ts - ONE_HOUR_MS, StatsResolution::Hour).await;
+      if ts % ONE_DAY_MS == 0 {
+        rollup(ts - ONE_DAY_MS, StatsResolution::Day).await;
+      }
+    }
+  });
+}
+
+async fn rollup(start_ts: u128, resolution: StatsResolution) {
+  if let Err(e) = rollup_inner(start_ts as i64, resolution).await {
+    error!("failed to roll up {resolution} server stats | {e:#}");
+  }
+}
+
+/// Aggregates the raw stats records in the period starting
+/// at `start_ts` into one rollup per server.
+async fn rollup_inner(
+  start_ts: i64,
+  resolution: StatsResolution,
+) -> anyhow::Result<()> {
+  let length = match resolution {
+    StatsResolution::Hour => ONE_HOUR_MS,
+    StatsResolution::Day => ONE_DAY_MS,
+    StatsResolution::Raw => return Ok(()),
+  } as i64;
+  let db = db_client();
+
+  let mut group = doc! {
+    "_id": "$sid",
+    "count": { "$sum": 1 },
+    "mem_total_gb": { "$max": "$mem_total_gb" },
+    "disk_total_gb": { "$max": "$disk_total_gb" },
+  };
+  let mut project = doc! {
+    "_id": 0,
+    "ts": { "$literal": start_ts },
+    "sid": "$_id",
+    "resolution": resolution.to_string(),
+    "count": 1,
+    "mem_total_gb": 1,
+    "disk_total_gb": 1,
+  };
+  for (field, path) in AGGREGATED_FIELDS {
+    let value = doc! { "$ifNull": [format!("${path}"), 0.0] };
+    for op in ["min", "avg", "max"] {
+      group.insert(
+        format!("{field}_{op}"),
+        doc! { format!("${op}"): value.clone() },
+      );
+    }
+    project.insert(
+      field,
+      doc! {
+        "min": format!("${field}_min"),
+        "avg": format!("${field}_avg"),
+        "max": format!("${field}_max"),
+      },
+    );
+  }
+
+  let rollups = db
+    .stats
+    .aggregate([
+      doc! {
+        "$match": {
+          "ts": { "$gte": start_ts, "$lt": start_ts + length }
+        }
+      },
+      doc! { "$group": group },
+      doc! { "$project": project },
+    ])
+    .await
+    .context("failed to aggregate stats")?
+    .try_collect::<Vec<Document>>()
+    .await
+    .context("failed to collect stats aggregation")?
+    .into_iter()
+    .map(mungos::mongodb::bson::from_document::<SystemStatsRollup>)
+    .collect::<Result<Vec<_>, _>>()
+    .context("failed to parse stats aggregation")?;
+
+  for rollup in rollups {
+    db.stats_rollups
+      .replace_one(
+        doc! {
+          "sid": &rollup.sid,
+          "resolution": resolution.to_string(),
+          "ts": start_ts,
+        },
+        &rollup,
+      )
+      .with_options(ReplaceOptions::builder().upsert(true).build())
+      .await
+      .with_context(|| {
+        format!(
+          "failed to write stats rollup for server {}",
+          rollup.sid
+        )
+      })?;
+  }
+
+  Ok(())
+}
+
+/// The rollup field, and the path of the stat on the raw record.
+const AGGREGATED_FIELDS: [(&str, &str); 6] = [
+  ("cpu_perc", "cpu_perc"),
+  ("mem_used_gb", "mem_used_gb"),
+  ("disk_used_gb", "disk_used_gb"),
+  ("net_rx_bytes_per_sec", "net_rx_bytes_per_sec"),
+  ("net_tx_bytes_per_sec", "net_tx_bytes_per_sec"),
+  ("load_average", "load_average.five"),
+];
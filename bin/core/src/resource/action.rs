@@ -10,14 +10,17 @@ use komodo_client::entities::{
   resource::Resource,
   update::Update,
   user::User,
-  Operation, ResourceTargetVariant,
+  Operation, ResourceTarget, ResourceTargetVariant,
 };
 use mungos::{
   find::find_collect,
   mongodb::{bson::doc, options::FindOneOptions, Collection},
 };
 
-use crate::state::{action_state_cache, action_states, db_client};
+use crate::{
+  schedule::{cancel_schedule, get_schedule, update_action_schedule},
+  state::{action_state_cache, action_states, db_client},
+};
 
 impl super::KomodoResource for Action {
   type Config = ActionConfig;
@@ -39,7 +42,12 @@ impl super::KomodoResource for Action {
   async fn to_list_item(
     action: Resource<Self::Config, Self::Info>,
   ) -> Self::ListItem {
-    let state = get_action_state(&action.id).await;
+    let target = ResourceTarget::Action(action.id.clone());
+    let (state, schedule) = tokio::join!(
+      get_action_state(&action.id),
+      get_schedule(&target)
+    );
+    let schedule = schedule.unwrap_or_default();
     ActionListItem {
       name: action.name,
       id: action.id,
@@ -48,6 +56,9 @@ impl super::KomodoResource for Action {
       info: ActionListItemInfo {
         state,
         last_run_at: action.info.last_run_at,
+        next_scheduled_run: schedule.next_scheduled_run,
+        last_scheduled_run: action.info.last_scheduled_run,
+        schedule_error: schedule.error,
       },
     }
   }
@@ -83,9 +94,10 @@ impl super::KomodoResource for Action {
   }
 
   async fn post_create(
-    _created: &Resource<Self::Config, Self::Info>,
+    created: &Resource<Self::Config, Self::Info>,
     _update: &mut Update,
   ) -> anyhow::Result<()> {
+    update_action_schedule(created).await;
     refresh_action_state_cache().await;
     Ok(())
   }
@@ -131,9 +143,11 @@ impl super::KomodoResource for Action {
   }
 
   async fn post_delete(
-    _resource: &Resource<Self::Config, Self::Info>,
+    resource: &Resource<Self::Config, Self::Info>,
     _update: &mut Update,
   ) -> anyhow::Result<()> {
+    cancel_schedule(&ResourceTarget::Action(resource.id.clone()))
+      .await;
     Ok(())
   }
 }
@@ -1,8 +1,10 @@
+use anyhow::{anyhow, Context};
 use derive_variants::ExtractVariant;
 use komodo_client::entities::{
   alerter::{
-    Alerter, AlerterConfig, AlerterConfigDiff, AlerterListItem,
-    AlerterListItemInfo, AlerterQuerySpecifics, PartialAlerterConfig,
+    Alerter, AlerterConfig, AlerterConfigDiff, AlerterEndpoint,
+    AlerterListItem, AlerterListItemInfo, AlerterQuerySpecifics,
+    PartialAlerterConfig,
   },
   resource::Resource,
   update::Update,
@@ -60,10 +62,10 @@ impl super::KomodoResource for Alerter {
   }
 
   async fn validate_create_config(
-    _config: &mut Self::PartialConfig,
+    config: &mut Self::PartialConfig,
     _user: &User,
   ) -> anyhow::Result<()> {
-    Ok(())
+    validate_config(config)
   }
 
   async fn post_create(
@@ -81,10 +83,10 @@ impl super::KomodoResource for Alerter {
 
   async fn validate_update_config(
     _id: &str,
-    _config: &mut Self::PartialConfig,
+    config: &mut Self::PartialConfig,
     _user: &User,
   ) -> anyhow::Result<()> {
-    Ok(())
+    validate_config(config)
   }
 
   async fn post_update(
@@ -120,3 +122,101 @@ impl super::KomodoResource for Alerter {
     Ok(())
   }
 }
+
+fn validate_config(
+  config: &PartialAlerterConfig,
+) -> anyhow::Result<()> {
+  match &config.endpoint {
+    Some(AlerterEndpoint::Email(endpoint)) => {
+      validate_secret_reference(&endpoint.password)
+        .context("Invalid Email alerter password")
+    }
+    Some(AlerterEndpoint::Ntfy(endpoint)) => {
+      validate_secret_reference(&endpoint.token)
+        .context("Invalid ntfy alerter token")
+    }
+    Some(AlerterEndpoint::Gotify(endpoint)) => {
+      validate_secret_reference(&endpoint.token)
+        .context("Invalid Gotify alerter token")
+    }
+    Some(AlerterEndpoint::Pushover(endpoint)) => {
+      validate_secret_reference(&endpoint.token)
+        .context("Invalid Pushover alerter token")
+    }
+    Some(AlerterEndpoint::Custom(endpoint)) => {
+      validate_custom_headers(&endpoint.headers)
+    }
+    _ => Ok(()),
+  }
+}
+
+/// Credentials must be given as a single reference to a core secret
+/// or variable, eg. `[[SMTP_PASSWORD]]`, so they are not stored
+/// in the alerter config, returned to readers or written to syncs.
+fn validate_secret_reference(value: &str) -> anyhow::Result<()> {
+  if value.is_empty() {
+    return Ok(());
+  }
+  let name = value
+    .strip_prefix("[[")
+    .and_then(|value| value.strip_suffix("]]"))
+    .map(str::trim);
+  match name {
+    Some(name)
+      if !name.is_empty()
+        && !name.contains(['[', ']'])
+        && !name.chars().any(char::is_whitespace) =>
+    {
+      Ok(())
+    }
+    _ => Err(anyhow!(
+      "Must reference a core secret or variable, eg. [[SMTP_PASSWORD]]"
+    )),
+  }
+}
+
+/// Headers which carry credentials, eg. `Authorization`, must take
+/// the credential from a core secret or variable, eg.
+/// `Authorization: Bearer [[WEBHOOK_TOKEN]]`.
+fn validate_custom_headers(headers: &str) -> anyhow::Result<()> {
+  for line in headers
+    .lines()
+    .map(str::trim)
+    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+  {
+    let Some((name, value)) = line.split_once(':') else {
+      // Malformed lines are reported when sending.
+      continue;
+    };
+    let name = name.trim().to_ascii_lowercase();
+    let is_credential = name == "cookie"
+      || ["auth", "key", "token", "secret", "password"]
+        .iter()
+        .any(|part| name.contains(part));
+    if is_credential && !contains_secret_reference(value) {
+      return Err(anyhow!(
+        "Invalid Custom alerter header '{}': Must reference a core secret or variable, eg. Bearer [[WEBHOOK_TOKEN]]",
+        name
+      ));
+    }
+  }
+  Ok(())
+}
+
+fn contains_secret_reference(value: &str) -> bool {
+  let mut rest = value;
+  while let Some((_, after)) = rest.split_once("[[") {
+    let Some((name, after)) = after.split_once("]]") else {
+      return false;
+    };
+    let name = name.trim();
+    if !name.is_empty()
+      && !name.contains('[')
+      && !name.chars().any(char::is_whitespace)
+    {
+      return true;
+    }
+    rest = after;
+  }
+  false
+}
@@ -1,12 +1,14 @@
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 use formatting::format_serror;
 use komodo_client::entities::{
   build::Build,
   deployment::{
-    conversions_from_str, Deployment, DeploymentConfig,
-    DeploymentConfigDiff, DeploymentImage, DeploymentListItem,
-    DeploymentListItemInfo, DeploymentQuerySpecifics,
-    DeploymentState, PartialDeploymentConfig,
+    container_names, conversions_from_str, fixed_host_port_mappings,
+    ulimit_from_str, validate_memory_size, DeployStrategy,
+    Deployment, DeploymentConfig, DeploymentConfigDiff,
+    DeploymentImage, DeploymentListItem, DeploymentListItemInfo,
+    DeploymentQuerySpecifics, DeploymentState,
+    PartialDeploymentConfig,
   },
   environment_vars_from_str,
   permission::PermissionLevel,
@@ -16,7 +18,7 @@ use komodo_client::entities::{
   user::User,
   Operation, ResourceTargetVariant,
 };
-use mungos::mongodb::Collection;
+use mungos::{find::find_collect, mongodb::Collection};
 use periphery_client::api::container::RemoveContainer;
 
 use crate::{
@@ -129,7 +131,7 @@ impl super::KomodoResource for Deployment {
     config: &mut Self::PartialConfig,
     user: &User,
   ) -> anyhow::Result<()> {
-    validate_config(config, user).await
+    validate_config(config, &DeploymentConfig::default(), user).await
   }
 
   async fn post_create(
@@ -161,11 +163,16 @@ impl super::KomodoResource for Deployment {
   }
 
   async fn validate_update_config(
-    _id: &str,
+    id: &str,
     config: &mut Self::PartialConfig,
     user: &User,
   ) -> anyhow::Result<()> {
-    validate_config(config, user).await
+    let current = super::get::<Deployment>(id).await?;
+    if let Some(replicas) = config.replicas {
+      check_container_name_collisions(id, &current.name, replicas)
+        .await?;
+    }
+    validate_config(config, &current.config, user).await
   }
 
   async fn post_update(
@@ -243,22 +250,24 @@ impl super::KomodoResource for Deployment {
         return Ok(());
       }
     };
-    match periphery
-      .request(RemoveContainer {
-        name: deployment.name.clone(),
-        signal: deployment.config.termination_signal.into(),
-        time: deployment.config.termination_timeout.into(),
-      })
-      .await
-    {
-      Ok(log) => update.logs.push(log),
-      Err(e) => update.push_error_log(
-        "remove container",
-        format_serror(
-          &e.context("failed to remove container").into(),
+    for name in deployment.container_names() {
+      match periphery
+        .request(RemoveContainer {
+          name,
+          signal: deployment.config.termination_signal.into(),
+          time: deployment.config.termination_timeout.into(),
+        })
+        .await
+      {
+        Ok(log) => update.logs.push(log),
+        Err(e) => update.push_error_log(
+          "remove container",
+          format_serror(
+            &e.context("failed to remove container").into(),
+          ),
         ),
-      ),
-    };
+      };
+    }
     Ok(())
   }
 
@@ -270,9 +279,41 @@ impl super::KomodoResource for Deployment {
   }
 }
 
+/// Replica containers are named `{name}-{index}`, so the container names
+/// of one Deployment can't be those of another,
+/// eg. `app-2` and `app` with 2 replicas.
+/// `id` is empty for a new Deployment.
+pub async fn check_container_name_collisions(
+  id: &str,
+  name: &str,
+  replicas: i32,
+) -> anyhow::Result<()> {
+  let names = container_names(name, replicas);
+  let deployments =
+    find_collect(&db_client().deployments, None, None)
+      .await
+      .context("Failed to get Deployments from db")?;
+  for other in deployments.iter().filter(|d| d.id != id) {
+    if let Some(collision) = other
+      .container_names()
+      .into_iter()
+      .find(|other_name| names.contains(other_name))
+    {
+      return Err(anyhow!(
+        "Container name '{collision}' is already used by Deployment '{}'",
+        other.name
+      ));
+    }
+  }
+  Ok(())
+}
+
+/// `current` is the config being updated,
+/// for the fields missing from the partial config.
 #[instrument(skip(user))]
 async fn validate_config(
   config: &mut PartialDeploymentConfig,
+  current: &DeploymentConfig,
   user: &User,
 ) -> anyhow::Result<()> {
   if let Some(server_id) = &config.server_id {
@@ -319,5 +360,48 @@ async fn validate_config(
   if let Some(extra_args) = &mut config.extra_args {
     extra_args.retain(|v| !empty_or_only_spaces(v))
   }
+  let deploy_strategy =
+    config.deploy_strategy.unwrap_or(current.deploy_strategy);
+  let network = config.network.as_ref().unwrap_or(&current.network);
+  if deploy_strategy == DeployStrategy::BlueGreen && network == "host"
+  {
+    return Err(anyhow!(
+      "BlueGreen deploys can't use the host network, as both containers would bind the same ports"
+    ));
+  }
+  let ports = config.ports.as_ref().unwrap_or(&current.ports);
+  if deploy_strategy == DeployStrategy::BlueGreen {
+    let fixed =
+      fixed_host_port_mappings(ports).context("Invalid ports")?;
+    if !fixed.is_empty() {
+      return Err(anyhow!(
+        "BlueGreen deploys can't publish fixed host ports ({}), as both containers would bind them",
+        fixed.join(", ")
+      ));
+    }
+  }
+  if let Some(replicas) = config.replicas {
+    if replicas < 1 {
+      return Err(anyhow!("Replicas must be at least 1"));
+    }
+  }
+  if let Some(cpus) = config.cpus {
+    if !cpus.is_finite() || cpus < 0.0 {
+      return Err(anyhow!("Invalid cpus: {cpus}"));
+    }
+  }
+  if let Some(memory) = &config.memory {
+    validate_memory_size(memory).context("Invalid memory")?;
+  }
+  if let Some(memory_reservation) = &config.memory_reservation {
+    validate_memory_size(memory_reservation)
+      .context("Invalid memory reservation")?;
+  }
+  if let Some(ulimits) = &mut config.ulimits {
+    ulimits.retain(|v| !empty_or_only_spaces(v));
+    for ulimit in ulimits {
+      ulimit_from_str(ulimit).context("Invalid ulimits")?;
+    }
+  }
   Ok(())
 }
@@ -65,6 +65,7 @@ pub use action::{
 pub use build::{
   refresh_build_state_cache, spawn_build_state_refresh_loop,
 };
+pub use deployment::check_container_name_collisions;
 pub use procedure::{
   refresh_procedure_state_cache, spawn_procedure_state_refresh_loop,
 };
@@ -72,6 +73,7 @@ pub use refresh::spawn_resource_refresh_loop;
 pub use repo::{
   refresh_repo_state_cache, spawn_repo_state_refresh_loop,
 };
+pub use server::redact_enrollment_token;
 pub use sync::{
   refresh_resource_sync_state_cache,
   spawn_resource_sync_state_refresh_loop,
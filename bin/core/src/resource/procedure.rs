@@ -10,8 +10,8 @@ use komodo_client::{
     permission::PermissionLevel,
     procedure::{
       PartialProcedureConfig, Procedure, ProcedureConfig,
-      ProcedureConfigDiff, ProcedureListItem, ProcedureListItemInfo,
-      ProcedureQuerySpecifics, ProcedureState,
+      ProcedureConfigDiff, ProcedureInfo, ProcedureListItem,
+      ProcedureListItemInfo, ProcedureQuerySpecifics, ProcedureState,
     },
     repo::Repo,
     resource::Resource,
@@ -20,7 +20,7 @@ use komodo_client::{
     sync::ResourceSync,
     update::Update,
     user::User,
-    Operation, ResourceTargetVariant,
+    Operation, ResourceTarget, ResourceTargetVariant,
   },
 };
 use mungos::{
@@ -30,6 +30,9 @@ use mungos::{
 
 use crate::{
   config::core_config,
+  schedule::{
+    cancel_schedule, get_schedule, update_procedure_schedule,
+  },
   state::{action_states, db_client, procedure_state_cache},
 };
 
@@ -37,7 +40,7 @@ impl super::KomodoResource for Procedure {
   type Config = ProcedureConfig;
   type PartialConfig = PartialProcedureConfig;
   type ConfigDiff = ProcedureConfigDiff;
-  type Info = ();
+  type Info = ProcedureInfo;
   type ListItem = ProcedureListItem;
   type QuerySpecifics = ProcedureQuerySpecifics;
 
@@ -53,7 +56,12 @@ impl super::KomodoResource for Procedure {
   async fn to_list_item(
     procedure: Resource<Self::Config, Self::Info>,
   ) -> Self::ListItem {
-    let state = get_procedure_state(&procedure.id).await;
+    let target = ResourceTarget::Procedure(procedure.id.clone());
+    let (state, schedule) = tokio::join!(
+      get_procedure_state(&procedure.id),
+      get_schedule(&target)
+    );
+    let schedule = schedule.unwrap_or_default();
     ProcedureListItem {
       name: procedure.name,
       id: procedure.id,
@@ -62,6 +70,9 @@ impl super::KomodoResource for Procedure {
       info: ProcedureListItemInfo {
         stages: procedure.config.stages.len() as i64,
         state,
+        next_scheduled_run: schedule.next_scheduled_run,
+        last_scheduled_run: procedure.info.last_scheduled_run,
+        schedule_error: schedule.error,
       },
     }
   }
@@ -93,9 +104,10 @@ impl super::KomodoResource for Procedure {
   }
 
   async fn post_create(
-    _created: &Resource<Self::Config, Self::Info>,
+    created: &Resource<Self::Config, Self::Info>,
     _update: &mut Update,
   ) -> anyhow::Result<()> {
+    update_procedure_schedule(created).await;
     refresh_procedure_state_cache().await;
     Ok(())
   }
@@ -141,9 +153,11 @@ impl super::KomodoResource for Procedure {
   }
 
   async fn post_delete(
-    _resource: &Resource<Self::Config, Self::Info>,
+    resource: &Resource<Self::Config, Self::Info>,
     _update: &mut Update,
   ) -> anyhow::Result<()> {
+    cancel_schedule(&ResourceTarget::Procedure(resource.id.clone()))
+      .await;
     Ok(())
   }
 }
@@ -253,6 +267,16 @@ async fn validate_config(
             .await?;
           params.deployment = deployment.id;
         }
+        Execution::RollbackDeployment(params) => {
+          let deployment =
+            super::get_check_permissions::<Deployment>(
+              &params.deployment,
+              user,
+              PermissionLevel::Execute,
+            )
+            .await?;
+          params.deployment = deployment.id;
+        }
         Execution::StartDeployment(params) => {
           let deployment =
             super::get_check_permissions::<Deployment>(
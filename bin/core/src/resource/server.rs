@@ -4,7 +4,8 @@ use komodo_client::entities::{
   resource::Resource,
   server::{
     PartialServerConfig, Server, ServerConfig, ServerConfigDiff,
-    ServerListItem, ServerListItemInfo, ServerQuerySpecifics,
+    ServerInfo, ServerListItem, ServerListItemInfo,
+    ServerQuerySpecifics,
   },
   update::Update,
   user::User,
@@ -14,6 +15,7 @@ use mungos::mongodb::{bson::doc, Collection};
 
 use crate::{
   config::core_config,
+  helpers::tunnel::close_stale_periphery_tunnel,
   monitor::update_cache_for_server,
   state::{action_states, db_client, server_status_cache},
 };
@@ -22,7 +24,7 @@ impl super::KomodoResource for Server {
   type Config = ServerConfig;
   type PartialConfig = PartialServerConfig;
   type ConfigDiff = ServerConfigDiff;
-  type Info = ();
+  type Info = ServerInfo;
   type ListItem = ServerListItem;
   type QuerySpecifics = ServerQuerySpecifics;
 
@@ -54,6 +56,8 @@ impl super::KomodoResource for Server {
         send_cpu_alerts: server.config.send_cpu_alerts,
         send_mem_alerts: server.config.send_mem_alerts,
         send_disk_alerts: server.config.send_disk_alerts,
+        send_network_alerts: server.config.send_network_alerts,
+        send_load_alerts: server.config.send_load_alerts,
       },
     }
   }
@@ -187,6 +191,16 @@ impl super::KomodoResource for Server {
     _update: &mut Update,
   ) -> anyhow::Result<()> {
     server_status_cache().remove(&resource.id).await;
+    close_stale_periphery_tunnel(&resource.id, "");
     Ok(())
   }
 }
+
+/// The enrollment token hash is redacted
+/// before the Server is returned to the user.
+pub fn redact_enrollment_token(server: &mut Server) {
+  if !server.info.enrollment_token_hash.is_empty() {
+    server.info.enrollment_token_hash =
+      String::from("##############");
+  }
+}
@@ -0,0 +1,275 @@
+use std::{collections::HashMap, sync::OnceLock};
+
+use anyhow::{anyhow, Context};
+use async_timing_util::{wait_until_timelength, Timelength};
+use chrono::{Local, TimeZone};
+use croner::Cron;
+use komodo_client::{
+  api::execute::{RunAction, RunProcedure},
+  entities::{
+    action::Action,
+    komodo_timestamp,
+    procedure::{Procedure, ProcedureInfo},
+    user::{action_user, procedure_user},
+    ResourceTarget,
+  },
+};
+use mungos::{
+  by_id::update_one_by_id,
+  find::find_collect,
+  mongodb::bson::{doc, to_document},
+};
+use tokio::sync::RwLock;
+
+use crate::{
+  api::execute::{inner_handler, ExecuteRequest},
+  state::db_client,
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct Schedule {
+  /// The CRON expression
+  pub schedule: String,
+  /// The TZ identifier. Empty means Core local timezone.
+  pub timezone: String,
+  /// The next scheduled run, if the schedule is valid.
+  pub next_scheduled_run: Option<i64>,
+  /// The error in parsing the schedule, if any.
+  pub error: Option<String>,
+}
+
+type Schedules = RwLock<HashMap<ResourceTarget, Schedule>>;
+
+fn schedules() -> &'static Schedules {
+  static SCHEDULES: OnceLock<Schedules> = OnceLock::new();
+  SCHEDULES.get_or_init(Default::default)
+}
+
+/// Get the schedule for the target.
+/// Returns None if the target is not scheduled.
+pub async fn get_schedule(
+  target: &ResourceTarget,
+) -> Option<Schedule> {
+  schedules().read().await.get(target).cloned()
+}
+
+/// Loads all Procedure / Action schedules from the database.
+pub async fn init_schedules() {
+  let (procedures, actions) = tokio::join!(
+    find_collect(&db_client().procedures, None, None),
+    find_collect(&db_client().actions, None, None),
+  );
+  match procedures {
+    Ok(procedures) => {
+      for procedure in procedures {
+        update_procedure_schedule(&procedure).await;
+      }
+    }
+    Err(e) => {
+      error!("Failed to get Procedures from db for schedules | {e:#}")
+    }
+  }
+  match actions {
+    Ok(actions) => {
+      for action in actions {
+        update_action_schedule(&action).await;
+      }
+    }
+    Err(e) => {
+      error!("Failed to get Actions from db for schedules | {e:#}")
+    }
+  }
+}
+
+pub async fn update_procedure_schedule(procedure: &Procedure) {
+  update_schedule(
+    ResourceTarget::Procedure(procedure.id.clone()),
+    procedure.config.schedule_enabled,
+    &procedure.config.schedule,
+    &procedure.config.schedule_timezone,
+  )
+  .await
+}
+
+pub async fn update_action_schedule(action: &Action) {
+  update_schedule(
+    ResourceTarget::Action(action.id.clone()),
+    action.config.schedule_enabled,
+    &action.config.schedule,
+    &action.config.schedule_timezone,
+  )
+  .await
+}
+
+async fn update_schedule(
+  target: ResourceTarget,
+  enabled: bool,
+  schedule: &str,
+  timezone: &str,
+) {
+  let schedule = schedule.trim();
+  if !enabled || schedule.is_empty() {
+    cancel_schedule(&target).await;
+    return;
+  }
+  let mut lock = schedules().write().await;
+  let entry = lock.entry(target).or_default();
+  entry.schedule = schedule.to_string();
+  entry.timezone = timezone.trim().to_string();
+  match find_next_occurrence(
+    &entry.schedule,
+    &entry.timezone,
+    komodo_timestamp(),
+  ) {
+    Ok(next) => {
+      entry.next_scheduled_run = Some(next);
+      entry.error = None;
+    }
+    Err(e) => {
+      entry.next_scheduled_run = None;
+      entry.error = Some(format!("{e:#}"));
+    }
+  }
+}
+
+pub async fn cancel_schedule(target: &ResourceTarget) {
+  schedules().write().await.remove(target);
+}
+
+pub fn spawn_schedule_executor() {
+  tokio::spawn(async move {
+    loop {
+      wait_until_timelength(Timelength::OneSecond, 0).await;
+      let now = komodo_timestamp();
+      let mut due = Vec::new();
+      {
+        let mut lock = schedules().write().await;
+        for (target, schedule) in lock.iter_mut() {
+          match schedule.next_scheduled_run {
+            Some(next) if next <= now => {}
+            _ => continue,
+          }
+          due.push(target.clone());
+          match find_next_occurrence(
+            &schedule.schedule,
+            &schedule.timezone,
+            now,
+          ) {
+            Ok(next) => schedule.next_scheduled_run = Some(next),
+            Err(e) => {
+              schedule.next_scheduled_run = None;
+              schedule.error = Some(format!("{e:#}"));
+            }
+          }
+        }
+      }
+      for target in due {
+        tokio::spawn(async move {
+          if let Err(e) = record_scheduled_run(&target, now).await {
+            warn!("{e:#}");
+          }
+          run_scheduled(target).await;
+        });
+      }
+    }
+  });
+}
+
+/// Persists the last scheduled run on the resource info,
+/// so it survives Core restarts.
+async fn record_scheduled_run(
+  target: &ResourceTarget,
+  ts: i64,
+) -> anyhow::Result<()> {
+  let db = db_client();
+  match target {
+    ResourceTarget::Procedure(id) => {
+      // Procedures created before ProcedureInfo existed have null info,
+      // so the whole info document is set.
+      let info = to_document(&ProcedureInfo {
+        last_scheduled_run: Some(ts),
+      })
+      .context("Failed to serialize ProcedureInfo")?;
+      update_one_by_id(
+        &db.procedures,
+        id,
+        doc! { "$set": { "info": info } },
+        None,
+      )
+      .await
+    }
+    ResourceTarget::Action(id) => {
+      update_one_by_id(
+        &db.actions,
+        id,
+        doc! { "$set": { "info.last_scheduled_run": ts } },
+        None,
+      )
+      .await
+    }
+    _ => return Ok(()),
+  }
+  .with_context(|| {
+    format!("Failed to record scheduled run for {target:?}")
+  })?;
+  Ok(())
+}
+
+async fn run_scheduled(target: ResourceTarget) {
+  let (request, user) = match &target {
+    ResourceTarget::Procedure(id) => (
+      ExecuteRequest::RunProcedure(RunProcedure {
+        procedure: id.clone(),
+      }),
+      procedure_user(),
+    ),
+    ResourceTarget::Action(id) => (
+      ExecuteRequest::RunAction(RunAction { action: id.clone() }),
+      action_user(),
+    ),
+    _ => return,
+  };
+  if let Err(e) = inner_handler(request, user.to_owned()).await {
+    warn!("Failed to run scheduled execution for {target:?} | {e:#}");
+  }
+}
+
+/// Finds the next occurrence of the CRON schedule
+/// strictly after the given timestamp (ms).
+fn find_next_occurrence(
+  schedule: &str,
+  timezone: &str,
+  after_ts: i64,
+) -> anyhow::Result<i64> {
+  let cron = Cron::new(schedule)
+    .with_seconds_optional()
+    .parse()
+    .map_err(|e| anyhow!("{e:?}"))
+    .context("Failed to parse schedule CRON expression")?;
+  let next = if timezone.is_empty() {
+    let after = Local
+      .timestamp_millis_opt(after_ts)
+      .single()
+      .context("Invalid timestamp")?;
+    cron
+      .find_next_occurrence(&after, false)
+      .map_err(|e| anyhow!("{e:?}"))
+      .context("Failed to find next schedule occurrence")?
+      .timestamp_millis()
+  } else {
+    let tz: chrono_tz::Tz =
+      timezone.parse().map_err(|e| anyhow!("{e}")).with_context(
+        || format!("Invalid schedule timezone: {timezone}"),
+      )?;
+    let after = tz
+      .timestamp_millis_opt(after_ts)
+      .single()
+      .context("Invalid timestamp")?;
+    cron
+      .find_next_occurrence(&after, false)
+      .map_err(|e| anyhow!("{e:?}"))
+      .context("Failed to find next schedule occurrence")?
+      .timestamp_millis()
+  };
+  Ok(next)
+}
@@ -542,41 +542,32 @@ fn build_cache_for_stack<'a>(
         // Here can diff the changes, to see if they merit a redeploy.
 
         // See if any remote contents don't match deployed contents
-        match (
+        if let (Some(deployed_contents), Some(remote_contents)) = (
           &original.info.deployed_contents,
           &original.info.remote_contents,
         ) {
-          (Some(deployed_contents), Some(remote_contents)) => {
-            for FileContents { path, contents } in remote_contents {
-              if let Some(deployed) =
-                deployed_contents.iter().find(|c| &c.path == path)
-              {
-                if &deployed.contents != contents {
-                  cache.insert(
-                    target,
-                    Some((
-                      format!(
-                        "File contents for {path} have changed"
-                      ),
-                      after,
-                    )),
-                  );
-                  return Ok(());
-                }
-              } else {
+          for FileContents { path, contents } in remote_contents {
+            if let Some(deployed) =
+              deployed_contents.iter().find(|c| &c.path == path)
+            {
+              if &deployed.contents != contents {
                 cache.insert(
                   target,
                   Some((
-                    format!("New file contents at {path}"),
+                    format!("File contents for {path} have changed"),
                     after,
                   )),
                 );
                 return Ok(());
               }
+            } else {
+              cache.insert(
+                target,
+                Some((format!("New file contents at {path}"), after)),
+              );
+              return Ok(());
             }
           }
-          // Maybe should handle other cases
-          _ => {}
         }
 
         // Merge toml resource config (partial) onto default resource config.
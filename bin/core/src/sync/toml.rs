@@ -188,9 +188,8 @@ impl ToToml for Stack {
     config
       .into_iter()
       .map(|(key, value)| {
-        match key.as_str() {
-          "server_id" => return Ok((String::from("server"), value)),
-          _ => {}
+        if key == "server_id" {
+          return Ok((String::from("server"), value));
         }
         Ok((key, value))
       })
@@ -456,6 +455,15 @@ impl ToToml for Procedure {
                 .unwrap_or(&String::new()),
             )
           }
+          Execution::RollbackDeployment(exec) => {
+            exec.deployment.clone_from(
+              all
+                .deployments
+                .get(&exec.deployment)
+                .map(|r| &r.name)
+                .unwrap_or(&String::new()),
+            )
+          }
           Execution::StartDeployment(exec) => {
             exec.deployment.clone_from(
               all
@@ -28,8 +28,17 @@ use crate::{
   },
 };
 
+mod log;
+mod periphery;
+mod terminal;
+
 pub fn router() -> Router {
-  Router::new().route("/update", get(ws_handler))
+  Router::new()
+    .route("/update", get(ws_handler))
+    .route("/log", get(log::handler))
+    .route("/exec", get(terminal::exec_handler))
+    .route("/terminal", get(terminal::host_handler))
+    .route("/periphery", get(periphery::handler))
 }
 
 #[instrument(level = "debug")]
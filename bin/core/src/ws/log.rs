@@ -0,0 +1,167 @@
+use std::cmp;
+
+use anyhow::{anyhow, Context};
+use axum::{
+  extract::{
+    ws::{Message, WebSocket},
+    WebSocketUpgrade,
+  },
+  response::IntoResponse,
+};
+use futures::StreamExt;
+use komodo_client::{
+  entities::{
+    deployment::Deployment, permission::PermissionLevel,
+    server::Server, user::User,
+  },
+  ws::{LogStreamMessage, LogStreamQuery, LogStreamTarget},
+};
+use periphery_client::{
+  api::stream::FollowLog, tungstenite, PeripheryWebSocket,
+};
+
+use crate::{
+  helpers::periphery_client, resource, stack::get_stack_and_server,
+};
+
+use super::ws_login;
+
+const MAX_LOG_LENGTH: u64 = 5000;
+
+/// Proxies `docker logs -f` from periphery.
+/// After login, the client sends a [LogStreamQuery].
+#[instrument(level = "debug")]
+pub async fn handler(ws: WebSocketUpgrade) -> impl IntoResponse {
+  ws.on_upgrade(|socket| async move {
+    let Some((mut socket, user)) = ws_login(socket).await else {
+      return;
+    };
+    if let Err(e) = follow_log(&mut socket, &user).await {
+      if let Ok(msg) =
+        LogStreamMessage::Error(format!("{e:#}")).to_json_string()
+      {
+        let _ = socket.send(Message::Text(msg)).await;
+      }
+    }
+    let _ = socket.close().await;
+  })
+}
+
+async fn follow_log(
+  socket: &mut WebSocket,
+  user: &User,
+) -> anyhow::Result<()> {
+  let query = match socket.recv().await {
+    Some(Ok(Message::Text(query))) => {
+      LogStreamQuery::from_json_str(&query)?
+    }
+    Some(Ok(msg)) => {
+      return Err(anyhow!("invalid log stream query: {msg:?}"))
+    }
+    Some(Err(e)) => {
+      return Err(
+        anyhow::Error::from(e)
+          .context("failed to get log stream query"),
+      )
+    }
+    None => return Ok(()),
+  };
+
+  let mut periphery = connect_periphery(query, user).await?;
+
+  loop {
+    tokio::select! {
+      msg = periphery.next() => match msg {
+        Some(Ok(tungstenite::Message::Text(msg))) => {
+          socket
+            .send(Message::Text(msg))
+            .await
+            .context("failed to send log line")?;
+        }
+        Some(Ok(tungstenite::Message::Close(_))) | None => break,
+        Some(Ok(_)) => {}
+        Some(Err(e)) => {
+          return Err(
+            anyhow::Error::from(e)
+              .context("failed to recieve log line from periphery"),
+          )
+        }
+      },
+      msg = socket.recv() => match msg {
+        // Only close messages are expected after the query.
+        Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+        Some(Ok(_)) => {}
+      },
+    }
+  }
+
+  let _ = periphery.close(None).await;
+
+  Ok(())
+}
+
+async fn connect_periphery(
+  LogStreamQuery {
+    target,
+    tail,
+    timestamps,
+  }: LogStreamQuery,
+  user: &User,
+) -> anyhow::Result<PeripheryWebSocket> {
+  let tail = cmp::min(tail, MAX_LOG_LENGTH);
+  let (server, request) = match target {
+    LogStreamTarget::Container { server, container } => {
+      let server = resource::get_check_permissions::<Server>(
+        &server,
+        user,
+        PermissionLevel::Read,
+      )
+      .await?;
+      let request = FollowLog::Container {
+        name: container,
+        tail,
+        timestamps,
+      };
+      (server, request)
+    }
+    LogStreamTarget::Deployment { deployment } => {
+      let deployment = resource::get_check_permissions::<Deployment>(
+        &deployment,
+        user,
+        PermissionLevel::Read,
+      )
+      .await?;
+      if deployment.config.server_id.is_empty() {
+        return Err(anyhow!("deployment has no server attached"));
+      }
+      let server =
+        resource::get::<Server>(&deployment.config.server_id).await?;
+      let request = FollowLog::Container {
+        name: deployment.container_name(),
+        tail,
+        timestamps,
+      };
+      (server, request)
+    }
+    LogStreamTarget::StackService { stack, service } => {
+      let (stack, server) = get_stack_and_server(
+        &stack,
+        user,
+        PermissionLevel::Read,
+        true,
+      )
+      .await?;
+      let request = FollowLog::ComposeService {
+        project: stack.project_name(false),
+        service,
+        tail,
+        timestamps,
+      };
+      (server, request)
+    }
+  };
+  periphery_client(&server)?
+    .follow_log(&request)
+    .await
+    .context("failed to follow log on periphery")
+}
@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Context};
+use axum::{
+  extract::{
+    ws::{Message, WebSocket},
+    WebSocketUpgrade,
+  },
+  http::HeaderMap,
+  response::Response,
+};
+use komodo_client::entities::server::Server;
+use mungos::mongodb::bson::doc;
+use periphery_client::{
+  api::stream::ContainerEvent,
+  tunnel::{
+    PeripheryTunnel, TunnelMessage, TunnelRequest, TunnelResponse,
+  },
+};
+use reqwest::StatusCode;
+use serror::AddStatusCode;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::{
+  helpers::tunnel::{
+    hash_enrollment_token, insert_periphery_tunnel,
+    remove_periphery_tunnel,
+  },
+  monitor::{
+    events::spawn_tunneled_event_handler, update_cache_for_server,
+  },
+  state::db_client,
+};
+
+/// Periphery dials out to this endpoint when configured with a `core_address`,
+/// authenticating with the Server enrollment token.
+/// Core then sends periphery requests over the connection.
+pub async fn handler(
+  headers: HeaderMap,
+  ws: WebSocketUpgrade,
+) -> serror::Result<Response> {
+  let server = authenticate_periphery(&headers)
+    .await
+    .status_code(StatusCode::UNAUTHORIZED)?;
+  Ok(ws.on_upgrade(|socket| async move {
+    let (tunnel, requests) = PeripheryTunnel::new(100);
+    insert_periphery_tunnel(&server, tunnel.clone());
+    info!("periphery connected for server {}", server.name);
+    let cache_server = server.clone();
+    tokio::spawn(async move {
+      update_cache_for_server(&cache_server).await;
+    });
+    let events = spawn_tunneled_event_handler(server.id.clone());
+    if let Err(e) = serve_tunnel(socket, requests, events).await {
+      warn!("periphery connection error | {e:#}");
+    }
+    remove_periphery_tunnel(&server.id, &tunnel);
+  }))
+}
+
+async fn authenticate_periphery(
+  headers: &HeaderMap,
+) -> anyhow::Result<Server> {
+  let token = headers
+    .get("authorization")
+    .context("no enrollment token passed")?
+    .to_str()
+    .context("failed to convert enrollment token to str")?;
+  if token.is_empty() {
+    return Err(anyhow!("enrollment token is empty"));
+  }
+  db_client()
+    .servers
+    .find_one(doc! {
+      "info.enrollment_token_hash": hash_enrollment_token(token)
+    })
+    .await
+    .context("failed to query db for servers")?
+    .context("no server matches enrollment token")
+}
+
+/// Sends requests to periphery and routes back the responses,
+/// along with any container events, until either side closes.
+async fn serve_tunnel(
+  mut socket: WebSocket,
+  mut requests: mpsc::Receiver<TunnelRequest>,
+  events: mpsc::Sender<ContainerEvent>,
+) -> anyhow::Result<()> {
+  let mut pending =
+    HashMap::<u64, oneshot::Sender<TunnelResponse>>::new();
+  let mut next_id = 0;
+
+  let res = loop {
+    tokio::select! {
+      request = requests.recv() => {
+        // All tunnel handles dropped, ie the server was removed.
+        let Some(TunnelRequest { request, response }) = request else {
+          break Ok(());
+        };
+        next_id += 1;
+        let msg = serde_json::to_string(&TunnelMessage::Request {
+          id: next_id,
+          request,
+        })
+        .context("failed to serialize TunnelMessage")?;
+        if let Err(e) = socket.send(Message::Text(msg)).await {
+          break Err(
+            anyhow::Error::from(e)
+              .context("failed to send request to periphery"),
+          );
+        }
+        // Forget requests which timed out waiting for a response.
+        pending.retain(|_, response| !response.is_closed());
+        pending.insert(next_id, response);
+      }
+      msg = socket.recv() => match msg {
+        Some(Ok(Message::Text(msg))) => {
+          match serde_json::from_str::<TunnelMessage>(&msg) {
+            Ok(TunnelMessage::Response { id, response }) => {
+              if let Some(tx) = pending.remove(&id) {
+                let _ = tx.send(response);
+              }
+            }
+            Ok(TunnelMessage::Event { event }) => {
+              // Dropped if behind, the next poll catches up.
+              let _ = events.try_send(event);
+            }
+            Ok(msg) => {
+              warn!("unexpected message from periphery | {msg:?}")
+            }
+            Err(e) => {
+              warn!("failed to parse message from periphery | {e:?}")
+            }
+          }
+        }
+        Some(Ok(Message::Close(_))) | None => break Ok(()),
+        Some(Ok(_)) => {}
+        Some(Err(e)) => {
+          break Err(
+            anyhow::Error::from(e)
+              .context("failed to recieve message from periphery"),
+          )
+        }
+      },
+    }
+  };
+
+  let _ = socket.close().await;
+
+  res
+}
@@ -0,0 +1,292 @@
+use anyhow::{anyhow, Context};
+use axum::{
+  extract::{
+    ws::{Message, WebSocket},
+    WebSocketUpgrade,
+  },
+  response::IntoResponse,
+};
+use futures::{SinkExt, StreamExt};
+use komodo_client::{
+  entities::{
+    deployment::Deployment,
+    permission::PermissionLevel,
+    server::Server,
+    update::{Log, Update},
+    user::User,
+    Operation,
+  },
+  ws::{ExecTerminalQuery, ExecTerminalTarget, HostTerminalQuery},
+};
+use periphery_client::{
+  api::stream::{ExecTerminal, HostTerminal},
+  tungstenite, PeripheryWebSocket,
+};
+
+use crate::{
+  helpers::{
+    periphery_client,
+    update::{add_update, make_update, update_update},
+  },
+  resource,
+  stack::get_stack_and_server,
+};
+
+use super::ws_login;
+
+/// Proxies a `docker exec -it` terminal from periphery.
+/// After login, the client sends an [ExecTerminalQuery].
+/// Each session is recorded as an Update.
+#[instrument(level = "debug")]
+pub async fn exec_handler(ws: WebSocketUpgrade) -> impl IntoResponse {
+  ws.on_upgrade(|socket| async move {
+    let Some((mut socket, user)) = ws_login(socket).await else {
+      return;
+    };
+    if let Err(e) = exec_terminal(&mut socket, &user).await {
+      let _ = socket.send(Message::Text(format!("{e:#}"))).await;
+    }
+    let _ = socket.close().await;
+  })
+}
+
+async fn exec_terminal(
+  socket: &mut WebSocket,
+  user: &User,
+) -> anyhow::Result<()> {
+  let Some(query) = recv_query(socket).await? else {
+    return Ok(());
+  };
+
+  let (server, request, update) =
+    exec_terminal_request(query, user).await?;
+
+  let periphery = periphery_client(&server)?
+    .exec_terminal(&request)
+    .await
+    .context("failed to open terminal on periphery");
+
+  run_session(socket, periphery, update).await
+}
+
+/// Proxies a shell on the server host from periphery.
+/// After login, the client sends a [HostTerminalQuery].
+/// Only admins can open host terminals.
+#[instrument(level = "debug")]
+pub async fn host_handler(ws: WebSocketUpgrade) -> impl IntoResponse {
+  ws.on_upgrade(|socket| async move {
+    let Some((mut socket, user)) = ws_login(socket).await else {
+      return;
+    };
+    if let Err(e) = host_terminal(&mut socket, &user).await {
+      let _ = socket.send(Message::Text(format!("{e:#}"))).await;
+    }
+    let _ = socket.close().await;
+  })
+}
+
+async fn host_terminal(
+  socket: &mut WebSocket,
+  user: &User,
+) -> anyhow::Result<()> {
+  if !user.admin {
+    return Err(anyhow!("only admins can open host terminals"));
+  }
+
+  let Some(HostTerminalQuery { server, rows, cols }) =
+    recv_query(socket).await?
+  else {
+    return Ok(());
+  };
+
+  let server = resource::get::<Server>(&server).await?;
+  let mut update =
+    make_update(&server, Operation::HostTerminal, user);
+  update.in_progress();
+  update.logs.push(terminal_log(String::from("host shell")));
+
+  let periphery = periphery_client(&server)?
+    .host_terminal(&HostTerminal { rows, cols })
+    .await
+    .context("failed to open host terminal on periphery");
+
+  run_session(socket, periphery, update).await
+}
+
+/// Receives the first message after login, which selects the terminal.
+async fn recv_query<T: serde::de::DeserializeOwned>(
+  socket: &mut WebSocket,
+) -> anyhow::Result<Option<T>> {
+  let query = match socket.recv().await {
+    Some(Ok(Message::Text(query))) => serde_json::from_str(&query)
+      .context("failed to parse terminal query")?,
+    Some(Ok(msg)) => {
+      return Err(anyhow!("invalid terminal query: {msg:?}"))
+    }
+    Some(Err(e)) => {
+      return Err(
+        anyhow::Error::from(e)
+          .context("failed to get terminal query"),
+      )
+    }
+    None => return Ok(None),
+  };
+  Ok(Some(query))
+}
+
+/// Records the terminal session as an Update,
+/// proxying the terminal until either side closes.
+async fn run_session(
+  socket: &mut WebSocket,
+  periphery: anyhow::Result<PeripheryWebSocket>,
+  mut update: Update,
+) -> anyhow::Result<()> {
+  let mut periphery = match periphery {
+    Ok(periphery) => periphery,
+    Err(e) => {
+      update.push_error_log("Open Terminal", format!("{e:#}"));
+      update.finalize();
+      add_update(update).await?;
+      return Err(e);
+    }
+  };
+
+  update.id = add_update(update.clone()).await?;
+
+  let res = proxy_terminal(socket, &mut periphery).await;
+  let _ = periphery.close(None).await;
+
+  match &res {
+    Ok(_) => update.push_simple_log("Session Ended", String::new()),
+    Err(e) => {
+      update.push_error_log("Session Ended", format!("{e:#}"))
+    }
+  }
+  update.finalize();
+  update_update(update).await?;
+
+  res
+}
+
+async fn exec_terminal_request(
+  ExecTerminalQuery {
+    target,
+    command,
+    rows,
+    cols,
+  }: ExecTerminalQuery,
+  user: &User,
+) -> anyhow::Result<(Server, ExecTerminal, Update)> {
+  match target {
+    ExecTerminalTarget::Deployment { deployment } => {
+      let deployment = resource::get_check_permissions::<Deployment>(
+        &deployment,
+        user,
+        PermissionLevel::Write,
+      )
+      .await?;
+      if deployment.config.server_id.is_empty() {
+        return Err(anyhow!("deployment has no server attached"));
+      }
+      let server =
+        resource::get::<Server>(&deployment.config.server_id).await?;
+      let mut update =
+        make_update(&deployment, Operation::ExecDeployment, user);
+      update.in_progress();
+      let name = deployment.container_name();
+      update.logs.push(terminal_log(format!(
+        "docker exec -it {name} {command}"
+      )));
+      let request = ExecTerminal::Container {
+        name,
+        command,
+        rows,
+        cols,
+      };
+      Ok((server, request, update))
+    }
+    ExecTerminalTarget::StackService { stack, service } => {
+      let (stack, server) = get_stack_and_server(
+        &stack,
+        user,
+        PermissionLevel::Write,
+        true,
+      )
+      .await?;
+      let project = stack.project_name(false);
+      let mut update =
+        make_update(&stack, Operation::ExecStackService, user);
+      update.in_progress();
+      update.logs.push(terminal_log(format!(
+        "docker compose -p {project} exec {service} {command}"
+      )));
+      let request = ExecTerminal::ComposeService {
+        project,
+        service,
+        command,
+        rows,
+        cols,
+      };
+      Ok((server, request, update))
+    }
+  }
+}
+
+fn terminal_log(command: String) -> Log {
+  let mut log = Log::simple(
+    "Open Terminal",
+    String::from("Opened terminal session"),
+  );
+  log.command = command;
+  log
+}
+
+/// Forwards binary and text messages both ways until either side closes.
+async fn proxy_terminal(
+  socket: &mut WebSocket,
+  periphery: &mut PeripheryWebSocket,
+) -> anyhow::Result<()> {
+  loop {
+    tokio::select! {
+      msg = periphery.next() => match msg {
+        Some(Ok(tungstenite::Message::Binary(output))) => {
+          socket
+            .send(Message::Binary(output))
+            .await
+            .context("failed to send terminal output")?;
+        }
+        Some(Ok(tungstenite::Message::Text(msg))) => {
+          return Err(anyhow!(msg));
+        }
+        Some(Ok(tungstenite::Message::Close(_))) | None => {
+          return Ok(())
+        }
+        Some(Ok(_)) => {}
+        Some(Err(e)) => {
+          return Err(
+            anyhow::Error::from(e)
+              .context("failed to recieve terminal output from periphery"),
+          )
+        }
+      },
+      msg = socket.recv() => match msg {
+        Some(Ok(Message::Binary(input))) => {
+          periphery
+            .send(tungstenite::Message::Binary(input))
+            .await
+            .context("failed to send terminal input to periphery")?;
+        }
+        Some(Ok(Message::Text(msg))) => {
+          periphery
+            .send(tungstenite::Message::Text(msg))
+            .await
+            .context("failed to send terminal message to periphery")?;
+        }
+        Some(Ok(Message::Close(_))) | Some(Err(_)) | None => {
+          return Ok(())
+        }
+        Some(Ok(_)) => {}
+      },
+    }
+  }
+}
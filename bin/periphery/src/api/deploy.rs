@@ -4,8 +4,9 @@ use formatting::format_serror;
 use komodo_client::{
   entities::{
     deployment::{
-      conversions_from_str, extract_registry_domain, Conversion,
-      Deployment, DeploymentConfig, DeploymentImage, RestartMode,
+      conversions_from_str, extract_registry_domain, ulimit_from_str,
+      validate_memory_size, Conversion, Deployment, DeploymentConfig,
+      DeploymentImage, RestartMode,
     },
     environment_vars_from_str, to_komodo_name,
     update::Log,
@@ -131,9 +132,20 @@ fn docker_run_command(
         network,
         command,
         restart,
+        restart_max_retries,
+        cpus,
+        memory,
+        memory_reservation,
+        pids_limit,
+        ulimits,
         environment,
         labels,
         extra_args,
+        healthcheck_command,
+        healthcheck_interval,
+        healthcheck_timeout,
+        healthcheck_retries,
+        healthcheck_start_period,
         ..
       },
     ..
@@ -150,7 +162,14 @@ fn docker_run_command(
     "-v",
   );
   let network = parse_network(network);
-  let restart = parse_restart(restart);
+  let restart = parse_restart(restart, *restart_max_retries);
+  let limits = parse_limits(
+    *cpus,
+    memory,
+    memory_reservation,
+    *pids_limit,
+    ulimits,
+  )?;
   let environment = parse_environment(
     &environment_vars_from_str(environment)
       .context("Invalid environment")?,
@@ -158,9 +177,16 @@ fn docker_run_command(
   let labels = parse_labels(
     &environment_vars_from_str(labels).context("Invalid labels")?,
   );
+  let healthcheck = parse_healthcheck(
+    healthcheck_command,
+    *healthcheck_interval,
+    *healthcheck_timeout,
+    *healthcheck_retries,
+    *healthcheck_start_period,
+  );
   let command = parse_command(command);
   let extra_args = parse_extra_args(extra_args);
-  let command = format!("docker run -d --name {name}{ports}{volumes}{network}{restart}{environment}{labels}{extra_args} {image}{command}");
+  let command = format!("docker run -d --name {name}{ports}{volumes}{network}{restart}{limits}{environment}{labels}{healthcheck}{extra_args} {image}{command}");
   Ok(command)
 }
 
@@ -196,14 +222,83 @@ fn parse_network(network: &str) -> String {
   format!(" --network {network}")
 }
 
-fn parse_restart(restart: &RestartMode) -> String {
+fn parse_restart(restart: &RestartMode, max_retries: i32) -> String {
   let restart = match restart {
-    RestartMode::OnFailure => "on-failure:10".to_string(),
+    RestartMode::OnFailure => format!("on-failure:{max_retries}"),
     _ => restart.to_string(),
   };
   format!(" --restart {restart}")
 }
 
+/// Zero / empty values are unlimited.
+fn parse_limits(
+  cpus: f64,
+  memory: &str,
+  memory_reservation: &str,
+  pids_limit: i32,
+  ulimits: &[String],
+) -> anyhow::Result<String> {
+  let mut res = String::new();
+  if cpus > 0.0 {
+    res.push_str(&format!(" --cpus {cpus}"));
+  }
+  if !memory.is_empty() {
+    validate_memory_size(memory).context("Invalid memory")?;
+    res.push_str(&format!(" --memory {memory}"));
+  }
+  if !memory_reservation.is_empty() {
+    validate_memory_size(memory_reservation)
+      .context("Invalid memory reservation")?;
+    res.push_str(&format!(
+      " --memory-reservation {memory_reservation}"
+    ));
+  }
+  if pids_limit > 0 {
+    res.push_str(&format!(" --pids-limit {pids_limit}"));
+  }
+  for ulimit in ulimits {
+    let ulimit = ulimit_from_str(ulimit)?;
+    res.push_str(&format!(
+      " --ulimit {}={}:{}",
+      ulimit.name, ulimit.soft, ulimit.hard
+    ));
+  }
+  Ok(res)
+}
+
+/// Zero values are left to the image / docker defaults.
+fn parse_healthcheck(
+  command: &str,
+  interval: i32,
+  timeout: i32,
+  retries: i32,
+  start_period: i32,
+) -> String {
+  let mut res = String::new();
+  if !command.is_empty() {
+    if command.starts_with(QUOTE_PATTERN)
+      && command.ends_with(QUOTE_PATTERN)
+    {
+      res.push_str(&format!(" --health-cmd {command}"));
+    } else {
+      res.push_str(&format!(" --health-cmd \"{command}\""));
+    }
+  }
+  if interval > 0 {
+    res.push_str(&format!(" --health-interval {interval}s"));
+  }
+  if timeout > 0 {
+    res.push_str(&format!(" --health-timeout {timeout}s"));
+  }
+  if retries > 0 {
+    res.push_str(&format!(" --health-retries {retries}"));
+  }
+  if start_period > 0 {
+    res.push_str(&format!(" --health-start-period {start_period}s"));
+  }
+  res
+}
+
 fn parse_command(command: &str) -> String {
   if command.is_empty() {
     String::new()
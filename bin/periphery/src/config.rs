@@ -43,6 +43,15 @@ pub fn periphery_config() -> &'static PeripheryConfig {
       legacy_compose_cli: env
         .periphery_legacy_compose_cli
         .unwrap_or(config.legacy_compose_cli),
+      disable_host_terminal: env
+        .periphery_disable_host_terminal
+        .unwrap_or(config.disable_host_terminal),
+      host_terminal_shell: env
+        .periphery_host_terminal_shell
+        .unwrap_or(config.host_terminal_shell),
+      host_terminal_dir: env
+        .periphery_host_terminal_dir
+        .unwrap_or(config.host_terminal_dir),
       logging: LogConfig {
         level: args
           .log_level
@@ -59,14 +68,29 @@ pub fn periphery_config() -> &'static PeripheryConfig {
           .periphery_logging_opentelemetry_service_name
           .unwrap_or(config.logging.opentelemetry_service_name),
       },
+      core_address: env
+        .periphery_core_address
+        .unwrap_or(config.core_address),
+      enrollment_token: env
+        .periphery_enrollment_token
+        .unwrap_or(config.enrollment_token),
       allowed_ips: env
         .periphery_allowed_ips
         .unwrap_or(config.allowed_ips),
+      trusted_proxies: env
+        .periphery_trusted_proxies
+        .unwrap_or(config.trusted_proxies),
+      proxy_protocol: env
+        .periphery_proxy_protocol
+        .unwrap_or(config.proxy_protocol),
       passkeys: maybe_read_list_from_file(
         env.periphery_passkeys_file,
         env.periphery_passkeys,
       )
       .unwrap_or(config.passkeys),
+      require_signed_requests: env
+        .periphery_require_signed_requests
+        .unwrap_or(config.require_signed_requests),
       include_disk_mounts: env
         .periphery_include_disk_mounts
         .unwrap_or(config.include_disk_mounts),
@@ -82,6 +106,9 @@ pub fn periphery_config() -> &'static PeripheryConfig {
       ssl_cert_file: env
         .periphery_ssl_cert_file
         .unwrap_or(config.ssl_cert_file),
+      ssl_client_ca_file: env
+        .periphery_ssl_client_ca_file
+        .or(config.ssl_client_ca_file),
       secrets: config.secrets,
       git_providers: config.git_providers,
       docker_registries: config.docker_registries,
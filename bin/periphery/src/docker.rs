@@ -4,9 +4,12 @@ use anyhow::{anyhow, Context};
 use bollard::{
   container::{InspectContainerOptions, ListContainersOptions},
   network::InspectNetworkOptions,
+  secret::EventMessage,
+  system::EventsOptions,
   Docker,
 };
 use command::run_komodo_command;
+use futures::{future::join_all, Stream, StreamExt};
 use komodo_client::entities::{
   docker::{
     container::*, image::*, network::*, volume::*, ContainerConfig,
@@ -16,6 +19,9 @@ use komodo_client::entities::{
   update::Log,
   TerminationSignal,
 };
+use periphery_client::api::stream::{
+  ContainerEvent, ContainerEventAction,
+};
 use run_command::async_run_command;
 
 pub fn docker_client() -> &'static DockerClient {
@@ -27,6 +33,39 @@ pub struct DockerClient {
   docker: Docker,
 }
 
+/// Returns None for events which don't change container state.
+fn container_event(event: EventMessage) -> Option<ContainerEvent> {
+  let action = event.action?;
+  // Health events come as eg. `health_status: unhealthy`
+  let (action, health) = match action.split_once(':') {
+    Some(("health_status", health)) => (
+      ContainerEventAction::HealthStatus,
+      serde_json::from_value(serde_json::Value::String(
+        health.trim().to_string(),
+      ))
+      .ok(),
+    ),
+    _ => match action.as_str() {
+      "start" => (ContainerEventAction::Start, None),
+      "stop" => (ContainerEventAction::Stop, None),
+      "die" => (ContainerEventAction::Die, None),
+      _ => return None,
+    },
+  };
+  let name = event.actor?.attributes?.remove("name")?;
+  let ts = event
+    .time_nano
+    .map(|nanos| nanos / 1_000_000)
+    .or(event.time.map(|secs| secs * 1000))
+    .unwrap_or_default();
+  Some(ContainerEvent {
+    name,
+    action,
+    health,
+    ts,
+  })
+}
+
 impl Default for DockerClient {
   fn default() -> DockerClient {
     DockerClient {
@@ -68,6 +107,11 @@ impl DockerClient {
             .context("no container state")?
             .parse()
             .context("failed to parse container state")?,
+          health: container
+            .status
+            .as_deref()
+            .and_then(health_from_status),
+          restart_count: None,
           status: container.status,
           network_mode: container
             .host_config
@@ -114,9 +158,61 @@ impl DockerClient {
       container.network_mode =
         container_id_to_network.get(container_id).cloned();
     });
+    // Restart count is not included in the list response.
+    // Only inspect containers which look to have recently restarted,
+    // which is enough to detect a restart loop.
+    let restart_counts = join_all(
+      containers
+        .iter()
+        .filter(|container| recently_restarted(container))
+        .map(|container| async {
+          let restart_count = self
+            .docker
+            .inspect_container(&container.name, None)
+            .await
+            .ok()?
+            .restart_count;
+          Some((container.name.clone(), restart_count?))
+        }),
+    )
+    .await
+    .into_iter()
+    .flatten()
+    .collect::<HashMap<_, _>>();
+    containers.iter_mut().for_each(|container| {
+      container.restart_count =
+        restart_counts.get(&container.name).copied();
+    });
     Ok(containers)
   }
 
+  /// Streams the docker events which change container state.
+  pub fn container_events(
+    &self,
+  ) -> impl Stream<Item = anyhow::Result<ContainerEvent>> + '_ {
+    let events = ["start", "stop", "die", "health_status"]
+      .map(String::from)
+      .to_vec();
+    self
+      .docker
+      .events(Some(EventsOptions::<String> {
+        filters: HashMap::from([
+          (String::from("type"), vec![String::from("container")]),
+          (String::from("event"), events),
+        ]),
+        ..Default::default()
+      }))
+      .filter_map(|event| async move {
+        match event {
+          Ok(event) => container_event(event).map(Ok),
+          Err(e) => Some(Err(
+            anyhow::Error::from(e)
+              .context("failed to read docker event"),
+          )),
+        }
+      })
+  }
+
   pub async fn inspect_container(
     &self,
     container_name: &str,
@@ -1006,3 +1102,35 @@ pub async fn container_stats(
     Err(anyhow!("{}", output.stderr.replace('\n', "")))
   }
 }
+
+/// Parses the health from the human-readable container status,
+/// eg. `Up 5 minutes (unhealthy)`.
+fn health_from_status(status: &str) -> Option<HealthStatusEnum> {
+  if status.ends_with("(healthy)") {
+    Some(HealthStatusEnum::Healthy)
+  } else if status.ends_with("(unhealthy)") {
+    Some(HealthStatusEnum::Unhealthy)
+  } else if status.ends_with("(health: starting)") {
+    Some(HealthStatusEnum::Starting)
+  } else {
+    None
+  }
+}
+
+/// Whether the container is restarting, or was started in the last minute,
+/// eg. `Up 30 seconds`. Docker backs off restarts of a crashing
+/// container by at most a minute, so this includes containers in a restart loop.
+fn recently_restarted(container: &ContainerListItem) -> bool {
+  if container.state == ContainerStateStatusEnum::Restarting {
+    return true;
+  }
+  let Some(status) = &container.status else {
+    return false;
+  };
+  status.starts_with("Up Less than a second")
+    || (status.starts_with("Up ")
+      && status
+        .split(' ')
+        .nth(2)
+        .is_some_and(|unit| unit.starts_with("second")))
+}
@@ -0,0 +1,119 @@
+use std::{
+  collections::HashMap,
+  net::IpAddr,
+  sync::{OnceLock, RwLock},
+  time::Duration,
+};
+
+use komodo_client::entities::config::periphery::IpNetwork;
+
+use crate::config::periphery_config;
+
+const RESOLVE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// hostname -> the addresses it last resolved to
+type ResolvedHostnames = RwLock<HashMap<String, Vec<IpAddr>>>;
+
+fn resolved_hostnames() -> &'static ResolvedHostnames {
+  static RESOLVED: OnceLock<ResolvedHostnames> = OnceLock::new();
+  RESOLVED.get_or_init(Default::default)
+}
+
+/// Whether the address is in the network, or is one
+/// of the addresses the hostname last resolved to.
+pub fn contains(network: &IpNetwork, ip: &IpAddr) -> bool {
+  let ip = ip.to_canonical();
+  match network {
+    IpNetwork::Network(net) => net.contains(&ip),
+    IpNetwork::Hostname(hostname) => resolved_hostnames()
+      .read()
+      .unwrap()
+      .get(hostname)
+      .is_some_and(|ips| ips.contains(&ip)),
+  }
+}
+
+/// Resolves the hostnames in `allowed_ips` and `trusted_proxies`,
+/// then keeps them up to date in the background.
+pub async fn spawn_hostname_resolution() {
+  let config = periphery_config();
+  let mut hostnames = config
+    .allowed_ips
+    .iter()
+    .chain(&config.trusted_proxies)
+    .filter_map(|network| match network {
+      IpNetwork::Hostname(hostname) => Some(hostname.clone()),
+      IpNetwork::Network(_) => None,
+    })
+    .collect::<Vec<_>>();
+  hostnames.sort();
+  hostnames.dedup();
+  if hostnames.is_empty() {
+    return;
+  }
+  // Resolve before serving, so allowed hosts aren't rejected on startup.
+  resolve_hostnames(&hostnames).await;
+  tokio::spawn(async move {
+    loop {
+      tokio::time::sleep(RESOLVE_INTERVAL).await;
+      resolve_hostnames(&hostnames).await;
+    }
+  });
+}
+
+async fn resolve_hostnames(hostnames: &[String]) {
+  for hostname in hostnames {
+    match tokio::net::lookup_host((hostname.as_str(), 0)).await {
+      Ok(addrs) => {
+        let ips = addrs
+          .map(|addr| addr.ip().to_canonical())
+          .collect::<Vec<_>>();
+        resolved_hostnames()
+          .write()
+          .unwrap()
+          .insert(hostname.clone(), ips);
+      }
+      // Keep the last resolved addresses through DNS outages.
+      Err(e) => {
+        warn!("failed to resolve hostname {hostname} | {e:#}")
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_addresses_networks_and_hostnames() {
+    assert!(matches!(
+      "10.0.0.1".parse::<IpNetwork>().unwrap(),
+      IpNetwork::Network(_)
+    ));
+    assert!(matches!(
+      "fd00::/8".parse::<IpNetwork>().unwrap(),
+      IpNetwork::Network(_)
+    ));
+    assert_eq!(
+      "Proxy.Internal".parse::<IpNetwork>().unwrap(),
+      IpNetwork::Hostname(String::from("proxy.internal"))
+    );
+    assert!("10.0.0.256".parse::<IpNetwork>().is_err());
+    assert!("bad host".parse::<IpNetwork>().is_err());
+    assert!("-bad.internal".parse::<IpNetwork>().is_err());
+  }
+
+  #[test]
+  fn hostname_matches_resolved_addresses() {
+    let network = IpNetwork::Hostname(String::from("test.internal"));
+    let ip = "10.1.2.3".parse().unwrap();
+    assert!(!contains(&network, &ip));
+    resolved_hostnames()
+      .write()
+      .unwrap()
+      .insert(String::from("test.internal"), vec![ip]);
+    assert!(contains(&network, &ip));
+    assert!(!contains(&network, &"10.1.2.4".parse().unwrap()));
+  }
+}
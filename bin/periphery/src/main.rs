@@ -4,16 +4,22 @@ extern crate tracing;
 use std::{net::SocketAddr, str::FromStr};
 
 use anyhow::Context;
-use axum_server::tls_openssl::OpenSSLConfig;
+use axum_server::tls_openssl::OpenSSLAcceptor;
+use proxy_protocol::ProxyProtocolAcceptor;
 
 mod api;
 mod compose;
 mod config;
 mod docker;
 mod helpers;
+mod ip_network;
+mod proxy_protocol;
 mod router;
 mod ssl;
 mod stats;
+mod terminal;
+mod tunnel;
+mod ws;
 
 struct State;
 
@@ -26,6 +32,8 @@ async fn app() -> anyhow::Result<()> {
   info!("{:?}", config.sanitized());
 
   stats::spawn_system_stats_polling_threads();
+  tunnel::spawn_reverse_connection();
+  ip_network::spawn_hostname_resolution().await;
 
   let socket_addr =
     SocketAddr::from_str(&format!("0.0.0.0:{}", config.port))
@@ -38,18 +46,34 @@ async fn app() -> anyhow::Result<()> {
     info!("🔒 Periphery SSL Enabled");
     ssl::ensure_certs().await;
     info!("Komodo Periphery starting on https://{}", socket_addr);
-    let ssl_config = OpenSSLConfig::from_pem_file(
-      &config.ssl_cert_file,
-      &config.ssl_key_file,
-    )
-    .context("Invalid ssl cert / key")?;
-    axum_server::bind_openssl(socket_addr, ssl_config)
-      .serve(app)
-      .await?
+    let ssl_config = ssl::ssl_config()?;
+    if config.proxy_protocol {
+      // The PROXY protocol header comes before the TLS handshake.
+      let acceptor = OpenSSLAcceptor::new(ssl_config)
+        .acceptor(ProxyProtocolAcceptor);
+      axum_server::bind(socket_addr)
+        .acceptor(acceptor)
+        .serve(app)
+        .await?
+    } else {
+      axum_server::bind_openssl(socket_addr, ssl_config)
+        .serve(app)
+        .await?
+    }
   } else {
     info!("🔓 Periphery SSL Disabled");
+    if config.ssl_client_ca_file.is_some() {
+      warn!("ssl_client_ca_file is ignored with ssl disabled");
+    }
     info!("Komodo Periphery starting on http://{}", socket_addr);
-    axum_server::bind(socket_addr).serve(app).await?
+    if config.proxy_protocol {
+      axum_server::bind(socket_addr)
+        .acceptor(ProxyProtocolAcceptor)
+        .serve(app)
+        .await?
+    } else {
+      axum_server::bind(socket_addr).serve(app).await?
+    }
   }
 
   Ok(())
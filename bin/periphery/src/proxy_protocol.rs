@@ -0,0 +1,256 @@
+use std::{
+  io,
+  net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+  time::Duration,
+};
+
+use anyhow::{anyhow, Context};
+use axum::{middleware::AddExtension, Extension};
+use axum_server::accept::Accept;
+use futures::future::BoxFuture;
+use tokio::{
+  io::{AsyncRead, AsyncReadExt},
+  net::TcpStream,
+};
+use tower_layer::Layer;
+
+/// The v2 header starts with this signature.
+const V2_SIGNATURE: [u8; 12] = *b"\r\n\r\n\0\r\nQUIT\n";
+/// The longest v1 header, including the CRLF.
+const V1_MAX_LENGTH: usize = 107;
+const HEADER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The client address given in the PROXY protocol header,
+/// added to every request on the connection.
+/// None if the proxy sent no address, such as for its own health checks.
+#[derive(Debug, Clone, Copy)]
+pub struct ProxiedAddr(pub Option<SocketAddr>);
+
+/// Reads the PROXY protocol header from each connection
+/// before it is handed to TLS / http.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProxyProtocolAcceptor;
+
+impl<S: Send + 'static> Accept<TcpStream, S>
+  for ProxyProtocolAcceptor
+{
+  type Stream = TcpStream;
+  type Service = AddExtension<S, ProxiedAddr>;
+  type Future =
+    BoxFuture<'static, io::Result<(TcpStream, Self::Service)>>;
+
+  fn accept(
+    &self,
+    mut stream: TcpStream,
+    service: S,
+  ) -> Self::Future {
+    Box::pin(async move {
+      let addr = tokio::time::timeout(
+        HEADER_TIMEOUT,
+        read_proxy_header(&mut stream),
+      )
+      .await
+      .map_err(|_| {
+        io::Error::new(
+          io::ErrorKind::TimedOut,
+          "timed out waiting for PROXY protocol header",
+        )
+      })?
+      .map_err(|e| {
+        io::Error::new(io::ErrorKind::InvalidData, format!("{e:#}"))
+      })?;
+      Ok((stream, Extension(ProxiedAddr(addr)).layer(service)))
+    })
+  }
+}
+
+async fn read_proxy_header(
+  stream: &mut TcpStream,
+) -> anyhow::Result<Option<SocketAddr>> {
+  // Both versions are at least this long.
+  let mut prefix = [0; 12];
+  stream
+    .read_exact(&mut prefix)
+    .await
+    .context("failed to read PROXY protocol header")?;
+  if prefix == V2_SIGNATURE {
+    read_v2_header(stream).await
+  } else if prefix.starts_with(b"PROXY ") {
+    read_v1_header(stream, &prefix).await
+  } else {
+    Err(anyhow!("connection has no PROXY protocol header"))
+  }
+}
+
+/// eg. `PROXY TCP4 192.168.0.1 192.168.0.11 56324 443\r\n`
+async fn read_v1_header(
+  stream: &mut (impl AsyncRead + Unpin),
+  prefix: &[u8],
+) -> anyhow::Result<Option<SocketAddr>> {
+  let mut header = prefix.to_vec();
+  // Read byte by byte so nothing after the header is consumed.
+  while !header.ends_with(b"\r\n") {
+    if header.len() >= V1_MAX_LENGTH {
+      return Err(anyhow!("PROXY protocol v1 header too long"));
+    }
+    header.push(
+      stream
+        .read_u8()
+        .await
+        .context("failed to read PROXY protocol v1 header")?,
+    );
+  }
+  let header = std::str::from_utf8(&header)
+    .context("PROXY protocol v1 header is not utf8")?;
+  let parts = header.split_whitespace().collect::<Vec<_>>();
+  match parts.as_slice() {
+    ["PROXY", "UNKNOWN", ..] => Ok(None),
+    ["PROXY", "TCP4" | "TCP6", source, _, source_port, _] => {
+      let ip = source
+        .parse::<IpAddr>()
+        .context("invalid PROXY protocol v1 source address")?;
+      let port = source_port
+        .parse::<u16>()
+        .context("invalid PROXY protocol v1 source port")?;
+      Ok(Some(SocketAddr::new(ip, port)))
+    }
+    _ => Err(anyhow!("invalid PROXY protocol v1 header")),
+  }
+}
+
+async fn read_v2_header(
+  stream: &mut (impl AsyncRead + Unpin),
+) -> anyhow::Result<Option<SocketAddr>> {
+  let mut header = [0; 4];
+  stream
+    .read_exact(&mut header)
+    .await
+    .context("failed to read PROXY protocol v2 header")?;
+  let [version_command, family, len @ ..] = header;
+  if version_command >> 4 != 2 {
+    return Err(anyhow!("unsupported PROXY protocol version"));
+  }
+  let mut body = vec![0; u16::from_be_bytes(len) as usize];
+  stream
+    .read_exact(&mut body)
+    .await
+    .context("failed to read PROXY protocol v2 addresses")?;
+  // LOCAL command, the connection is from the proxy itself.
+  if version_command & 0x0F == 0 {
+    return Ok(None);
+  }
+  let addr = match family >> 4 {
+    // AF_INET
+    1 if body.len() >= 12 => {
+      let ip: [u8; 4] = body[0..4].try_into()?;
+      let port = u16::from_be_bytes(body[8..10].try_into()?);
+      Some(SocketAddr::new(Ipv4Addr::from(ip).into(), port))
+    }
+    // AF_INET6
+    2 if body.len() >= 36 => {
+      let ip: [u8; 16] = body[0..16].try_into()?;
+      let port = u16::from_be_bytes(body[32..34].try_into()?);
+      Some(SocketAddr::new(Ipv6Addr::from(ip).into(), port))
+    }
+    // AF_UNSPEC / AF_UNIX
+    _ => None,
+  };
+  Ok(addr)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn v1_tcp4() {
+    let header =
+      b"PROXY TCP4 192.168.0.1 192.168.0.11 56324 443\r\nGET /";
+    let (prefix, mut rest) = header.split_at(12);
+    let addr = read_v1_header(&mut rest, prefix).await.unwrap();
+    assert_eq!(addr, Some("192.168.0.1:56324".parse().unwrap()));
+    // Nothing after the header is consumed.
+    assert_eq!(rest, b"GET /");
+  }
+
+  #[tokio::test]
+  async fn v1_tcp6() {
+    let header = b"PROXY TCP6 2001:db8::1 2001:db8::2 56324 443\r\n";
+    let (prefix, mut rest) = header.split_at(12);
+    let addr = read_v1_header(&mut rest, prefix).await.unwrap();
+    assert_eq!(addr, Some("[2001:db8::1]:56324".parse().unwrap()));
+  }
+
+  #[tokio::test]
+  async fn v1_unknown() {
+    let header = b"PROXY UNKNOWN\r\n";
+    let (prefix, mut rest) = header.split_at(12);
+    let addr = read_v1_header(&mut rest, prefix).await.unwrap();
+    assert_eq!(addr, None);
+  }
+
+  #[tokio::test]
+  async fn v1_invalid() {
+    for header in [
+      &b"PROXY TCP4 not-an-ip 192.168.0.11 56324 443\r\n"[..],
+      b"PROXY TCP4 192.168.0.1 192.168.0.11 99999 443\r\n",
+      b"PROXY TCP4 192.168.0.1\r\n",
+    ] {
+      let (prefix, mut rest) = header.split_at(12);
+      assert!(read_v1_header(&mut rest, prefix).await.is_err());
+    }
+  }
+
+  #[tokio::test]
+  async fn v1_too_long() {
+    let header = [b"PROXY TCP4 ".as_slice(), &[b'1'; 200]].concat();
+    let (prefix, mut rest) = header.split_at(12);
+    assert!(read_v1_header(&mut rest, prefix).await.is_err());
+  }
+
+  #[tokio::test]
+  async fn v2_ipv4() {
+    let mut header = vec![0x21, 0x11, 0, 12];
+    header.extend([192, 168, 0, 1, 192, 168, 0, 11]);
+    header.extend(56324u16.to_be_bytes());
+    header.extend(443u16.to_be_bytes());
+    header.extend(b"GET /");
+    let mut rest = header.as_slice();
+    let addr = read_v2_header(&mut rest).await.unwrap();
+    assert_eq!(addr, Some("192.168.0.1:56324".parse().unwrap()));
+    assert_eq!(rest, b"GET /");
+  }
+
+  #[tokio::test]
+  async fn v2_ipv6() {
+    let source = "2001:db8::1".parse::<Ipv6Addr>().unwrap();
+    let mut header = vec![0x21, 0x21, 0, 36];
+    header.extend(source.octets());
+    header.extend([0; 16]);
+    header.extend(56324u16.to_be_bytes());
+    header.extend(443u16.to_be_bytes());
+    let addr = read_v2_header(&mut header.as_slice()).await.unwrap();
+    assert_eq!(addr, Some("[2001:db8::1]:56324".parse().unwrap()));
+  }
+
+  #[tokio::test]
+  async fn v2_local() {
+    let mut header = vec![0x20, 0x11, 0, 12];
+    header.extend([0; 12]);
+    let addr = read_v2_header(&mut header.as_slice()).await.unwrap();
+    assert_eq!(addr, None);
+  }
+
+  #[tokio::test]
+  async fn v2_unsupported_version() {
+    let mut header = vec![0x11, 0x11, 0, 12];
+    header.extend([0; 12]);
+    assert!(read_v2_header(&mut header.as_slice()).await.is_err());
+  }
+
+  #[tokio::test]
+  async fn v2_truncated() {
+    let header = [0x21, 0x11, 0, 12, 192, 168];
+    assert!(read_v2_header(&mut header.as_slice()).await.is_err());
+  }
+}
@@ -1,10 +1,14 @@
-use std::net::SocketAddr;
+use std::{
+  collections::HashMap,
+  net::{IpAddr, SocketAddr},
+  sync::{Mutex, OnceLock},
+};
 
 use anyhow::{anyhow, Context};
 use axum::{
   body::Body,
   extract::ConnectInfo,
-  http::{Request, StatusCode},
+  http::{HeaderMap, Request, StatusCode},
   middleware::{self, Next},
   response::Response,
   routing::post,
@@ -12,15 +16,26 @@ use axum::{
 };
 use axum_extra::{headers::ContentType, TypedHeader};
 use derive_variants::ExtractVariant;
+use komodo_client::entities::{
+  config::periphery::IpNetwork, komodo_timestamp,
+};
+use periphery_client::signature::{
+  verify_request, MAX_TIMESTAMP_SKEW_MS, NONCE_HEADER,
+  SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
 use resolver_api::Resolver;
 use serror::{AddStatusCode, AddStatusCodeError, Json};
 use uuid::Uuid;
 
-use crate::{config::periphery_config, State};
+use crate::{
+  config::periphery_config, ip_network, proxy_protocol::ProxiedAddr,
+  State,
+};
 
 pub fn router() -> Router {
   Router::new()
     .route("/", post(handler))
+    .nest("/ws", crate::ws::router())
     .layer(middleware::from_fn(guard_request_by_ip))
     .layer(middleware::from_fn(guard_request_by_passkey))
 }
@@ -41,7 +56,7 @@ async fn handler(
   Ok((TypedHeader(ContentType::json()), res??))
 }
 
-async fn task(
+pub async fn task(
   req_id: Uuid,
   request: crate::api::PeripheryRequest,
 ) -> anyhow::Result<String> {
@@ -69,9 +84,22 @@ async fn guard_request_by_passkey(
   req: Request<Body>,
   next: Next,
 ) -> serror::Result<Response> {
-  if periphery_config().passkeys.is_empty() {
+  let config = periphery_config();
+  if config.passkeys.is_empty() {
+    return Ok(next.run(req).await);
+  }
+  if req.headers().contains_key(SIGNATURE_HEADER) {
+    let req = verify_signed_request(req)
+      .await
+      .status_code(StatusCode::UNAUTHORIZED)?;
     return Ok(next.run(req).await);
   }
+  if config.require_signed_requests {
+    return Err(
+      anyhow!("request was not signed")
+        .status_code(StatusCode::UNAUTHORIZED),
+    );
+  }
   let Some(req_passkey) = req.headers().get("authorization") else {
     return Err(
       anyhow!("request was not sent with passkey")
@@ -96,11 +124,84 @@ async fn guard_request_by_passkey(
   }
 }
 
+/// Matches the axum `Json` extractor default limit.
+const MAX_SIGNED_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Verifies the request was signed with one of the passkeys,
+/// recently, and hasn't been seen before.
+async fn verify_signed_request(
+  req: Request<Body>,
+) -> anyhow::Result<Request<Body>> {
+  let (parts, body) = req.into_parts();
+  let timestamp = signature_header(&parts.headers, TIMESTAMP_HEADER)?
+    .parse::<i64>()
+    .context("failed to parse request timestamp")?;
+  let nonce = signature_header(&parts.headers, NONCE_HEADER)?;
+  let signature = signature_header(&parts.headers, SIGNATURE_HEADER)?;
+
+  if (komodo_timestamp() - timestamp).abs() > MAX_TIMESTAMP_SKEW_MS {
+    return Err(anyhow!(
+      "request timestamp is stale. check the Core and Periphery clocks are in sync"
+    ));
+  }
+
+  let body = axum::body::to_bytes(body, MAX_SIGNED_BODY_BYTES)
+    .await
+    .context("failed to read request body")?;
+
+  let method = parts.method.as_str();
+  let path = parts.uri.path();
+  if !periphery_config().passkeys.iter().any(|passkey| {
+    verify_request(
+      passkey, method, path, timestamp, nonce, &body, signature,
+    )
+  }) {
+    return Err(anyhow!("request signature invalid"));
+  }
+
+  // Only record nonces after verifying the signature,
+  // so unsigned requests can't fill the cache.
+  check_nonce(nonce, timestamp)?;
+
+  Ok(Request::from_parts(parts, Body::from(body)))
+}
+
+fn signature_header<'a>(
+  headers: &'a HeaderMap,
+  name: &str,
+) -> anyhow::Result<&'a str> {
+  headers
+    .get(name)
+    .with_context(|| format!("request missing {name} header"))?
+    .to_str()
+    .with_context(|| {
+      format!("failed to convert {name} header to str")
+    })
+}
+
+/// Rejects nonces seen within the timestamp window.
+/// Older requests are rejected by timestamp, so their nonces are dropped.
+fn check_nonce(nonce: &str, timestamp: i64) -> anyhow::Result<()> {
+  static NONCES: OnceLock<Mutex<HashMap<String, i64>>> =
+    OnceLock::new();
+  let mut nonces = NONCES
+    .get_or_init(Default::default)
+    .lock()
+    .map_err(|_| anyhow!("nonce cache lock poisoned"))?;
+  let now = komodo_timestamp();
+  nonces.retain(|_, ts| now - *ts <= MAX_TIMESTAMP_SKEW_MS);
+  if nonces.insert(nonce.to_string(), timestamp).is_some() {
+    return Err(anyhow!("request nonce has already been used"));
+  }
+  Ok(())
+}
+
 async fn guard_request_by_ip(
   req: Request<Body>,
   next: Next,
 ) -> serror::Result<Response> {
-  if periphery_config().allowed_ips.is_empty() {
+  let config = periphery_config();
+  if config.allowed_ips.is_empty() {
     return Ok(next.run(req).await);
   }
   let ConnectInfo(socket_addr) = req
@@ -108,8 +209,21 @@ async fn guard_request_by_ip(
     .get::<ConnectInfo<SocketAddr>>()
     .context("could not get ConnectionInfo of request")
     .status_code(StatusCode::UNAUTHORIZED)?;
-  let ip = socket_addr.ip();
-  if periphery_config().allowed_ips.contains(&ip) {
+  let proxied = req
+    .extensions()
+    .get::<ProxiedAddr>()
+    .and_then(|ProxiedAddr(addr)| *addr);
+  let ip = client_ip(
+    *socket_addr,
+    proxied,
+    req.headers(),
+    &config.trusted_proxies,
+  );
+  if config
+    .allowed_ips
+    .iter()
+    .any(|allowed| ip_network::contains(allowed, &ip))
+  {
     Ok(next.run(req).await)
   } else {
     Err(
@@ -118,3 +232,158 @@ async fn guard_request_by_ip(
     )
   }
 }
+
+/// The address the request is checked against `allowed_ips` by.
+/// The PROXY protocol address and `X-Forwarded-For` are only honoured
+/// when the connection itself comes from a trusted proxy.
+fn client_ip(
+  socket_addr: SocketAddr,
+  proxied: Option<SocketAddr>,
+  headers: &HeaderMap,
+  trusted_proxies: &[IpNetwork],
+) -> IpAddr {
+  let socket_ip = socket_addr.ip().to_canonical();
+  if !is_trusted(trusted_proxies, &socket_ip) {
+    return socket_ip;
+  }
+  let peer = proxied
+    .map(|addr| addr.ip().to_canonical())
+    .unwrap_or(socket_ip);
+  forwarded_client_ip(peer, headers, trusted_proxies)
+}
+
+fn is_trusted(trusted_proxies: &[IpNetwork], ip: &IpAddr) -> bool {
+  trusted_proxies
+    .iter()
+    .any(|trusted| ip_network::contains(trusted, ip))
+}
+
+/// Walks back through `X-Forwarded-For` while the address is a trusted proxy,
+/// returning the first untrusted address.
+fn forwarded_client_ip(
+  peer: IpAddr,
+  headers: &HeaderMap,
+  trusted_proxies: &[IpNetwork],
+) -> IpAddr {
+  let forwarded = headers
+    .get_all("x-forwarded-for")
+    .iter()
+    .filter_map(|value| value.to_str().ok())
+    .flat_map(|value| value.split(','))
+    .collect::<Vec<_>>();
+  let mut client = peer;
+  for ip in forwarded.into_iter().rev() {
+    if !is_trusted(trusted_proxies, &client) {
+      break;
+    }
+    // Stop at the trusted proxy if the header is malformed.
+    let Ok(ip) = ip.trim().parse::<IpAddr>() else {
+      break;
+    };
+    client = ip.to_canonical();
+  }
+  client
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn trusted() -> Vec<IpNetwork> {
+    vec!["10.0.0.0/8".parse().unwrap()]
+  }
+
+  fn forwarded_for(value: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-forwarded-for", value.parse().unwrap());
+    headers
+  }
+
+  fn ip(ip: &str) -> IpAddr {
+    ip.parse().unwrap()
+  }
+
+  #[test]
+  fn forwarded_ignored_from_untrusted_peer() {
+    let headers = forwarded_for("1.2.3.4");
+    assert_eq!(
+      forwarded_client_ip(ip("5.6.7.8"), &headers, &trusted()),
+      ip("5.6.7.8")
+    );
+  }
+
+  #[test]
+  fn forwarded_from_trusted_peer() {
+    let headers = forwarded_for("1.2.3.4");
+    assert_eq!(
+      forwarded_client_ip(ip("10.0.0.1"), &headers, &trusted()),
+      ip("1.2.3.4")
+    );
+  }
+
+  #[test]
+  fn forwarded_stops_at_first_untrusted() {
+    // The client could have set 9.9.9.9 itself.
+    let headers = forwarded_for("9.9.9.9, 1.2.3.4, 10.0.0.2");
+    assert_eq!(
+      forwarded_client_ip(ip("10.0.0.1"), &headers, &trusted()),
+      ip("1.2.3.4")
+    );
+  }
+
+  #[test]
+  fn forwarded_across_multiple_headers() {
+    let mut headers = forwarded_for("1.2.3.4");
+    headers.append("x-forwarded-for", "10.0.0.2".parse().unwrap());
+    assert_eq!(
+      forwarded_client_ip(ip("10.0.0.1"), &headers, &trusted()),
+      ip("1.2.3.4")
+    );
+  }
+
+  #[test]
+  fn forwarded_malformed_stops_at_proxy() {
+    let headers = forwarded_for("1.2.3.4, not-an-ip");
+    assert_eq!(
+      forwarded_client_ip(ip("10.0.0.1"), &headers, &trusted()),
+      ip("10.0.0.1")
+    );
+  }
+
+  #[test]
+  fn forwarded_canonicalizes_mapped_ipv4() {
+    let headers = forwarded_for("::ffff:1.2.3.4");
+    assert_eq!(
+      forwarded_client_ip(ip("10.0.0.1"), &headers, &trusted()),
+      ip("1.2.3.4")
+    );
+  }
+
+  #[test]
+  fn proxied_addr_ignored_from_untrusted_socket() {
+    let proxied = Some("10.0.0.5:1000".parse().unwrap());
+    assert_eq!(
+      client_ip(
+        "5.6.7.8:1000".parse().unwrap(),
+        proxied,
+        &HeaderMap::new(),
+        &trusted()
+      ),
+      ip("5.6.7.8")
+    );
+  }
+
+  #[test]
+  fn proxied_addr_from_trusted_socket() {
+    let proxied = Some("1.2.3.4:1000".parse().unwrap());
+    assert_eq!(
+      client_ip(
+        "10.0.0.1:1000".parse().unwrap(),
+        proxied,
+        &forwarded_for("9.9.9.9"),
+        &trusted()
+      ),
+      ip("1.2.3.4")
+    );
+  }
+}
@@ -1,3 +1,9 @@
+use anyhow::Context;
+use axum_server::tls_openssl::OpenSSLConfig;
+use openssl::ssl::{
+  SslAcceptor, SslFiletype, SslMethod, SslVerifyMode,
+};
+
 use crate::config::periphery_config;
 
 pub async fn ensure_certs() {
@@ -8,6 +14,36 @@ pub async fn ensure_certs() {
   }
 }
 
+/// If `ssl_client_ca_file` is configured,
+/// also requires a client certificate signed by the CA.
+pub fn ssl_config() -> anyhow::Result<OpenSSLConfig> {
+  let config = periphery_config();
+  let Some(ca_file) = &config.ssl_client_ca_file else {
+    return OpenSSLConfig::from_pem_file(
+      &config.ssl_cert_file,
+      &config.ssl_key_file,
+    )
+    .context("Invalid ssl cert / key");
+  };
+  let mut acceptor =
+    SslAcceptor::mozilla_intermediate_v5(SslMethod::tls())
+      .context("failed to create ssl acceptor")?;
+  acceptor
+    .set_certificate_chain_file(&config.ssl_cert_file)
+    .context("Invalid ssl cert")?;
+  acceptor
+    .set_private_key_file(&config.ssl_key_file, SslFiletype::PEM)
+    .context("Invalid ssl key")?;
+  acceptor
+    .set_ca_file(ca_file)
+    .context("Invalid ssl client ca file")?;
+  acceptor.set_verify(
+    SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT,
+  );
+  info!("🔒 Periphery requiring Core client certificate");
+  OpenSSLConfig::try_from(acceptor).context("Invalid ssl cert / key")
+}
+
 #[instrument]
 async fn generate_self_signed_ssl_certs() {
   info!("Generating certs...");
@@ -1,8 +1,9 @@
-use std::{cmp::Ordering, sync::OnceLock};
+use std::{cmp::Ordering, sync::OnceLock, time::Instant};
 
 use async_timing_util::wait_until_timelength;
 use komodo_client::entities::stats::{
-  SingleDiskUsage, SystemInformation, SystemProcess, SystemStats,
+  LoadAverage, SingleDiskUsage, SingleNetworkInterfaceUsage,
+  SystemInformation, SystemProcess, SystemStats,
 };
 use sysinfo::System;
 use tokio::sync::RwLock;
@@ -57,6 +58,12 @@ pub struct StatsClient {
   // the handles used to get the stats
   system: sysinfo::System,
   disks: sysinfo::Disks,
+  networks: sysinfo::Networks,
+  /// When the networks were last refreshed,
+  /// used to turn the received / transmitted bytes into rates.
+  networks_refreshed: Instant,
+  /// Seconds between the last two network refreshes
+  networks_interval: f64,
 }
 
 const BYTES_PER_GB: f64 = 1073741824.0;
@@ -67,6 +74,7 @@ impl Default for StatsClient {
   fn default() -> Self {
     let system = sysinfo::System::new_all();
     let disks = sysinfo::Disks::new_with_refreshed_list();
+    let networks = sysinfo::Networks::new_with_refreshed_list();
     let stats = SystemStats {
       polling_rate: periphery_config().stats_polling_rate,
       ..Default::default()
@@ -75,6 +83,9 @@ impl Default for StatsClient {
       info: get_system_information(&system),
       system,
       disks,
+      networks,
+      networks_refreshed: Instant::now(),
+      networks_interval: 0.0,
       stats,
     }
   }
@@ -84,21 +95,36 @@ impl StatsClient {
   fn refresh(&mut self) {
     self.system.refresh_all();
     self.disks.refresh();
+    self.networks.refresh();
+    self.networks_interval =
+      self.networks_refreshed.elapsed().as_secs_f64();
+    self.networks_refreshed = Instant::now();
   }
 
   fn refresh_lists(&mut self) {
     self.disks.refresh_list();
+    // Refreshing the network list also resets the received / transmitted
+    // counters, so the next rate must be measured from here.
+    self.networks.refresh_list();
+    self.networks_refreshed = Instant::now();
   }
 
   pub fn get_system_stats(&self) -> SystemStats {
     let total_mem = self.system.total_memory();
     let available_mem = self.system.available_memory();
+    let load_average = System::load_average();
     SystemStats {
       cpu_perc: self.system.global_cpu_usage(),
       mem_free_gb: self.system.free_memory() as f64 / BYTES_PER_GB,
       mem_used_gb: (total_mem - available_mem) as f64 / BYTES_PER_GB,
       mem_total_gb: total_mem as f64 / BYTES_PER_GB,
       disks: self.get_disks(),
+      networks: self.get_networks(),
+      load_average: LoadAverage {
+        one: load_average.one,
+        five: load_average.five,
+        fifteen: load_average.fifteen,
+      },
       polling_rate: self.stats.polling_rate,
       refresh_ts: self.stats.refresh_ts,
       refresh_list_ts: self.stats.refresh_list_ts,
@@ -146,6 +172,28 @@ impl StatsClient {
       .collect()
   }
 
+  fn get_networks(&self) -> Vec<SingleNetworkInterfaceUsage> {
+    if self.networks_interval == 0.0 {
+      return Vec::new();
+    }
+    let mut networks = self
+      .networks
+      .list()
+      .iter()
+      // Filter out loopback and the virtual container interfaces
+      .filter(|(name, _)| *name != "lo" && !name.starts_with("veth"))
+      .map(|(name, network)| SingleNetworkInterfaceUsage {
+        name: name.to_string(),
+        rx_bytes_per_sec: network.received() as f64
+          / self.networks_interval,
+        tx_bytes_per_sec: network.transmitted() as f64
+          / self.networks_interval,
+      })
+      .collect::<Vec<_>>();
+    networks.sort_by(|a, b| a.name.cmp(&b.name));
+    networks
+  }
+
   pub fn get_processes(&self) -> Vec<SystemProcess> {
     let mut procs: Vec<_> = self
       .system
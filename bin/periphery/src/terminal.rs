@@ -0,0 +1,115 @@
+use std::io::{Read, Write};
+
+use anyhow::{anyhow, Context};
+use axum::extract::ws::{Message, WebSocket};
+use komodo_client::ws::TerminalMessage;
+use portable_pty::{native_pty_system, CommandBuilder, PtySize};
+use tokio::sync::mpsc;
+
+/// Runs the command under a pty, forwarding binary messages
+/// to / from the terminal until either side closes.
+pub async fn run_terminal(
+  socket: &mut WebSocket,
+  command: CommandBuilder,
+  rows: u16,
+  cols: u16,
+) -> anyhow::Result<()> {
+  let pair = native_pty_system()
+    .openpty(pty_size(rows, cols))
+    .context("failed to open pty")?;
+  let mut child = pair
+    .slave
+    .spawn_command(command)
+    .context("failed to spawn terminal command")?;
+  // Release the slave, so reads on the master end once the child exits.
+  drop(pair.slave);
+  let master = pair.master;
+  let mut killer = child.clone_killer();
+
+  // The pty reader / writer are blocking, so they get their own threads.
+  let mut reader = master
+    .try_clone_reader()
+    .context("failed to get pty reader")?;
+  let (output_tx, mut output_rx) = mpsc::channel::<Vec<u8>>(64);
+  std::thread::spawn(move || {
+    let mut buf = [0u8; 4096];
+    loop {
+      match reader.read(&mut buf) {
+        Ok(0) | Err(_) => break,
+        Ok(n) => {
+          if output_tx.blocking_send(buf[..n].to_vec()).is_err() {
+            break;
+          }
+        }
+      }
+    }
+  });
+
+  let mut writer =
+    master.take_writer().context("failed to get pty writer")?;
+  let (input_tx, input_rx) = std::sync::mpsc::channel::<Vec<u8>>();
+  std::thread::spawn(move || {
+    while let Ok(input) = input_rx.recv() {
+      if writer
+        .write_all(&input)
+        .and_then(|_| writer.flush())
+        .is_err()
+      {
+        break;
+      }
+    }
+  });
+
+  let res = loop {
+    tokio::select! {
+      output = output_rx.recv() => match output {
+        Some(output) => {
+          if let Err(e) = socket.send(Message::Binary(output)).await {
+            break Err(
+              anyhow::Error::from(e)
+                .context("failed to send terminal output"),
+            );
+          }
+        }
+        // The terminal has exited
+        None => break Ok(()),
+      },
+      msg = socket.recv() => match msg {
+        Some(Ok(Message::Binary(input))) => {
+          if input_tx.send(input).is_err() {
+            break Err(anyhow!("terminal stdin is closed"));
+          }
+        }
+        Some(Ok(Message::Text(msg))) => {
+          match TerminalMessage::from_json_str(&msg) {
+            Ok(TerminalMessage::Resize { rows, cols }) => {
+              if let Err(e) = master.resize(pty_size(rows, cols)) {
+                warn!("failed to resize terminal | {e:#}");
+              }
+            }
+            Err(e) => warn!("{e:#}"),
+          }
+        }
+        Some(Ok(Message::Close(_))) | Some(Err(_)) | None => {
+          break Ok(())
+        }
+        Some(Ok(_)) => {}
+      },
+    }
+  };
+
+  let _ = killer.kill();
+  // Reap the child off the async runtime.
+  tokio::task::spawn_blocking(move || child.wait());
+
+  res
+}
+
+fn pty_size(rows: u16, cols: u16) -> PtySize {
+  PtySize {
+    rows,
+    cols,
+    pixel_width: 0,
+    pixel_height: 0,
+  }
+}
@@ -0,0 +1,183 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use futures::{stream::BoxStream, SinkExt, StreamExt};
+use periphery_client::{
+  api::stream::ContainerEvent,
+  tunnel::{TunnelMessage, TunnelResponse},
+};
+use serror::serialize_error;
+use tokio::sync::mpsc;
+use tokio_tungstenite::{
+  connect_async,
+  tungstenite::{client::IntoClientRequest, Message},
+};
+use uuid::Uuid;
+
+use crate::{config::periphery_config, docker::docker_client};
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+const PING_INTERVAL: Duration = Duration::from_secs(30);
+const EVENTS_RESUBSCRIBE_DELAY: Duration = Duration::from_secs(5);
+
+/// If `core_address` is configured, keeps an outbound websocket
+/// open to core, which core uses to send periphery requests.
+pub fn spawn_reverse_connection() {
+  let config = periphery_config();
+  if config.core_address.is_empty() {
+    return;
+  }
+  if config.enrollment_token.is_empty() {
+    warn!(
+      "core_address is configured without an enrollment_token, not connecting to core"
+    );
+    return;
+  }
+  // The enrollment token is sent in the connection request,
+  // so it must never go over plain http / ws.
+  if !config.core_address.starts_with("https://")
+    && !config.core_address.starts_with("wss://")
+  {
+    error!(
+      "core_address must use https:// or wss://, not connecting to core"
+    );
+    return;
+  }
+  tokio::spawn(async move {
+    loop {
+      match connect_and_serve().await {
+        Ok(_) => info!("reverse connection to core closed"),
+        Err(e) => warn!("reverse connection to core failed | {e:#}"),
+      }
+      tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+  });
+}
+
+async fn connect_and_serve() -> anyhow::Result<()> {
+  let config = periphery_config();
+  let address = format!(
+    "{}/ws/periphery",
+    config
+      .core_address
+      .trim_end_matches('/')
+      .replacen("http", "ws", 1)
+  );
+  let mut req = address
+    .as_str()
+    .into_client_request()
+    .context("failed to build core websocket request")?;
+  req.headers_mut().insert(
+    "authorization",
+    config
+      .enrollment_token
+      .parse()
+      .context("failed to parse enrollment token as header value")?,
+  );
+  let (ws, _) = connect_async(req).await.with_context(|| {
+    format!("failed to connect to core websocket at {address}")
+  })?;
+
+  info!("reverse connection to core established at {address}");
+
+  let (mut ws_sender, mut ws_receiver) = ws.split();
+  let (response_tx, mut response_rx) = mpsc::channel(100);
+  let mut ping = tokio::time::interval(PING_INTERVAL);
+  // Container events are pushed to core as they happen.
+  let mut events = docker_client().container_events().boxed();
+
+  loop {
+    tokio::select! {
+      msg = ws_receiver.next() => match msg {
+        Some(Ok(Message::Text(msg))) => {
+          match serde_json::from_str::<TunnelMessage>(&msg) {
+            Ok(TunnelMessage::Request { id, request }) => {
+              let response_tx = response_tx.clone();
+              tokio::spawn(async move {
+                let response = handle_request(request).await;
+                let _ = response_tx
+                  .send(TunnelMessage::Response { id, response })
+                  .await;
+              });
+            }
+            Ok(msg) => warn!("unexpected message from core | {msg:?}"),
+            Err(e) => {
+              warn!("failed to parse message from core | {e:?}")
+            }
+          }
+        }
+        Some(Ok(Message::Close(_))) | None => return Ok(()),
+        Some(Ok(_)) => {}
+        Some(Err(e)) => {
+          return Err(
+            anyhow::Error::from(e)
+              .context("failed to recieve message from core"),
+          )
+        }
+      },
+      Some(msg) = response_rx.recv() => {
+        let msg = serde_json::to_string(&msg)
+          .context("failed to serialize TunnelMessage")?;
+        ws_sender
+          .send(Message::Text(msg))
+          .await
+          .context("failed to send response to core")?;
+      }
+      event = events.next() => {
+        let event = match event {
+          Some(Ok(event)) => event,
+          Some(Err(e)) => {
+            warn!("{e:#}");
+            continue;
+          }
+          None => {
+            warn!("docker events stream ended, resubscribing");
+            events = resubscribe_container_events();
+            continue;
+          }
+        };
+        let msg = serde_json::to_string(&TunnelMessage::Event { event })
+          .context("failed to serialize TunnelMessage")?;
+        ws_sender
+          .send(Message::Text(msg))
+          .await
+          .context("failed to send container event to core")?;
+      }
+      _ = ping.tick() => {
+        ws_sender
+          .send(Message::Ping(Vec::new()))
+          .await
+          .context("failed to ping core")?;
+      }
+    }
+  }
+}
+
+/// Subscribes to container events again after a delay,
+/// so an unavailable docker daemon isn't retried in a hot loop.
+fn resubscribe_container_events(
+) -> BoxStream<'static, anyhow::Result<ContainerEvent>> {
+  futures::stream::once(tokio::time::sleep(EVENTS_RESUBSCRIBE_DELAY))
+    .filter_map(|_| async { None })
+    .chain(docker_client().container_events())
+    .boxed()
+}
+
+/// Resolves the request the same as the http api.
+async fn handle_request(
+  request: serde_json::Value,
+) -> TunnelResponse {
+  let res = match serde_json::from_value(request)
+    .context("failed to parse request as PeripheryRequest")
+  {
+    Ok(request) => crate::router::task(Uuid::new_v4(), request).await,
+    Err(e) => Err(e),
+  };
+  match res {
+    Ok(body) => TunnelResponse { ok: true, body },
+    Err(e) => TunnelResponse {
+      ok: false,
+      body: serialize_error(&e),
+    },
+  }
+}
@@ -0,0 +1,343 @@
+use std::process::Stdio;
+
+use anyhow::{anyhow, Context};
+use axum::{
+  extract::{
+    ws::{Message, WebSocket},
+    WebSocketUpgrade,
+  },
+  response::IntoResponse,
+  routing::get,
+  Router,
+};
+use futures::StreamExt;
+use komodo_client::ws::LogStreamMessage;
+use periphery_client::api::stream::{
+  ExecTerminal, FollowLog, HostTerminal,
+};
+use portable_pty::CommandBuilder;
+use tokio::{
+  io::{AsyncBufReadExt, BufReader},
+  process::Command,
+};
+
+use crate::{
+  compose::docker_compose, config::periphery_config,
+  docker::docker_client, terminal::run_terminal,
+};
+
+pub fn router() -> Router {
+  Router::new()
+    .route("/log", get(log_handler))
+    .route("/exec", get(exec_handler))
+    .route("/terminal", get(host_terminal_handler))
+    .route("/events", get(container_events_handler))
+}
+
+async fn container_events_handler(
+  ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+  ws.on_upgrade(|mut socket| async move {
+    if let Err(e) = stream_container_events(&mut socket).await {
+      warn!("container event stream error | {e:#}");
+    }
+    let _ = socket.close().await;
+  })
+}
+
+async fn stream_container_events(
+  socket: &mut WebSocket,
+) -> anyhow::Result<()> {
+  let events = docker_client().container_events();
+  tokio::pin!(events);
+  loop {
+    let event = tokio::select! {
+      event = events.next() => match event {
+        Some(event) => event?,
+        None => return Ok(()),
+      },
+      msg = socket.recv() => match msg {
+        Some(Ok(Message::Close(_))) | Some(Err(_)) | None => {
+          return Ok(());
+        }
+        Some(Ok(_)) => continue,
+      },
+    };
+    let msg = serde_json::to_string(&event)
+      .context("failed to serialize ContainerEvent")?;
+    socket
+      .send(Message::Text(msg))
+      .await
+      .context("failed to send container event")?;
+  }
+}
+
+async fn log_handler(ws: WebSocketUpgrade) -> impl IntoResponse {
+  ws.on_upgrade(|mut socket| async move {
+    if let Err(e) = follow_log(&mut socket).await {
+      warn!("log stream error | {e:#}");
+      if let Ok(msg) =
+        LogStreamMessage::Error(format!("{e:#}")).to_json_string()
+      {
+        let _ = socket.send(Message::Text(msg)).await;
+      }
+    }
+    let _ = socket.close().await;
+  })
+}
+
+async fn follow_log(socket: &mut WebSocket) -> anyhow::Result<()> {
+  let request = match socket.recv().await {
+    Some(Ok(Message::Text(msg))) => serde_json::from_str(&msg)
+      .context("failed to parse message as FollowLog")?,
+    Some(Ok(msg)) => {
+      return Err(anyhow!("invalid FollowLog message: {msg:?}"))
+    }
+    Some(Err(e)) => {
+      return Err(
+        anyhow::Error::from(e).context("failed to get FollowLog"),
+      )
+    }
+    None => return Ok(()),
+  };
+
+  let mut command = follow_log_command(request);
+  let mut child = command
+    .stdin(Stdio::null())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .kill_on_drop(true)
+    .spawn()
+    .context("failed to spawn log command")?;
+  let mut stdout = BufReader::new(
+    child.stdout.take().context("log command has no stdout")?,
+  )
+  .lines();
+  let mut stderr = BufReader::new(
+    child.stderr.take().context("log command has no stderr")?,
+  )
+  .lines();
+
+  let mut stdout_open = true;
+  let mut stderr_open = true;
+
+  while stdout_open || stderr_open {
+    let msg = tokio::select! {
+      line = stdout.next_line(), if stdout_open => match line {
+        Ok(Some(line)) => LogStreamMessage::Stdout(line),
+        Ok(None) => {
+          stdout_open = false;
+          continue;
+        }
+        Err(e) => return Err(
+          anyhow::Error::from(e).context("failed to read stdout")
+        ),
+      },
+      line = stderr.next_line(), if stderr_open => match line {
+        Ok(Some(line)) => LogStreamMessage::Stderr(line),
+        Ok(None) => {
+          stderr_open = false;
+          continue;
+        }
+        Err(e) => return Err(
+          anyhow::Error::from(e).context("failed to read stderr")
+        ),
+      },
+      msg = socket.recv() => match msg {
+        // Only close messages are expected after the request.
+        Some(Ok(Message::Close(_))) | Some(Err(_)) | None => {
+          return Ok(());
+        }
+        Some(Ok(_)) => continue,
+      },
+    };
+    socket
+      .send(Message::Text(msg.to_json_string()?))
+      .await
+      .context("failed to send log line")?;
+  }
+
+  let status = child
+    .wait()
+    .await
+    .context("failed to wait on log command")?;
+  if !status.success() {
+    return Err(anyhow!("log command exited with {status}"));
+  }
+
+  Ok(())
+}
+
+/// Arguments are passed directly, without a shell.
+fn follow_log_command(request: FollowLog) -> Command {
+  match request {
+    FollowLog::Container {
+      name,
+      tail,
+      timestamps,
+    } => {
+      let mut command = Command::new("docker");
+      command.args(["logs", "-f", "--tail", &tail.to_string()]);
+      if timestamps {
+        command.arg("--timestamps");
+      }
+      command.arg(name);
+      command
+    }
+    FollowLog::ComposeService {
+      project,
+      service,
+      tail,
+      timestamps,
+    } => {
+      let mut args = docker_compose().split(' ');
+      // unwrap ok: split always yields at least one item
+      let mut command = Command::new(args.next().unwrap());
+      command.args(args).args([
+        "-p",
+        &project,
+        "logs",
+        "-f",
+        "--tail",
+        &tail.to_string(),
+      ]);
+      if timestamps {
+        command.arg("--timestamps");
+      }
+      command.arg(service);
+      command
+    }
+  }
+}
+
+async fn exec_handler(ws: WebSocketUpgrade) -> impl IntoResponse {
+  ws.on_upgrade(|mut socket| async move {
+    if let Err(e) = exec_terminal(&mut socket).await {
+      warn!("exec terminal error | {e:#}");
+      let _ = socket.send(Message::Text(format!("{e:#}"))).await;
+    }
+    let _ = socket.close().await;
+  })
+}
+
+async fn exec_terminal(socket: &mut WebSocket) -> anyhow::Result<()> {
+  let request = match socket.recv().await {
+    Some(Ok(Message::Text(msg))) => serde_json::from_str(&msg)
+      .context("failed to parse message as ExecTerminal")?,
+    Some(Ok(msg)) => {
+      return Err(anyhow!("invalid ExecTerminal message: {msg:?}"))
+    }
+    Some(Err(e)) => {
+      return Err(
+        anyhow::Error::from(e).context("failed to get ExecTerminal"),
+      )
+    }
+    None => return Ok(()),
+  };
+  let (command, rows, cols) = exec_terminal_command(request)?;
+  run_terminal(socket, command, rows, cols).await
+}
+
+/// Arguments are passed directly, without a shell.
+/// The command is split into arguments following shell quoting rules.
+fn exec_terminal_command(
+  request: ExecTerminal,
+) -> anyhow::Result<(CommandBuilder, u16, u16)> {
+  match request {
+    ExecTerminal::Container {
+      name,
+      command,
+      rows,
+      cols,
+    } => {
+      let mut builder = CommandBuilder::new("docker");
+      builder.args(["exec", "-it", &name]);
+      builder.args(split_command(&command)?);
+      Ok((builder, rows, cols))
+    }
+    ExecTerminal::ComposeService {
+      project,
+      service,
+      command,
+      rows,
+      cols,
+    } => {
+      let mut args = docker_compose().split(' ');
+      // unwrap ok: split always yields at least one item
+      let mut builder = CommandBuilder::new(args.next().unwrap());
+      builder.args(args);
+      builder.args(["-p", &project, "exec", &service]);
+      builder.args(split_command(&command)?);
+      Ok((builder, rows, cols))
+    }
+  }
+}
+
+fn split_command(command: &str) -> anyhow::Result<Vec<String>> {
+  let args = shell_words::split(command)
+    .with_context(|| format!("invalid command: {command}"))?;
+  if args.is_empty() {
+    return Err(anyhow!("command cannot be empty"));
+  }
+  Ok(args)
+}
+
+async fn host_terminal_handler(
+  ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+  ws.on_upgrade(|mut socket| async move {
+    if let Err(e) = host_terminal(&mut socket).await {
+      warn!("host terminal error | {e:#}");
+      let _ = socket.send(Message::Text(format!("{e:#}"))).await;
+    }
+    let _ = socket.close().await;
+  })
+}
+
+async fn host_terminal(socket: &mut WebSocket) -> anyhow::Result<()> {
+  let config = periphery_config();
+  if config.disable_host_terminal {
+    return Err(anyhow!(
+      "host terminal is disabled in the periphery config"
+    ));
+  }
+  let HostTerminal { rows, cols } = match socket.recv().await {
+    Some(Ok(Message::Text(msg))) => serde_json::from_str(&msg)
+      .context("failed to parse message as HostTerminal")?,
+    Some(Ok(msg)) => {
+      return Err(anyhow!("invalid HostTerminal message: {msg:?}"))
+    }
+    Some(Err(e)) => {
+      return Err(
+        anyhow::Error::from(e).context("failed to get HostTerminal"),
+      )
+    }
+    None => return Ok(()),
+  };
+  let mut builder = CommandBuilder::new(&config.host_terminal_shell);
+  builder.cwd(&config.host_terminal_dir);
+  run_terminal(socket, builder, rows, cols).await
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn split_command_keeps_quoted_arguments() {
+    assert_eq!(
+      split_command(r#"sh -c "echo 'hello world'""#).unwrap(),
+      ["sh", "-c", "echo 'hello world'"]
+    );
+  }
+
+  #[test]
+  fn split_command_rejects_unbalanced_quotes() {
+    assert!(split_command(r#"sh -c "echo"#).is_err());
+  }
+
+  #[test]
+  fn split_command_rejects_empty() {
+    assert!(split_command("  ").is_err());
+  }
+}
@@ -60,7 +60,7 @@ pub struct BatchDeploy {
   /// Supports multiline and comma delineated combinations of the above.
   ///
   /// Example:
-  /// ```
+  /// ```text
   /// # match all foo-* deployments
   /// foo-*
   /// # add some more
@@ -92,6 +92,34 @@ pub struct PullDeployment {
 
 //
 
+/// Redeploys the image and config of an earlier successful deploy
+/// of the target deployment. Response: [Update]
+///
+/// Variables and secrets are interpolated with their current values.
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  Clone,
+  PartialEq,
+  Request,
+  EmptyTraits,
+  Parser,
+)]
+#[empty_traits(KomodoExecuteRequest)]
+#[response(Update)]
+pub struct RollbackDeployment {
+  /// Name or id
+  pub deployment: String,
+  /// The id of the successful `Deploy` / `RollbackDeployment` Update to redeploy.
+  /// If not provided, uses the last successful deploy
+  /// before the latest one.
+  pub update_id: Option<String>,
+}
+
+//
+
 /// Starts the container for the target deployment. Response: [Update]
 ///
 /// 1. Runs `docker start ${container_name}`.
@@ -260,7 +288,7 @@ pub struct BatchDestroyDeployment {
   /// Supports multiline and comma delineated combinations of the above.
   ///
   /// Example:
-  /// ```
+  /// ```text
   /// # match all foo-* deployments
   /// foo-*
   /// # add some more
@@ -74,6 +74,7 @@ pub enum Execution {
   Deploy(Deploy),
   BatchDeploy(BatchDeploy),
   PullDeployment(PullDeployment),
+  RollbackDeployment(RollbackDeployment),
   StartDeployment(StartDeployment),
   RestartDeployment(RestartDeployment),
   PauseDeployment(PauseDeployment),
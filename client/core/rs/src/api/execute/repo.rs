@@ -58,7 +58,7 @@ pub struct BatchCloneRepo {
   /// Supports multiline and comma delineated combinations of the above.
   ///
   /// Example:
-  /// ```
+  /// ```text
   /// # match all foo-* repos
   /// foo-*
   /// # add some more
@@ -114,7 +114,7 @@ pub struct BatchPullRepo {
   /// Supports multiline and comma delineated combinations of the above.
   ///
   /// Example:
-  /// ```
+  /// ```text
   /// # match all foo-* repos
   /// foo-*
   /// # add some more
@@ -174,7 +174,7 @@ pub struct BatchBuildRepo {
   /// Supports multiline and comma delineated combinations of the above.
   ///
   /// Example:
-  /// ```
+  /// ```text
   /// # match all foo-* repos
   /// foo-*
   /// # add some more
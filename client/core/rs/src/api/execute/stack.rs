@@ -53,7 +53,7 @@ pub struct BatchDeployStack {
   /// Supports multiline and comma delineated combinations of the above.
   ///
   /// Example:
-  /// ```
+  /// ```text
   /// # match all foo-* stacks
   /// foo-*
   /// # add some more
@@ -109,7 +109,7 @@ pub struct BatchDeployStackIfChanged {
   /// Supports multiline and comma delineated combinations of the above.
   ///
   /// Example:
-  /// ```
+  /// ```text
   /// # match all foo-* stacks
   /// foo-*
   /// # add some more
@@ -309,7 +309,7 @@ pub struct BatchDestroyStack {
   /// Supports multiline and comma delineated combinations of the above.
   ///d
   /// Example:
-  /// ```
+  /// ```text
   /// # match all foo-* stacks
   /// foo-*
   /// # add some more
@@ -0,0 +1,42 @@
+use derive_empty_traits::EmptyTraits;
+use resolver_api::derive::Request;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::alert_silence::AlertSilence;
+
+use super::KomodoReadRequest;
+
+/// Get an alert silence. Response: [AlertSilence].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Request, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(GetAlertSilenceResponse)]
+pub struct GetAlertSilence {
+  /// The id of the silence.
+  pub id: String,
+}
+
+#[typeshare]
+pub type GetAlertSilenceResponse = AlertSilence;
+
+//
+
+/// List alert silences, sorted by end time descending.
+/// Response: [ListAlertSilencesResponse].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Default, Request, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(ListAlertSilencesResponse)]
+pub struct ListAlertSilences {
+  /// Only include silences which have not yet ended.
+  #[serde(default)]
+  pub active_only: bool,
+}
+
+#[typeshare]
+pub type ListAlertSilencesResponse = Vec<AlertSilence>;
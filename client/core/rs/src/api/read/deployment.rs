@@ -9,8 +9,9 @@ use crate::entities::{
     DeploymentQuery, DeploymentState,
   },
   docker::container::{ContainerListItem, ContainerStats},
+  stats::ContainerStatsRecord,
   update::Log,
-  SearchCombinator, I64, U64,
+  ResourceTarget, SearchCombinator, Timelength, I64, U64,
 };
 
 use super::KomodoReadRequest;
@@ -190,6 +191,40 @@ pub type GetDeploymentStatsResponse = ContainerStats;
 
 //
 
+/// Paginated endpoint serving historical (timeseries) container stats for graphing.
+/// Supports Deployments, and Stacks along with the `service`.
+/// Response: [GetHistoricalContainerStatsResponse].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Request, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(GetHistoricalContainerStatsResponse)]
+pub struct GetHistoricalContainerStats {
+  /// The Deployment or Stack. Id or name.
+  pub target: ResourceTarget,
+  /// The Stack service. Required for Stack targets.
+  pub service: Option<String>,
+  /// The granularity of the data.
+  pub granularity: Timelength,
+  /// Page of historical data. Default is 0, which is the most recent data.
+  /// Use with the `next_page` field of the response.
+  #[serde(default)]
+  pub page: u32,
+}
+
+/// Response to [GetHistoricalContainerStats].
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GetHistoricalContainerStatsResponse {
+  /// The timeseries page of data.
+  pub stats: Vec<ContainerStatsRecord>,
+  /// If there is a next page of data, pass this to `page` to get it.
+  pub next_page: Option<u32>,
+}
+
+//
+
 /// Get current action state for the deployment.
 /// Response: [DeploymentActionState].
 #[typeshare]
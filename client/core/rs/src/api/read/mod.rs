@@ -5,6 +5,7 @@ use typeshare::typeshare;
 
 mod action;
 mod alert;
+mod alert_silence;
 mod alerter;
 mod build;
 mod builder;
@@ -27,6 +28,7 @@ mod variable;
 
 pub use action::*;
 pub use alert::*;
+pub use alert_silence::*;
 pub use alerter::*;
 pub use build::*;
 pub use builder::*;
@@ -16,7 +16,8 @@ use crate::entities::{
   },
   stack::ComposeProject,
   stats::{
-    SystemInformation, SystemProcess, SystemStats, SystemStatsRecord,
+    StatsResolution, SystemInformation, SystemProcess, SystemStats,
+    SystemStatsRecord, SystemStatsRollup,
   },
   update::Log,
   ResourceTarget, SearchCombinator, Timelength, I64, U64,
@@ -520,6 +521,9 @@ pub type ListSystemProcessesResponse = Vec<SystemProcess>;
 //
 
 /// Paginated endpoint serving historical (timeseries) server stats for graphing.
+/// The resolution is picked based on the time range covered by the page.
+/// With `1-hr` granularity and above, the stats are served from the
+/// hourly rollups, and with `1-day` and above, from the daily rollups.
 /// Response: [GetHistoricalServerStatsResponse].
 #[typeshare]
 #[derive(
@@ -544,7 +548,14 @@ pub struct GetHistoricalServerStats {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct GetHistoricalServerStatsResponse {
   /// The timeseries page of data.
+  /// When served from rollups, these hold the averages.
   pub stats: Vec<SystemStatsRecord>,
+  /// The resolution the page is served at.
+  #[serde(default)]
+  pub resolution: StatsResolution,
+  /// The min / avg / max rollups, when the resolution is `Hour` or `Day`.
+  #[serde(default)]
+  pub rollups: Vec<SystemStatsRollup>,
   /// If there is a next page of data, pass this to `page` to get it.
   pub next_page: Option<u32>,
 }
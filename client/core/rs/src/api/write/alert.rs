@@ -0,0 +1,25 @@
+use derive_empty_traits::EmptyTraits;
+use resolver_api::derive::Request;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::alert::Alert;
+
+use super::KomodoWriteRequest;
+
+/// Acknowledge an open alert, which stops any repeat notifications.
+/// Requires Execute permissions on the alert target.
+/// Response: [Alert].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Request, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(AcknowledgeAlertResponse)]
+pub struct AcknowledgeAlert {
+  /// The id of the alert to acknowledge.
+  pub id: String,
+}
+
+#[typeshare]
+pub type AcknowledgeAlertResponse = Alert;
@@ -0,0 +1,104 @@
+use derive_empty_traits::EmptyTraits;
+use resolver_api::derive::Request;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::{
+  alert::{AlertDataVariant, SeverityLevel},
+  alert_silence::AlertSilence,
+  ResourceTarget, I64,
+};
+
+use super::KomodoWriteRequest;
+
+//
+
+/// **Admin only.** Create an alert silence. Response: [AlertSilence].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Request, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(CreateAlertSilenceResponse)]
+pub struct CreateAlertSilence {
+  /// A comment explaining the silence.
+  #[serde(default)]
+  pub comment: String,
+  /// Only silence alerts on these resources.
+  #[serde(default)]
+  pub targets: Vec<ResourceTarget>,
+  /// Only silence alerts on resources with any of these tags (id or name).
+  #[serde(default)]
+  pub tags: Vec<String>,
+  /// Only silence these alert types.
+  #[serde(default)]
+  pub alert_types: Vec<AlertDataVariant>,
+  /// Only silence alerts with these severity levels.
+  #[serde(default)]
+  pub levels: Vec<SeverityLevel>,
+  /// Unix timestamp in milliseconds the silence starts.
+  /// If not given, the silence starts immediately.
+  #[serde(default)]
+  pub start_ts: I64,
+  /// Unix timestamp in milliseconds the silence ends.
+  pub end_ts: I64,
+}
+
+#[typeshare]
+pub type CreateAlertSilenceResponse = AlertSilence;
+
+//
+
+/// **Admin only.** Update an alert silence. Response: [AlertSilence].
+///
+/// Note. Set `end_ts` to end a silence early.
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Request, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(UpdateAlertSilenceResponse)]
+pub struct UpdateAlertSilence {
+  /// The id of the silence to update.
+  pub id: String,
+  /// A comment explaining the silence.
+  #[serde(default)]
+  pub comment: String,
+  /// Only silence alerts on these resources.
+  #[serde(default)]
+  pub targets: Vec<ResourceTarget>,
+  /// Only silence alerts on resources with any of these tags (id or name).
+  #[serde(default)]
+  pub tags: Vec<String>,
+  /// Only silence these alert types.
+  #[serde(default)]
+  pub alert_types: Vec<AlertDataVariant>,
+  /// Only silence alerts with these severity levels.
+  #[serde(default)]
+  pub levels: Vec<SeverityLevel>,
+  /// Unix timestamp in milliseconds the silence starts.
+  pub start_ts: I64,
+  /// Unix timestamp in milliseconds the silence ends.
+  pub end_ts: I64,
+}
+
+#[typeshare]
+pub type UpdateAlertSilenceResponse = AlertSilence;
+
+//
+
+/// **Admin only.** Delete an alert silence,
+/// and return the deleted silence. Response: [AlertSilence].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Request, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(DeleteAlertSilenceResponse)]
+pub struct DeleteAlertSilence {
+  /// The id of the silence to delete.
+  pub id: String,
+}
+
+#[typeshare]
+pub type DeleteAlertSilenceResponse = AlertSilence;
@@ -1,4 +1,6 @@
 mod action;
+mod alert;
+mod alert_silence;
 mod alerter;
 mod api_key;
 mod build;
@@ -19,6 +21,8 @@ mod user_group;
 mod variable;
 
 pub use action::*;
+pub use alert::*;
+pub use alert_silence::*;
 pub use alerter::*;
 pub use api_key::*;
 pub use build::*;
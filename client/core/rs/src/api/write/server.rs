@@ -84,6 +84,68 @@ pub struct RenameServer {
 
 //
 
+/// Clear the pinned Periphery certificate fingerprint,
+/// so the next certificate Periphery presents is pinned.
+/// Use after rotating the Periphery certificate.
+/// Response: [Update].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Request, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(Update)]
+pub struct UnpinServerCertificate {
+  /// Server Id or name
+  pub server: String,
+}
+
+//
+
+/// Generate a new enrollment token for the Server, replacing any existing one.
+/// Periphery configured with the token (and the `core_address`)
+/// dials out to Core, instead of Core calling the Server `address`.
+/// Response: [CreateServerEnrollmentTokenResponse].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Request, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(CreateServerEnrollmentTokenResponse)]
+pub struct CreateServerEnrollmentToken {
+  /// Server Id or name
+  pub server: String,
+}
+
+/// Response for [CreateServerEnrollmentToken].
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreateServerEnrollmentTokenResponse {
+  /// The enrollment token to configure on Periphery.
+  ///
+  /// Note.
+  /// Only a hash of the token is stored,
+  /// there is no way to get the token again after it is returned here.
+  pub token: String,
+}
+
+//
+
+/// Remove the Server enrollment token, closing any connection
+/// Periphery made with it. Core goes back to calling the Server `address`.
+/// Response: [Update].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Request, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(Update)]
+pub struct DeleteServerEnrollmentToken {
+  /// Server Id or name
+  pub server: String,
+}
+
+//
+
 /// Create a docker network on the server.
 /// Response: [Update]
 ///
@@ -25,6 +25,14 @@ pub struct ActionListItemInfo {
   pub last_run_at: I64,
   /// Whether last action run successful
   pub state: ActionState,
+  /// If the action has a schedule, this is the timestamp (ms)
+  /// of the next scheduled run.
+  pub next_scheduled_run: Option<I64>,
+  /// The timestamp (ms) of the last run triggered by the schedule.
+  pub last_scheduled_run: Option<I64>,
+  /// If there is an error parsing the schedule expression / timezone,
+  /// it will be given here.
+  pub schedule_error: Option<String>,
 }
 
 #[typeshare]
@@ -52,6 +60,9 @@ pub struct ActionInfo {
   /// When action was last run
   #[serde(default)]
   pub last_run_at: I64,
+  /// The timestamp (ms) of the last run triggered by the schedule.
+  #[serde(default)]
+  pub last_scheduled_run: Option<I64>,
 }
 
 #[typeshare(serialized_as = "Partial<ActionConfig>")]
@@ -82,12 +93,40 @@ pub struct ActionConfig {
   #[serde(default)]
   #[builder(default)]
   pub webhook_secret: String,
+
+  /// Optionally provide a CRON expression to run the action on a schedule.
+  /// Uses the format (second is optional):
+  /// `second minute hour day-of-month month day-of-week`, eg:
+  /// ```text
+  /// 0 0 4 * * *
+  /// ```
+  /// If empty, the action is not scheduled.
+  #[serde(default)]
+  #[builder(default)]
+  pub schedule: String,
+
+  /// Whether the schedule is enabled, if one is provided.
+  /// Can be used to temporarily disable the schedule.
+  #[serde(default = "default_schedule_enabled")]
+  #[builder(default = "default_schedule_enabled()")]
+  #[partial_default(default_schedule_enabled())]
+  pub schedule_enabled: bool,
+
+  /// Optionally provide a timezone (TZ Identifier, eg `America/New_York`)
+  /// to evaluate the schedule in. If empty, uses the Core local timezone.
+  #[serde(default)]
+  #[builder(default)]
+  pub schedule_timezone: String,
 }
 
 fn default_webhook_enabled() -> bool {
   true
 }
 
+fn default_schedule_enabled() -> bool {
+  true
+}
+
 impl ActionConfig {
   pub fn builder() -> ActionConfigBuilder {
     ActionConfigBuilder::default()
@@ -100,6 +139,9 @@ impl Default for ActionConfig {
       file_contents: Default::default(),
       webhook_enabled: default_webhook_enabled(),
       webhook_secret: Default::default(),
+      schedule: Default::default(),
+      schedule_enabled: default_schedule_enabled(),
+      schedule_timezone: Default::default(),
     }
   }
 }
@@ -54,6 +54,25 @@ pub struct Alert {
 
   /// The timestamp of alert resolution
   pub resolved_ts: Option<I64>,
+
+  /// Whether the alert was silenced by an [AlertSilence][super::alert_silence::AlertSilence]
+  /// when it was last opened or updated. Alerts are only held back from Alerters
+  /// while a matching silence is active.
+  #[serde(default)]
+  pub silenced: bool,
+
+  /// Whether the alert has been acknowledged.
+  /// Acknowledged alerts are not renotified.
+  #[serde(default)]
+  pub acknowledged: bool,
+
+  /// The id of the user who acknowledged the alert
+  #[serde(default)]
+  pub acknowledged_by: Option<String>,
+
+  /// The timestamp of alert acknowledgement
+  #[serde(default)]
+  pub acknowledged_ts: Option<I64>,
 }
 
 /// The variants of data related to the alert.
@@ -128,6 +147,34 @@ pub enum AlertData {
     total_gb: f64,
   },
 
+  /// A server has high network usage.
+  ServerNetwork {
+    /// The id of the server
+    id: String,
+    /// The name of the server
+    name: String,
+    /// The region of the server
+    region: Option<String>,
+    /// The busiest network interface
+    interface: String,
+    /// The receive rate on the interface in Mbps
+    rx_mbps: f64,
+    /// The transmit rate on the interface in Mbps
+    tx_mbps: f64,
+  },
+
+  /// A server has high load average.
+  ServerLoad {
+    /// The id of the server
+    id: String,
+    /// The name of the server
+    name: String,
+    /// The region of the server
+    region: Option<String>,
+    /// The 5 minute load average
+    load_average: f64,
+  },
+
   /// A container's state has changed unexpectedly.
   ContainerStateChange {
     /// The id of the deployment
@@ -190,6 +237,37 @@ pub enum AlertData {
     image: String,
   },
 
+  /// A Deployment / Stack container is reporting unhealthy.
+  ContainerUnhealthy {
+    /// The id of the Deployment / Stack
+    id: String,
+    /// The name of the Deployment / Stack
+    name: String,
+    /// The server id of server that the container is on
+    server_id: String,
+    /// The server name
+    server_name: String,
+    /// The container name
+    container: String,
+  },
+
+  /// A Deployment / Stack container is being
+  /// repeatedly restarted by docker.
+  ContainerRestartLoop {
+    /// The id of the Deployment / Stack
+    id: String,
+    /// The name of the Deployment / Stack
+    name: String,
+    /// The server id of server that the container is on
+    server_id: String,
+    /// The server name
+    server_name: String,
+    /// The container name
+    container: String,
+    /// The number of restarts within the detection window
+    restarts: I64,
+  },
+
   /// An AWS builder failed to terminate.
   AwsBuilderTerminationFailed {
     /// The id of the aws instance which failed to terminate
@@ -223,6 +301,40 @@ pub enum AlertData {
     /// The name of the repo
     name: String,
   },
+
+  /// A procedure has failed
+  ProcedureFailed {
+    /// The id of the procedure
+    id: String,
+    /// The name of the procedure
+    name: String,
+    /// The name of the stage which failed
+    stage: String,
+    /// An excerpt of the error log
+    error: String,
+  },
+
+  /// An action has failed
+  ActionFailed {
+    /// The id of the action
+    id: String,
+    /// The name of the action
+    name: String,
+    /// An excerpt of the error log
+    error: String,
+  },
+
+  /// A resource sync has failed
+  ResourceSyncFailed {
+    /// The id of the resource sync
+    id: String,
+    /// The name of the resource sync
+    name: String,
+    /// The stage of the sync which failed
+    stage: String,
+    /// An excerpt of the error log
+    error: String,
+  },
 }
 
 impl Default for AlertData {
@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use super::{
+  alert::{AlertDataVariant, SeverityLevel},
+  MongoId, ResourceTarget, I64,
+};
+
+/// Silences matching alerts for a period of time,
+/// such as during planned maintenance.
+///
+/// Silenced alerts are still recorded, and marked as silenced,
+/// but are not sent to any Alerter.
+///
+/// An alert matches when it matches every non-empty matcher.
+/// A silence with no matchers will silence all alerts.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(
+  feature = "mongo",
+  derive(mongo_indexed::derive::MongoIndexed)
+)]
+pub struct AlertSilence {
+  /// The Mongo ID of the silence.
+  /// This field is de/serialized from/to JSON as
+  /// `{ "_id": { "$oid": "..." }, ...(rest of serialized AlertSilence) }`
+  #[serde(
+    default,
+    rename = "_id",
+    skip_serializing_if = "String::is_empty",
+    with = "bson::serde_helpers::hex_string_as_object_id"
+  )]
+  pub id: MongoId,
+
+  /// A comment explaining the silence, eg. the maintenance being done.
+  #[serde(default)]
+  pub comment: String,
+
+  /// Only silence alerts on these resources.
+  /// Including a Server will also silence alerts on
+  /// Deployments and Stacks on that Server.
+  #[serde(default)]
+  pub targets: Vec<ResourceTarget>,
+
+  /// Only silence alerts on resources with any of these tag ids.
+  #[serde(default)]
+  pub tags: Vec<String>,
+
+  /// Only silence these alert types.
+  #[serde(default)]
+  pub alert_types: Vec<AlertDataVariant>,
+
+  /// Only silence alerts with these severity levels.
+  #[serde(default)]
+  pub levels: Vec<SeverityLevel>,
+
+  /// Unix timestamp in milliseconds the silence starts.
+  #[cfg_attr(feature = "mongo", index)]
+  pub start_ts: I64,
+
+  /// Unix timestamp in milliseconds the silence ends.
+  #[cfg_attr(feature = "mongo", index)]
+  pub end_ts: I64,
+
+  /// The id of the user who created the silence.
+  #[serde(default)]
+  pub created_by: String,
+
+  /// Unix timestamp in milliseconds the silence was created.
+  #[serde(default)]
+  pub created_at: I64,
+}
+
+impl AlertSilence {
+  /// Whether the silence is in effect at the given timestamp.
+  pub fn is_active(&self, ts: I64) -> bool {
+    self.start_ts <= ts && ts < self.end_ts
+  }
+}
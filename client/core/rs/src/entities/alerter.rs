@@ -10,7 +10,7 @@ use typeshare::typeshare;
 use super::{
   alert::AlertDataVariant,
   resource::{Resource, ResourceListItem, ResourceQuery},
-  ResourceTarget,
+  ResourceTarget, U64,
 };
 
 #[typeshare]
@@ -32,7 +32,9 @@ pub struct AlerterListItemInfo {
 pub type _PartialAlerterConfig = PartialAlerterConfig;
 
 #[typeshare]
-#[derive(Serialize, Deserialize, Debug, Clone, Builder, Partial)]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Default, Builder, Partial,
+)]
 #[partial_derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[partial(skip_serializing_none, from, diff)]
 pub struct AlerterConfig {
@@ -64,6 +66,13 @@ pub struct AlerterConfig {
   #[serde(default)]
   #[builder(default)]
   pub except_resources: Vec<ResourceTarget>,
+
+  /// Send the alert again every `renotify_minutes`
+  /// while it is unresolved and unacknowledged.
+  /// If 0, alerts are only sent when they open, change, or resolve.
+  #[serde(default)]
+  #[builder(default)]
+  pub renotify_minutes: U64,
 }
 
 impl AlerterConfig {
@@ -72,18 +81,6 @@ impl AlerterConfig {
   }
 }
 
-impl Default for AlerterConfig {
-  fn default() -> Self {
-    Self {
-      enabled: Default::default(),
-      endpoint: Default::default(),
-      alert_types: Default::default(),
-      resources: Default::default(),
-      except_resources: Default::default(),
-    }
-  }
-}
-
 // ENDPOINTS
 
 #[typeshare]
@@ -110,6 +107,18 @@ pub enum AlerterEndpoint {
 
   /// Send alert to a Discord app
   Discord(DiscordAlerterEndpoint),
+
+  /// Send alert as an email over SMTP
+  Email(EmailAlerterEndpoint),
+
+  /// Send alert as a push notification via ntfy
+  Ntfy(NtfyAlerterEndpoint),
+
+  /// Send alert as a push notification via Gotify
+  Gotify(GotifyAlerterEndpoint),
+
+  /// Send alert as a push notification via Pushover
+  Pushover(PushoverAlerterEndpoint),
 }
 
 impl Default for AlerterEndpoint {
@@ -124,16 +133,56 @@ impl Default for AlerterEndpoint {
   Debug, Clone, PartialEq, Serialize, Deserialize, Builder,
 )]
 pub struct CustomAlerterEndpoint {
-  /// The http/s endpoint to send the POST to
+  /// The http/s endpoint to send the request to
   #[serde(default = "default_custom_url")]
   #[builder(default = "default_custom_url()")]
   pub url: String,
+
+  /// The HTTP method to use.
+  /// Default: `POST`
+  #[serde(default)]
+  #[builder(default)]
+  pub method: CustomAlerterMethod,
+
+  /// Extra headers to send with the request,
+  /// one `Name: Value` per line.
+  /// Lines starting with `#` are ignored.
+  ///
+  /// Credential headers, eg. `Authorization`, must reference a core
+  /// secret or variable, eg. `Authorization: Bearer [[WEBHOOK_TOKEN]]`.
+  #[serde(default)]
+  #[builder(default)]
+  pub headers: String,
+
+  /// A Handlebars template for the request body,
+  /// rendered against the alert.
+  /// If empty, the alert is sent serialized as JSON.
+  ///
+  /// Along with the alert fields (`level`, `target`, `data`, ...),
+  /// the template has access to:
+  /// - `id`: The alert id
+  /// - `severity`: The alert level, eg. `CRITICAL`
+  /// - `target_name`: The name of the alerting resource
+  /// - `resolved`: Whether the alert is resolved
+  /// - `title` / `message`: A plain text summary of the alert
+  /// - `link`: A link to the alerting resource in Komodo
+  ///
+  /// Use the `json` helper to embed values in a JSON body,
+  /// eg. `{"text": {{json message}}}`.
+  /// Keys, eg. a PagerDuty routing key, can reference a core
+  /// secret or variable, eg. `[[PAGERDUTY_ROUTING_KEY]]`.
+  #[serde(default)]
+  #[builder(default)]
+  pub body: String,
 }
 
 impl Default for CustomAlerterEndpoint {
   fn default() -> Self {
     Self {
       url: default_custom_url(),
+      method: Default::default(),
+      headers: Default::default(),
+      body: Default::default(),
     }
   }
 }
@@ -142,6 +191,29 @@ fn default_custom_url() -> String {
   String::from("http://localhost:7000")
 }
 
+/// The HTTP method used by a Custom alerter.
+#[typeshare]
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  Default,
+  PartialEq,
+  Eq,
+  Serialize,
+  Deserialize,
+  Display,
+  EnumString,
+)]
+#[serde(rename_all = "UPPERCASE")]
+#[strum(serialize_all = "UPPERCASE")]
+pub enum CustomAlerterMethod {
+  #[default]
+  Post,
+  Put,
+  Patch,
+}
+
 /// Configuration for a Slack alerter.
 #[typeshare]
 #[derive(
@@ -194,6 +266,200 @@ fn default_discord_url() -> String {
   )
 }
 
+/// Configuration for an Email (SMTP) alerter.
+#[typeshare]
+#[derive(
+  Debug, Clone, PartialEq, Serialize, Deserialize, Builder,
+)]
+pub struct EmailAlerterEndpoint {
+  /// The SMTP server host, eg. `smtp.example.com`
+  #[serde(default = "default_smtp_host")]
+  #[builder(default = "default_smtp_host()")]
+  pub host: String,
+
+  /// The SMTP server port.
+  /// Default: `587`
+  #[serde(default = "default_smtp_port")]
+  #[builder(default = "default_smtp_port()")]
+  pub port: u16,
+
+  /// How to secure the connection to the SMTP server.
+  /// Default: `StartTls`
+  #[serde(default)]
+  #[builder(default)]
+  pub security: SmtpSecurity,
+
+  /// The username to authenticate with.
+  /// If empty, will not authenticate.
+  #[serde(default)]
+  #[builder(default)]
+  pub username: String,
+
+  /// The password to authenticate with.
+  /// Must reference a core secret or variable, eg. `[[SMTP_PASSWORD]]`,
+  /// so the password itself is never stored on the alerter.
+  #[serde(default)]
+  #[builder(default)]
+  pub password: String,
+
+  /// The address to send the email from,
+  /// eg. `Komodo <komodo@example.com>`
+  #[serde(default = "default_email_from")]
+  #[builder(default = "default_email_from()")]
+  pub from: String,
+
+  /// The addresses to send the email to.
+  #[serde(default)]
+  #[builder(default)]
+  pub to: Vec<String>,
+}
+
+impl Default for EmailAlerterEndpoint {
+  fn default() -> Self {
+    Self {
+      host: default_smtp_host(),
+      port: default_smtp_port(),
+      security: Default::default(),
+      username: Default::default(),
+      password: Default::default(),
+      from: default_email_from(),
+      to: Default::default(),
+    }
+  }
+}
+
+fn default_smtp_host() -> String {
+  String::from("localhost")
+}
+
+fn default_smtp_port() -> u16 {
+  587
+}
+
+fn default_email_from() -> String {
+  String::from("Komodo <komodo@localhost>")
+}
+
+/// How to secure the connection to the SMTP server.
+#[typeshare]
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  Default,
+  PartialEq,
+  Eq,
+  Serialize,
+  Deserialize,
+  Display,
+  EnumString,
+)]
+pub enum SmtpSecurity {
+  /// Plaintext connection. Only use with a local SMTP relay / sink.
+  None,
+  /// Upgrade a plaintext connection using STARTTLS (usually port 587).
+  #[default]
+  StartTls,
+  /// Implicit TLS connection (usually port 465).
+  Tls,
+}
+
+/// Configuration for an ntfy alerter.
+#[typeshare]
+#[derive(
+  Debug, Clone, PartialEq, Serialize, Deserialize, Builder,
+)]
+pub struct NtfyAlerterEndpoint {
+  /// The ntfy server url.
+  /// Default: `https://ntfy.sh`
+  #[serde(default = "default_ntfy_url")]
+  #[builder(default = "default_ntfy_url()")]
+  pub url: String,
+
+  /// The topic to publish to.
+  #[serde(default = "default_ntfy_topic")]
+  #[builder(default = "default_ntfy_topic()")]
+  pub topic: String,
+
+  /// Optional access token for protected topics.
+  /// Must reference a core secret or variable, eg. `[[NTFY_TOKEN]]`.
+  #[serde(default)]
+  #[builder(default)]
+  pub token: String,
+}
+
+impl Default for NtfyAlerterEndpoint {
+  fn default() -> Self {
+    Self {
+      url: default_ntfy_url(),
+      topic: default_ntfy_topic(),
+      token: Default::default(),
+    }
+  }
+}
+
+fn default_ntfy_url() -> String {
+  String::from("https://ntfy.sh")
+}
+
+fn default_ntfy_topic() -> String {
+  String::from("komodo")
+}
+
+/// Configuration for a Gotify alerter.
+#[typeshare]
+#[derive(
+  Debug, Clone, PartialEq, Serialize, Deserialize, Builder,
+)]
+pub struct GotifyAlerterEndpoint {
+  /// The Gotify server url.
+  #[serde(default = "default_gotify_url")]
+  #[builder(default = "default_gotify_url()")]
+  pub url: String,
+
+  /// The Gotify application token.
+  /// Must reference a core secret or variable, eg. `[[GOTIFY_TOKEN]]`.
+  #[serde(default)]
+  #[builder(default)]
+  pub token: String,
+}
+
+impl Default for GotifyAlerterEndpoint {
+  fn default() -> Self {
+    Self {
+      url: default_gotify_url(),
+      token: Default::default(),
+    }
+  }
+}
+
+fn default_gotify_url() -> String {
+  String::from("http://localhost:80")
+}
+
+/// Configuration for a Pushover alerter.
+#[typeshare]
+#[derive(
+  Debug, Clone, Default, PartialEq, Serialize, Deserialize, Builder,
+)]
+pub struct PushoverAlerterEndpoint {
+  /// The Pushover application API token.
+  /// Must reference a core secret or variable, eg. `[[PUSHOVER_TOKEN]]`.
+  #[serde(default)]
+  #[builder(default)]
+  pub token: String,
+
+  /// The Pushover user (or group) key to send to.
+  #[serde(default)]
+  #[builder(default)]
+  pub user: String,
+
+  /// Optionally send only to a specific device of the user.
+  #[serde(default)]
+  #[builder(default)]
+  pub device: String,
+}
+
 // QUERY
 
 #[typeshare]
@@ -48,6 +48,8 @@ pub struct Env {
   pub komodo_passkey: Option<String>,
   /// Override `passkey` with file
   pub komodo_passkey_file: Option<PathBuf>,
+  /// Override `disable_periphery_passkey_header`
+  pub komodo_disable_periphery_passkey_header: Option<bool>,
   /// Override `first_server`
   pub komodo_first_server: Option<String>,
   /// Override `frontend_path`
@@ -70,6 +72,10 @@ pub struct Env {
   pub komodo_monitoring_interval: Option<Timelength>,
   /// Override `keep_stats_for_days`
   pub komodo_keep_stats_for_days: Option<u64>,
+  /// Override `keep_hourly_stats_for_days`
+  pub komodo_keep_hourly_stats_for_days: Option<u64>,
+  /// Override `keep_daily_stats_for_days`
+  pub komodo_keep_daily_stats_for_days: Option<u64>,
   /// Override `keep_alerts_for_days`
   pub komodo_keep_alerts_for_days: Option<u64>,
   /// Override `webhook_secret`
@@ -78,6 +84,10 @@ pub struct Env {
   pub komodo_webhook_secret_file: Option<PathBuf>,
   /// Override `webhook_base_url`
   pub komodo_webhook_base_url: Option<String>,
+  /// Override `metrics_token`
+  pub komodo_metrics_token: Option<String>,
+  /// Override `metrics_token` with file
+  pub komodo_metrics_token_file: Option<PathBuf>,
 
   /// Override `logging.level`
   pub komodo_logging_level: Option<LogLevel>,
@@ -214,6 +224,10 @@ pub struct Env {
   pub komodo_ssl_key_file: Option<PathBuf>,
   /// Override `ssl_cert_file`
   pub komodo_ssl_cert_file: Option<PathBuf>,
+  /// Override `periphery_client_cert_file`
+  pub komodo_periphery_client_cert_file: Option<PathBuf>,
+  /// Override `periphery_client_key_file`
+  pub komodo_periphery_client_key_file: Option<PathBuf>,
 }
 
 fn default_config_path() -> String {
@@ -258,6 +272,14 @@ pub struct CoreConfig {
   /// Should be some secure hash, maybe 20-40 chars.
   pub passkey: String,
 
+  /// Periphery requests are signed with the passkey.
+  /// By default the passkey is also sent in the auth header,
+  /// for Periphery versions which don't verify signatures.
+  /// Disable once all Periphery are updated,
+  /// so the passkey never crosses the wire.
+  #[serde(default)]
+  pub disable_periphery_passkey_header: bool,
+
   /// Disable user ability to use the UI to update resource configuration.
   #[serde(default)]
   pub ui_write_disabled: bool,
@@ -395,6 +417,16 @@ pub struct CoreConfig {
   #[serde(default)]
   pub github_webhook_app: GithubWebhookAppConfig,
 
+  // ===========
+  // = Metrics =
+  // ===========
+  /// A token which can be used by Prometheus to scrape `/metrics`,
+  /// passed as `Authorization: Bearer <metrics_token>`.
+  /// The endpoint also accepts the api key and secret of an admin user.
+  /// If empty, only api keys can be used.
+  #[serde(default)]
+  pub metrics_token: String,
+
   // ===========
   // = Logging =
   // ===========
@@ -411,6 +443,16 @@ pub struct CoreConfig {
   #[serde(default = "default_prune_days")]
   pub keep_stats_for_days: u64,
 
+  /// Number of days to keep the hourly stats rollups, or 0 to disable pruning.
+  /// Default: 90
+  #[serde(default = "default_hourly_stats_prune_days")]
+  pub keep_hourly_stats_for_days: u64,
+
+  /// Number of days to keep the daily stats rollups, or 0 to disable pruning.
+  /// Default: 730
+  #[serde(default = "default_daily_stats_prune_days")]
+  pub keep_daily_stats_for_days: u64,
+
   /// Number of days to keep alerts, or 0 to disable pruning.
   /// Alerts older than this number of days are deleted on a daily cycle
   /// Default: 14
@@ -484,6 +526,17 @@ pub struct CoreConfig {
   #[serde(default = "default_ssl_cert_file")]
   pub ssl_cert_file: PathBuf,
 
+  /// Path to the client cert presented to Periphery,
+  /// for Periphery to verify Core with mutual TLS.
+  /// Default: none
+  #[serde(default)]
+  pub periphery_client_cert_file: Option<PathBuf>,
+
+  /// Path to the key of the Periphery client cert.
+  /// Default: none
+  #[serde(default)]
+  pub periphery_client_key_file: Option<PathBuf>,
+
   // =========
   // = Other =
   // =========
@@ -539,6 +592,14 @@ fn default_prune_days() -> u64 {
   14
 }
 
+fn default_hourly_stats_prune_days() -> u64 {
+  90
+}
+
+fn default_daily_stats_prune_days() -> u64 {
+  730
+}
+
 fn default_poll_interval() -> Timelength {
   Timelength::FiveMinutes
 }
@@ -563,6 +624,8 @@ impl CoreConfig {
       host: config.host,
       port: config.port,
       passkey: empty_or_redacted(&config.passkey),
+      disable_periphery_passkey_header: config
+        .disable_periphery_passkey_header,
       first_server: config.first_server,
       frontend_path: config.frontend_path,
       jwt_secret: empty_or_redacted(&config.jwt_secret),
@@ -573,6 +636,8 @@ impl CoreConfig {
       resource_poll_interval: config.resource_poll_interval,
       monitoring_interval: config.monitoring_interval,
       keep_stats_for_days: config.keep_stats_for_days,
+      keep_hourly_stats_for_days: config.keep_hourly_stats_for_days,
+      keep_daily_stats_for_days: config.keep_daily_stats_for_days,
       keep_alerts_for_days: config.keep_alerts_for_days,
       logging: config.logging,
       transparent_mode: config.transparent_mode,
@@ -607,6 +672,7 @@ impl CoreConfig {
       },
       webhook_secret: empty_or_redacted(&config.webhook_secret),
       webhook_base_url: config.webhook_base_url,
+      metrics_token: empty_or_redacted(&config.metrics_token),
       github_webhook_app: config.github_webhook_app,
       database: DatabaseConfig {
         uri: empty_or_redacted(&config.database.uri),
@@ -654,6 +720,8 @@ impl CoreConfig {
       ssl_enabled: config.ssl_enabled,
       ssl_key_file: config.ssl_key_file,
       ssl_cert_file: config.ssl_cert_file,
+      periphery_client_cert_file: config.periphery_client_cert_file,
+      periphery_client_key_file: config.periphery_client_key_file,
     }
   }
 }
@@ -12,9 +12,13 @@
 //! the configuration file.
 //!
 
-use std::{collections::HashMap, net::IpAddr, path::PathBuf};
+use std::{
+  collections::HashMap, net::IpAddr, path::PathBuf, str::FromStr,
+};
 
+use anyhow::{anyhow, Context};
 use clap::Parser;
+use ipnet::IpNet;
 use serde::Deserialize;
 
 use crate::entities::{
@@ -122,6 +126,12 @@ pub struct Env {
   pub periphery_stats_polling_rate: Option<Timelength>,
   /// Override `legacy_compose_cli`
   pub periphery_legacy_compose_cli: Option<bool>,
+  /// Override `disable_host_terminal`
+  pub periphery_disable_host_terminal: Option<bool>,
+  /// Override `host_terminal_shell`
+  pub periphery_host_terminal_shell: Option<String>,
+  /// Override `host_terminal_dir`
+  pub periphery_host_terminal_dir: Option<PathBuf>,
 
   // LOGGING
   /// Override `logging.level`
@@ -133,12 +143,22 @@ pub struct Env {
   /// Override `logging.opentelemetry_service_name`
   pub periphery_logging_opentelemetry_service_name: Option<String>,
 
+  /// Override `core_address`
+  pub periphery_core_address: Option<String>,
+  /// Override `enrollment_token`
+  pub periphery_enrollment_token: Option<String>,
   /// Override `allowed_ips`
-  pub periphery_allowed_ips: Option<Vec<IpAddr>>,
+  pub periphery_allowed_ips: Option<Vec<IpNetwork>>,
+  /// Override `trusted_proxies`
+  pub periphery_trusted_proxies: Option<Vec<IpNetwork>>,
+  /// Override `proxy_protocol`
+  pub periphery_proxy_protocol: Option<bool>,
   /// Override `passkeys`
   pub periphery_passkeys: Option<Vec<String>>,
   /// Override `passkeys` from file
   pub periphery_passkeys_file: Option<PathBuf>,
+  /// Override `require_signed_requests`
+  pub periphery_require_signed_requests: Option<bool>,
   /// Override `include_disk_mounts`
   pub periphery_include_disk_mounts: Option<Vec<PathBuf>>,
   /// Override `exclude_disk_mounts`
@@ -150,6 +170,8 @@ pub struct Env {
   pub periphery_ssl_key_file: Option<PathBuf>,
   /// Override `ssl_cert_file`
   pub periphery_ssl_cert_file: Option<PathBuf>,
+  /// Override `ssl_client_ca_file`
+  pub periphery_ssl_client_ca_file: Option<PathBuf>,
 }
 
 /// # Periphery Configuration File
@@ -183,16 +205,64 @@ pub struct PeripheryConfig {
   #[serde(default)]
   pub legacy_compose_cli: bool,
 
+  /// Disable the interactive host terminal,
+  /// which admins can otherwise open through Core.
+  /// Default: false
+  #[serde(default)]
+  pub disable_host_terminal: bool,
+
+  /// The shell to run for host terminal sessions.
+  /// Default: `bash`
+  #[serde(default = "default_host_terminal_shell")]
+  pub host_terminal_shell: String,
+
+  /// The working directory of host terminal sessions.
+  /// Default: `/`
+  #[serde(default = "default_host_terminal_dir")]
+  pub host_terminal_dir: PathBuf,
+
   /// Logging configuration
   #[serde(default)]
   pub logging: LogConfig,
 
-  /// Limits which IPv4 addresses are allowed to call the api.
+  /// The address of Komodo Core, eg. `https://komodo.example.com`.
+  /// If set, periphery dials out to core over a persistent websocket,
+  /// so core doesn't need to reach periphery.
+  /// Must use `https://` or `wss://`, as the enrollment token is sent with it.
+  /// Default: none
+  #[serde(default)]
+  pub core_address: String,
+
+  /// Authenticates the outbound connection to core.
+  /// Generate it for the Server in core with `CreateServerEnrollmentToken`.
+  /// Default: none
+  #[serde(default)]
+  pub enrollment_token: String,
+
+  /// Limits which addresses are allowed to call the api.
+  /// Accepts IPv4 / IPv6 addresses, CIDR networks, eg. `10.0.0.0/16`,
+  /// and hostnames, which are resolved on startup and every minute.
   /// Default: none
   ///
   /// Note: this should be configured to increase security.
   #[serde(default)]
-  pub allowed_ips: Vec<IpAddr>,
+  pub allowed_ips: Vec<IpNetwork>,
+
+  /// Requests from these addresses / networks / hostnames are taken to be
+  /// from the address they forward in `X-Forwarded-For`,
+  /// which is then checked against `allowed_ips`.
+  /// Default: none
+  #[serde(default)]
+  pub trusted_proxies: Vec<IpNetwork>,
+
+  /// Expect a PROXY protocol (v1 or v2) header on every connection,
+  /// and check the client address it gives against `allowed_ips`.
+  /// The address is only used when the connection is from one of the `trusted_proxies`.
+  /// Enable when behind a load balancer sending the PROXY protocol,
+  /// as connections without the header are rejected.
+  /// Default: false
+  #[serde(default)]
+  pub proxy_protocol: bool,
 
   /// Limits the accepted passkeys.
   /// Default: none
@@ -201,6 +271,13 @@ pub struct PeripheryConfig {
   #[serde(default)]
   pub passkeys: Vec<String>,
 
+  /// Only accept requests signed with one of the passkeys,
+  /// rejecting requests which send the passkey itself.
+  /// Enable once Core has `disable_periphery_passkey_header`.
+  /// Default: false
+  #[serde(default)]
+  pub require_signed_requests: bool,
+
   /// If non-empty, only includes specific mount paths in the disk report.
   #[serde(default)]
   pub include_disk_mounts: Vec<PathBuf>,
@@ -238,6 +315,14 @@ pub struct PeripheryConfig {
   /// Default: `/etc/komodo/ssl/periphery/cert.pem`.
   #[serde(default = "default_ssl_cert_file")]
   pub ssl_cert_file: PathBuf,
+
+  /// Path to the CA cert used to verify the Core client certificate.
+  /// If set, connections without a valid client certificate are refused.
+  /// When Core uses a self-signed client cert, this is the cert itself.
+  /// Requires `ssl_enabled`.
+  /// Default: none
+  #[serde(default)]
+  pub ssl_client_ca_file: Option<PathBuf>,
 }
 
 fn default_periphery_port() -> u16 {
@@ -256,6 +341,14 @@ fn default_stats_polling_rate() -> Timelength {
   Timelength::FiveSeconds
 }
 
+fn default_host_terminal_shell() -> String {
+  String::from("bash")
+}
+
+fn default_host_terminal_dir() -> PathBuf {
+  "/".parse().unwrap()
+}
+
 fn default_ssl_enabled() -> bool {
   false
 }
@@ -276,9 +369,17 @@ impl Default for PeripheryConfig {
       stack_dir: default_stack_dir(),
       stats_polling_rate: default_stats_polling_rate(),
       legacy_compose_cli: Default::default(),
+      disable_host_terminal: Default::default(),
+      host_terminal_shell: default_host_terminal_shell(),
+      host_terminal_dir: default_host_terminal_dir(),
       logging: Default::default(),
+      core_address: Default::default(),
+      enrollment_token: Default::default(),
       allowed_ips: Default::default(),
+      trusted_proxies: Default::default(),
+      proxy_protocol: Default::default(),
       passkeys: Default::default(),
+      require_signed_requests: Default::default(),
       include_disk_mounts: Default::default(),
       exclude_disk_mounts: Default::default(),
       secrets: Default::default(),
@@ -287,6 +388,7 @@ impl Default for PeripheryConfig {
       ssl_enabled: default_ssl_enabled(),
       ssl_key_file: default_ssl_key_file(),
       ssl_cert_file: default_ssl_cert_file(),
+      ssl_client_ca_file: Default::default(),
     }
   }
 }
@@ -299,13 +401,21 @@ impl PeripheryConfig {
       stack_dir: self.stack_dir.clone(),
       stats_polling_rate: self.stats_polling_rate,
       legacy_compose_cli: self.legacy_compose_cli,
+      disable_host_terminal: self.disable_host_terminal,
+      host_terminal_shell: self.host_terminal_shell.clone(),
+      host_terminal_dir: self.host_terminal_dir.clone(),
       logging: self.logging.clone(),
+      core_address: self.core_address.clone(),
+      enrollment_token: empty_or_redacted(&self.enrollment_token),
       allowed_ips: self.allowed_ips.clone(),
+      trusted_proxies: self.trusted_proxies.clone(),
+      proxy_protocol: self.proxy_protocol,
       passkeys: self
         .passkeys
         .iter()
         .map(|passkey| empty_or_redacted(passkey))
         .collect(),
+      require_signed_requests: self.require_signed_requests,
       include_disk_mounts: self.include_disk_mounts.clone(),
       exclude_disk_mounts: self.exclude_disk_mounts.clone(),
       secrets: self
@@ -350,6 +460,62 @@ impl PeripheryConfig {
       ssl_enabled: self.ssl_enabled,
       ssl_key_file: self.ssl_key_file.clone(),
       ssl_cert_file: self.ssl_cert_file.clone(),
+      ssl_client_ca_file: self.ssl_client_ca_file.clone(),
+    }
+  }
+}
+
+/// An IPv4 / IPv6 address, a CIDR network like `10.0.0.0/16`,
+/// or a hostname like `proxy.internal`.
+/// A single address matches only itself, and a hostname
+/// matches the addresses it resolves to.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum IpNetwork {
+  Network(IpNet),
+  Hostname(String),
+}
+
+impl FromStr for IpNetwork {
+  type Err = anyhow::Error;
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let s = s.trim();
+    if s.contains('/') {
+      let net = s
+        .parse::<IpNet>()
+        .with_context(|| format!("invalid CIDR network: {s}"))?;
+      return Ok(IpNetwork::Network(net));
+    }
+    if let Ok(ip) = s.parse::<IpAddr>() {
+      return Ok(IpNetwork::Network(ip.into()));
+    }
+    if !is_valid_hostname(s) {
+      return Err(anyhow!(
+        "invalid ip address, CIDR network or hostname: {s}"
+      ));
     }
+    Ok(IpNetwork::Hostname(s.to_ascii_lowercase()))
+  }
+}
+
+fn is_valid_hostname(hostname: &str) -> bool {
+  let hostname = hostname.strip_suffix('.').unwrap_or(hostname);
+  !hostname.is_empty()
+    && hostname.len() <= 253
+    // All numeric names would be mistyped addresses.
+    && !hostname.chars().all(|c| c.is_ascii_digit() || c == '.')
+    && hostname.split('.').all(|label| {
+      !label.is_empty()
+        && label.len() <= 63
+        && !label.starts_with('-')
+        && !label.ends_with('-')
+        && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    })
+}
+
+impl TryFrom<String> for IpNetwork {
+  type Error = anyhow::Error;
+  fn try_from(value: String) -> Result<Self, Self::Error> {
+    value.parse()
   }
 }
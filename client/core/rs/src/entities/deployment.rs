@@ -1,4 +1,4 @@
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 use bson::{doc, Document};
 use derive_builder::Builder;
 use derive_default_builder::DefaultBuilder;
@@ -108,6 +108,18 @@ pub struct DeploymentConfig {
   #[partial_default(default_send_alerts())]
   pub send_alerts: bool,
 
+  /// Whether to send ContainerUnhealthy alerts for this deployment,
+  /// when a container with a healthcheck reports unhealthy.
+  #[serde(default)]
+  #[builder(default)]
+  pub send_unhealthy_alerts: bool,
+
+  /// Whether to send ContainerRestartLoop alerts for this deployment,
+  /// when a container is repeatedly restarted by docker.
+  #[serde(default)]
+  #[builder(default)]
+  pub send_restart_loop_alerts: bool,
+
   /// Configure quick links that are displayed in the resource header
   #[serde(default)]
   #[builder(default)]
@@ -125,6 +137,45 @@ pub struct DeploymentConfig {
   #[builder(default)]
   pub restart: RestartMode,
 
+  /// The max restarts with the `on-failure` restart mode.
+  #[serde(default = "default_restart_max_retries")]
+  #[builder(default = "default_restart_max_retries()")]
+  #[partial_default(default_restart_max_retries())]
+  pub restart_max_retries: i32,
+
+  /// The number of CPUs the container can use, passed to `--cpus`. Eg. 1.5.
+  /// 0 is unlimited.
+  #[serde(default)]
+  #[builder(default)]
+  pub cpus: f64,
+
+  /// The memory limit, passed to `--memory`. Eg. 512m, 2g.
+  /// Empty is unlimited.
+  #[serde(default)]
+  #[builder(default)]
+  pub memory: String,
+
+  /// The soft memory limit, passed to `--memory-reservation`.
+  /// Must be below the memory limit. Empty is unset.
+  #[serde(default)]
+  #[builder(default)]
+  pub memory_reservation: String,
+
+  /// The max number of processes in the container, passed to `--pids-limit`.
+  /// 0 is unlimited.
+  #[serde(default)]
+  #[builder(default)]
+  pub pids_limit: i32,
+
+  /// Ulimits passed to `--ulimit`, as `name=soft[:hard]`. Eg. nofile=1024:2048.
+  #[serde(default, deserialize_with = "string_list_deserializer")]
+  #[partial_attr(serde(
+    default,
+    deserialize_with = "option_string_list_deserializer"
+  ))]
+  #[builder(default)]
+  pub ulimits: Vec<String>,
+
   /// This is interpolated at the end of the `docker run` command,
   /// which means they are either passed to the containers inner process,
   /// or replaces the container command, depending on use of ENTRYPOINT or CMD in dockerfile.
@@ -133,6 +184,65 @@ pub struct DeploymentConfig {
   #[builder(default)]
   pub command: String,
 
+  /// The healthcheck command run inside the container, passed to `--health-cmd`.
+  /// Empty uses the image HEALTHCHECK, if it has one.
+  #[serde(default)]
+  #[builder(default)]
+  pub healthcheck_command: String,
+
+  /// Seconds between healthchecks. 0 uses the docker default (30s).
+  #[serde(default)]
+  #[builder(default)]
+  pub healthcheck_interval: i32,
+
+  /// Seconds before a healthcheck is considered failed.
+  /// 0 uses the docker default (30s).
+  #[serde(default)]
+  #[builder(default)]
+  pub healthcheck_timeout: i32,
+
+  /// Consecutive failed healthchecks before the container is unhealthy.
+  /// 0 uses the docker default (3).
+  #[serde(default)]
+  #[builder(default)]
+  pub healthcheck_retries: i32,
+
+  /// Seconds to give the container to start,
+  /// before failed healthchecks count towards the retries.
+  /// 0 uses the docker default (0s).
+  #[serde(default)]
+  #[builder(default)]
+  pub healthcheck_start_period: i32,
+
+  /// After `Deploy`, the seconds to wait for the container to become healthy.
+  /// Containers without a healthcheck must instead stay running.
+  /// Containers with restart `no` / `on-failure` may also exit with code 0.
+  /// The deploy fails if the container is unhealthy, exits, or restarts.
+  /// Default: 0, which waits 60s when `healthcheck_command` is set,
+  /// and otherwise skips the verification.
+  #[serde(default)]
+  #[builder(default)]
+  pub healthy_timeout: i32,
+
+  /// Whether to redeploy the last successful deploy
+  /// if the deploy fails, such as failing the verification.
+  /// Not done when a BlueGreen deploy fails with the old container still running.
+  #[serde(default)]
+  #[builder(default)]
+  pub auto_rollback: bool,
+
+  /// How `Deploy` replaces the running container.
+  #[serde(default)]
+  #[builder(default)]
+  pub deploy_strategy: DeployStrategy,
+
+  /// Run on the host during a BlueGreen deploy, after the new container
+  /// is healthy and before the old container is removed.
+  /// For example, to point a reverse proxy at the new container.
+  #[serde(default)]
+  #[builder(default)]
+  pub cutover_command: String,
+
   /// The default termination signal to use to stop the deployment. Defaults to SigTerm (default docker signal).
   #[serde(default)]
   #[builder(default)]
@@ -164,6 +274,14 @@ pub struct DeploymentConfig {
   #[builder(default)]
   pub term_signal_labels: String,
 
+  /// The number of identical containers to run on the server.
+  /// With more than 1, the containers are named `{name}-1` to `{name}-{replicas}`,
+  /// and each replica offsets the published host ports by its index - 1.
+  #[serde(default = "default_replicas")]
+  #[builder(default = "default_replicas()")]
+  #[partial_default(default_replicas())]
+  pub replicas: i32,
+
   /// The container port mapping.
   /// Irrelevant if container network is `host`.
   /// Maps ports on host to ports on container.
@@ -218,6 +336,14 @@ fn default_termination_timeout() -> i32 {
   10
 }
 
+fn default_replicas() -> i32 {
+  1
+}
+
+fn default_restart_max_retries() -> i32 {
+  10
+}
+
 fn default_network() -> String {
   String::from("host")
 }
@@ -227,6 +353,8 @@ impl Default for DeploymentConfig {
     Self {
       server_id: Default::default(),
       send_alerts: default_send_alerts(),
+      send_unhealthy_alerts: Default::default(),
+      send_restart_loop_alerts: Default::default(),
       links: Default::default(),
       image: Default::default(),
       image_registry_account: Default::default(),
@@ -237,13 +365,29 @@ impl Default for DeploymentConfig {
       term_signal_labels: Default::default(),
       termination_signal: Default::default(),
       termination_timeout: default_termination_timeout(),
+      replicas: default_replicas(),
       ports: Default::default(),
       volumes: Default::default(),
       environment: Default::default(),
       labels: Default::default(),
       network: default_network(),
       restart: Default::default(),
+      restart_max_retries: default_restart_max_retries(),
+      cpus: Default::default(),
+      memory: Default::default(),
+      memory_reservation: Default::default(),
+      pids_limit: Default::default(),
+      ulimits: Default::default(),
       command: Default::default(),
+      healthcheck_command: Default::default(),
+      healthcheck_interval: Default::default(),
+      healthcheck_timeout: Default::default(),
+      healthcheck_retries: Default::default(),
+      healthcheck_start_period: Default::default(),
+      healthy_timeout: Default::default(),
+      auto_rollback: Default::default(),
+      deploy_strategy: Default::default(),
+      cutover_command: Default::default(),
       extra_args: Default::default(),
     }
   }
@@ -315,6 +459,216 @@ pub fn conversions_from_str(
   })
 }
 
+/// Labels the replica containers with the deployment id.
+pub const REPLICA_DEPLOYMENT_LABEL: &str = "komodo.deployment.id";
+/// Labels the replica containers with their index, starting at 1.
+pub const REPLICA_INDEX_LABEL: &str = "komodo.replica";
+
+impl Deployment {
+  /// The names of the replica containers.
+  /// With one replica, the container is named after the deployment.
+  pub fn container_names(&self) -> Vec<String> {
+    container_names(&self.name, self.config.replicas)
+  }
+
+  /// The container used for logs, stats, and terminals,
+  /// which is the first replica.
+  pub fn container_name(&self) -> String {
+    if self.config.replicas <= 1 {
+      self.name.clone()
+    } else {
+      format!("{}-1", self.name)
+    }
+  }
+}
+
+/// The container names of a Deployment with the name and replica count.
+pub fn container_names(name: &str, replicas: i32) -> Vec<String> {
+  if replicas <= 1 {
+    vec![name.to_string()]
+  } else {
+    (1..=replicas)
+      .map(|index| format!("{name}-{index}"))
+      .collect()
+  }
+}
+
+/// Offsets the published host ports, so replicas don't conflict.
+/// Port ranges are offset by their length.
+pub fn offset_host_ports(
+  ports: &str,
+  offset: u16,
+) -> anyhow::Result<String> {
+  conversions_from_str(ports)?
+    .into_iter()
+    .map(|Conversion { local, container }| {
+      // [ip:]host_port:container_port
+      let mapping = format!("{local}:{container}");
+      let (rest, container) =
+        mapping.rsplit_once(':').with_context(|| {
+          format!("invalid port mapping '{mapping}'")
+        })?;
+      let (ip, host) = match rest.rsplit_once(':') {
+        Some((ip, host)) => (Some(ip), host),
+        None => (None, rest),
+      };
+      let host =
+        offset_port_range(host, offset).with_context(|| {
+          format!("invalid host port in '{mapping}'")
+        })?;
+      Ok(match ip {
+        Some(ip) => format!("{ip}:{host}:{container}"),
+        None => format!("{host}:{container}"),
+      })
+    })
+    .collect::<anyhow::Result<Vec<_>>>()
+    .map(|mappings| mappings.join("\n"))
+}
+
+/// The port mappings which publish a fixed host port,
+/// eg. `8080:80`. Mappings to an ephemeral host port,
+/// eg. `127.0.0.1::80`, are not included.
+pub fn fixed_host_port_mappings(
+  ports: &str,
+) -> anyhow::Result<Vec<String>> {
+  let mut fixed = Vec::new();
+  for Conversion { local, container } in conversions_from_str(ports)?
+  {
+    // [ip:]host_port:container_port
+    let mapping = format!("{local}:{container}");
+    let (rest, _) = mapping
+      .rsplit_once(':')
+      .with_context(|| format!("invalid port mapping '{mapping}'"))?;
+    let host = rest.rsplit_once(':').map_or(rest, |(_, host)| host);
+    if !host.trim().is_empty() {
+      fixed.push(mapping);
+    }
+  }
+  Ok(fixed)
+}
+
+fn offset_port_range(
+  ports: &str,
+  offset: u16,
+) -> anyhow::Result<String> {
+  let parse = |port: &str| {
+    port.trim().parse::<u32>().context("port is not a number")
+  };
+  let (start, end) = match ports.split_once('-') {
+    Some((start, end)) => (parse(start)?, parse(end)?),
+    None => (parse(ports)?, parse(ports)?),
+  };
+  if end < start {
+    return Err(anyhow!("port range end is before start"));
+  }
+  let shift = (end - start + 1) * offset as u32;
+  let (start, end) = (start + shift, end + shift);
+  if end > u16::MAX as u32 {
+    return Err(anyhow!("offset port {end} is out of range"));
+  }
+  if start == end {
+    Ok(start.to_string())
+  } else {
+    Ok(format!("{start}-{end}"))
+  }
+}
+
+/// Checks a memory size accepted by docker, eg. 512m, 1.5g, 1gb.
+/// Follows docker's `RAMInBytes`: a number, optionally decimal,
+/// with an optional b / k / m / g / t / p unit and trailing b,
+/// case insensitive. Empty is accepted.
+pub fn validate_memory_size(input: &str) -> anyhow::Result<()> {
+  if input.is_empty() {
+    return Ok(());
+  }
+  let lower = input.to_ascii_lowercase();
+  let without_b = lower.strip_suffix('b').unwrap_or(&lower);
+  const UNITS: [char; 5] = ['k', 'm', 'g', 't', 'p'];
+  let number = match without_b.strip_suffix('i') {
+    // The `i` is only accepted after a unit, eg. 512mib.
+    Some(with_unit) => {
+      with_unit.strip_suffix(UNITS).unwrap_or(without_b)
+    }
+    None => without_b.strip_suffix(UNITS).unwrap_or(without_b),
+  };
+  let is_digits =
+    |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+  let valid = match number.split_once('.') {
+    Some((whole, fraction)) => {
+      is_digits(whole) && is_digits(fraction)
+    }
+    None => is_digits(number),
+  };
+  if !valid {
+    return Err(anyhow!(
+      "invalid memory size '{input}', expected eg. 512m"
+    ));
+  }
+  Ok(())
+}
+
+/// A ulimit given as `name=soft[:hard]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ulimit {
+  pub name: String,
+  pub soft: i64,
+  /// Same as soft if not given.
+  pub hard: i64,
+}
+
+/// The ulimit names docker accepts.
+pub const ULIMIT_NAMES: [&str; 15] = [
+  "core",
+  "cpu",
+  "data",
+  "fsize",
+  "locks",
+  "memlock",
+  "msgqueue",
+  "nice",
+  "nofile",
+  "nproc",
+  "rss",
+  "rtprio",
+  "rttime",
+  "sigpending",
+  "stack",
+];
+
+pub fn ulimit_from_str(input: &str) -> anyhow::Result<Ulimit> {
+  let (name, limits) =
+    input.trim().split_once('=').with_context(|| {
+      format!(
+        "invalid ulimit '{input}', expected eg. nofile=1024:2048"
+      )
+    })?;
+  let (soft, hard) =
+    limits.split_once(':').unwrap_or((limits, limits));
+  let parse = |limit: &str| {
+    limit.trim().parse::<i64>().with_context(|| {
+      format!("invalid limit '{limit}' in ulimit '{input}'")
+    })
+  };
+  let ulimit = Ulimit {
+    name: name.trim().to_string(),
+    soft: parse(soft)?,
+    hard: parse(hard)?,
+  };
+  if !ULIMIT_NAMES.contains(&ulimit.name.as_str()) {
+    return Err(anyhow!(
+      "ulimit '{input}' has unknown name '{}', expected one of {}",
+      ulimit.name,
+      ULIMIT_NAMES.join(", ")
+    ));
+  }
+  if ulimit.soft > ulimit.hard {
+    return Err(anyhow!(
+      "ulimit '{input}' soft limit is above the hard limit"
+    ));
+  }
+  Ok(ulimit)
+}
+
 /// Variants de/serialized from/to snake_case.
 ///
 /// Eg.
@@ -397,6 +751,35 @@ pub enum RestartMode {
   UnlessStopped,
 }
 
+/// How `Deploy` replaces the running container.
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  PartialEq,
+  Hash,
+  Eq,
+  Clone,
+  Copy,
+  Default,
+  Display,
+  EnumString,
+)]
+pub enum DeployStrategy {
+  /// Remove the old container, then start the new one.
+  #[default]
+  Recreate,
+  /// Start the new container under a temporary name,
+  /// and only replace the old container once the new one is healthy.
+  /// The old container is stopped and renamed aside, the new container
+  /// takes its name, then the old container is removed.
+  ///
+  /// The containers run side by side, so they can't publish fixed
+  /// host ports, eg. `8080:80`, or use the `host` network.
+  BlueGreen,
+}
+
 #[typeshare]
 #[derive(
   Serialize,
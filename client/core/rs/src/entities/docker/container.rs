@@ -33,6 +33,15 @@ pub struct ContainerListItem {
   pub state: ContainerStateStatusEnum,
   /// Additional human-readable status of this container (e.g. `Exit 0`)
   pub status: Option<String>,
+  /// The healthcheck status, if the container has a healthcheck.
+  /// Parsed from the human-readable status.
+  #[serde(default)]
+  pub health: Option<HealthStatusEnum>,
+  /// The number of times the container has been restarted by docker.
+  /// Only included if the container recently (re)started,
+  /// as getting it requires inspecting the container.
+  #[serde(default)]
+  pub restart_count: Option<I64>,
   /// The network mode
   pub network_mode: Option<String>,
   /// The network names attached to container
@@ -26,6 +26,8 @@ use crate::{
 pub mod action;
 /// Subtypes of [Alert][alert::Alert].
 pub mod alert;
+/// Subtypes of [AlertSilence][alert_silence::AlertSilence].
+pub mod alert_silence;
 /// Subtypes of [Alerter][alerter::Alerter].
 pub mod alerter;
 /// Subtypes of [ApiKey][api_key::ApiKey].
@@ -647,6 +649,9 @@ pub enum Operation {
   UpdateServer,
   DeleteServer,
   RenameServer,
+  UnpinServerCertificate,
+  CreateServerEnrollmentToken,
+  DeleteServerEnrollmentToken,
   StartContainer,
   RestartContainer,
   PauseContainer,
@@ -669,6 +674,7 @@ pub enum Operation {
   PruneDockerBuilders,
   PruneBuildx,
   PruneSystem,
+  HostTerminal,
 
   // stack
   CreateStack,
@@ -695,6 +701,7 @@ pub enum Operation {
   UnpauseStackService,
   StopStackService,
   DestroyStackService,
+  ExecStackService,
 
   // deployment
   CreateDeployment,
@@ -703,12 +710,14 @@ pub enum Operation {
   DeleteDeployment,
   Deploy,
   PullDeployment,
+  RollbackDeployment,
   StartDeployment,
   RestartDeployment,
   PauseDeployment,
   UnpauseDeployment,
   StopDeployment,
   DestroyDeployment,
+  ExecDeployment,
 
   // build
   CreateBuild,
@@ -770,6 +779,14 @@ pub enum Operation {
   CommitSync,
   RunSync,
 
+  // alert
+  AcknowledgeAlert,
+
+  // alert silence
+  CreateAlertSilence,
+  UpdateAlertSilence,
+  DeleteAlertSilence,
+
   // variable
   CreateVariable,
   UpdateVariableValue,
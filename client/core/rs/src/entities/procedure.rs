@@ -2,7 +2,7 @@ use bson::Document;
 use derive_builder::Builder;
 use derive_default_builder::DefaultBuilder;
 use partial_derive2::Partial;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use strum::Display;
 use typeshare::typeshare;
 
@@ -23,6 +23,14 @@ pub struct ProcedureListItemInfo {
   pub stages: I64,
   /// Reflect whether last run successful / currently running.
   pub state: ProcedureState,
+  /// If the procedure has a schedule, this is the timestamp (ms)
+  /// of the next scheduled run.
+  pub next_scheduled_run: Option<I64>,
+  /// The timestamp (ms) of the last run triggered by the schedule.
+  pub last_scheduled_run: Option<I64>,
+  /// If there is an error parsing the schedule expression / timezone,
+  /// it will be given here.
+  pub schedule_error: Option<String>,
 }
 
 #[typeshare]
@@ -44,7 +52,35 @@ pub enum ProcedureState {
 /// Procedures run a series of stages sequentially, where
 /// each stage runs executions in parallel.
 #[typeshare]
-pub type Procedure = Resource<ProcedureConfig, ()>;
+pub type Procedure = Resource<ProcedureConfig, ProcedureInfo>;
+
+#[typeshare]
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct ProcedureInfo {
+  /// The timestamp (ms) of the last run triggered by the schedule.
+  pub last_scheduled_run: Option<I64>,
+}
+
+impl<'de> Deserialize<'de> for ProcedureInfo {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    #[derive(Deserialize)]
+    struct ProcedureInfoInner {
+      #[serde(default)]
+      last_scheduled_run: Option<I64>,
+    }
+    // Procedures created before ProcedureInfo existed store null info.
+    let info =
+      Option::<ProcedureInfoInner>::deserialize(deserializer)?
+        .map(|info| ProcedureInfo {
+          last_scheduled_run: info.last_scheduled_run,
+        })
+        .unwrap_or_default();
+    Ok(info)
+  }
+}
 
 #[typeshare(serialized_as = "Partial<ProcedureConfig>")]
 pub type _PartialProcedureConfig = PartialProcedureConfig;
@@ -72,6 +108,30 @@ pub struct ProcedureConfig {
   #[serde(default)]
   #[builder(default)]
   pub webhook_secret: String,
+
+  /// Optionally provide a CRON expression to run the procedure on a schedule.
+  /// Uses the format (second is optional):
+  /// `second minute hour day-of-month month day-of-week`, eg:
+  /// ```text
+  /// 0 0 4 * * *
+  /// ```
+  /// If empty, the procedure is not scheduled.
+  #[serde(default)]
+  #[builder(default)]
+  pub schedule: String,
+
+  /// Whether the schedule is enabled, if one is provided.
+  /// Can be used to temporarily disable the schedule.
+  #[serde(default = "default_schedule_enabled")]
+  #[builder(default = "default_schedule_enabled()")]
+  #[partial_default(default_schedule_enabled())]
+  pub schedule_enabled: bool,
+
+  /// Optionally provide a timezone (TZ Identifier, eg `America/New_York`)
+  /// to evaluate the schedule in. If empty, uses the Core local timezone.
+  #[serde(default)]
+  #[builder(default)]
+  pub schedule_timezone: String,
 }
 
 impl ProcedureConfig {
@@ -84,12 +144,19 @@ fn default_webhook_enabled() -> bool {
   true
 }
 
+fn default_schedule_enabled() -> bool {
+  true
+}
+
 impl Default for ProcedureConfig {
   fn default() -> Self {
     Self {
       stages: Default::default(),
       webhook_enabled: default_webhook_enabled(),
       webhook_secret: Default::default(),
+      schedule: Default::default(),
+      schedule_enabled: default_schedule_enabled(),
+      schedule_timezone: Default::default(),
     }
   }
 }
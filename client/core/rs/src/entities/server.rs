@@ -15,7 +15,7 @@ use super::{
 };
 
 #[typeshare]
-pub type Server = Resource<ServerConfig, ()>;
+pub type Server = Resource<ServerConfig, ServerInfo>;
 
 #[typeshare]
 pub type ServerListItem = ResourceListItem<ServerListItemInfo>;
@@ -37,6 +37,12 @@ pub struct ServerListItemInfo {
   pub send_mem_alerts: bool,
   /// Whether server is configured to send disk alerts.
   pub send_disk_alerts: bool,
+  /// Whether server is configured to send network alerts.
+  #[serde(default)]
+  pub send_network_alerts: bool,
+  /// Whether server is configured to send load alerts.
+  #[serde(default)]
+  pub send_load_alerts: bool,
 }
 
 #[typeshare(serialized_as = "Partial<ServerConfig>")]
@@ -86,6 +92,14 @@ pub struct ServerConfig {
   #[partial_default(default_stats_monitoring())]
   pub stats_monitoring: bool,
 
+  /// Whether to record historical stats for the Deployment / Stack containers
+  /// on the server. Runs `docker stats --no-stream` every monitoring interval,
+  /// so is opt in. Also requires `stats_monitoring`.
+  /// default: false
+  #[serde(default)]
+  #[builder(default)]
+  pub container_stats_monitoring: bool,
+
   /// Whether to trigger 'docker image prune -a -f' every 24 hours.
   /// default: true
   #[serde(default = "default_auto_prune")]
@@ -126,6 +140,18 @@ pub struct ServerConfig {
   #[partial_default(default_send_alerts())]
   pub send_disk_alerts: bool,
 
+  /// Whether to send alerts about the servers NETWORK status
+  #[serde(default = "default_send_alerts")]
+  #[builder(default = "default_send_alerts()")]
+  #[partial_default(default_send_alerts())]
+  pub send_network_alerts: bool,
+
+  /// Whether to send alerts about the servers LOAD status
+  #[serde(default = "default_send_alerts")]
+  #[builder(default = "default_send_alerts()")]
+  #[partial_default(default_send_alerts())]
+  pub send_load_alerts: bool,
+
   /// The percentage threshhold which triggers WARNING state for CPU.
   #[serde(default = "default_cpu_warning")]
   #[builder(default = "default_cpu_warning()")]
@@ -161,6 +187,30 @@ pub struct ServerConfig {
   #[builder(default = "default_disk_critical()")]
   #[partial_default(default_disk_critical())]
   pub disk_critical: f64,
+
+  /// The rate in Mbps, received or transmitted on any interface,
+  /// which triggers WARNING state for NETWORK. 0 to disable.
+  #[serde(default)]
+  #[builder(default)]
+  pub network_warning: f64,
+
+  /// The rate in Mbps, received or transmitted on any interface,
+  /// which triggers CRITICAL state for NETWORK. 0 to disable.
+  #[serde(default)]
+  #[builder(default)]
+  pub network_critical: f64,
+
+  /// The 5 minute load average which triggers WARNING state for LOAD.
+  /// 0 to disable.
+  #[serde(default)]
+  #[builder(default)]
+  pub load_warning: f64,
+
+  /// The 5 minute load average which triggers CRITICAL state for LOAD.
+  /// 0 to disable.
+  #[serde(default)]
+  #[builder(default)]
+  pub load_critical: f64,
 }
 
 impl ServerConfig {
@@ -220,12 +270,15 @@ impl Default for ServerConfig {
       enabled: default_enabled(),
       ignore_mounts: Default::default(),
       stats_monitoring: default_stats_monitoring(),
+      container_stats_monitoring: Default::default(),
       auto_prune: default_auto_prune(),
       links: Default::default(),
       send_unreachable_alerts: default_send_alerts(),
       send_cpu_alerts: default_send_alerts(),
       send_mem_alerts: default_send_alerts(),
       send_disk_alerts: default_send_alerts(),
+      send_network_alerts: default_send_alerts(),
+      send_load_alerts: default_send_alerts(),
       region: Default::default(),
       cpu_warning: default_cpu_warning(),
       cpu_critical: default_cpu_critical(),
@@ -233,10 +286,33 @@ impl Default for ServerConfig {
       mem_critical: default_mem_critical(),
       disk_warning: default_disk_warning(),
       disk_critical: default_disk_critical(),
+      network_warning: Default::default(),
+      network_critical: Default::default(),
+      load_warning: Default::default(),
+      load_critical: Default::default(),
     }
   }
 }
 
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ServerInfo {
+  /// The SHA-256 fingerprint of the Periphery https certificate,
+  /// pinned on first contact. Connections presenting a different
+  /// certificate are refused until it is unpinned.
+  #[serde(default)]
+  pub certificate_fingerprint: String,
+  /// The SHA-256 hash (hex) of the enrollment token, if one is set.
+  /// Periphery dials out to core over a persistent websocket,
+  /// authenticating with the token, instead of core calling the `address`.
+  /// Use for servers behind NAT or strict firewalls.
+  ///
+  /// Set with [CreateServerEnrollmentToken][crate::api::write::CreateServerEnrollmentToken].
+  /// The token itself is never stored, and the hash is redacted when read.
+  #[serde(default)]
+  pub enrollment_token_hash: String,
+}
+
 /// The health of a part of the server.
 #[typeshare]
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
@@ -253,6 +329,10 @@ pub struct ServerHealth {
   pub cpu: ServerHealthState,
   pub mem: ServerHealthState,
   pub disks: HashMap<PathBuf, ServerHealthState>,
+  #[serde(default)]
+  pub network: ServerHealthState,
+  #[serde(default)]
+  pub load: ServerHealthState,
 }
 
 /// Current pending actions on the server.
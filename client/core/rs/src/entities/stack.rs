@@ -367,6 +367,18 @@ pub struct StackConfig {
   #[partial_default(default_send_alerts())]
   pub send_alerts: bool,
 
+  /// Whether to send ContainerUnhealthy alerts for this stack,
+  /// when a container with a healthcheck reports unhealthy.
+  #[serde(default)]
+  #[builder(default)]
+  pub send_unhealthy_alerts: bool,
+
+  /// Whether to send ContainerRestartLoop alerts for this stack,
+  /// when a container is repeatedly restarted by docker.
+  #[serde(default)]
+  #[builder(default)]
+  pub send_restart_loop_alerts: bool,
+
   /// Used with `registry_account` to login to a registry before docker compose up.
   #[serde(default)]
   #[builder(default)]
@@ -508,6 +520,8 @@ impl Default for StackConfig {
       webhook_secret: Default::default(),
       webhook_force_deploy: Default::default(),
       send_alerts: default_send_alerts(),
+      send_unhealthy_alerts: Default::default(),
+      send_restart_loop_alerts: Default::default(),
       links: Default::default(),
     }
   }
@@ -1,9 +1,10 @@
 use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
+use strum::Display;
 use typeshare::typeshare;
 
-use crate::entities::{Timelength, I64};
+use crate::entities::{ResourceTarget, Timelength, I64};
 
 /// System information of a server
 #[typeshare]
@@ -51,6 +52,131 @@ pub struct SystemStatsRecord {
   pub disk_total_gb: f64,
   /// Breakdown of individual disks, ie their usages, sizes, and mount points
   pub disks: Vec<SingleDiskUsage>,
+  /// Network bytes received per second, across all interfaces
+  #[serde(default)]
+  pub net_rx_bytes_per_sec: f64,
+  /// Network bytes transmitted per second, across all interfaces
+  #[serde(default)]
+  pub net_tx_bytes_per_sec: f64,
+  /// Breakdown of individual network interfaces
+  #[serde(default)]
+  pub networks: Vec<SingleNetworkInterfaceUsage>,
+  /// System load average
+  #[serde(default)]
+  pub load_average: LoadAverage,
+}
+
+/// The resolution of historical server stats.
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  Default,
+  Display,
+)]
+pub enum StatsResolution {
+  /// The records taken on every monitoring interval.
+  #[default]
+  Raw,
+  /// Hourly rollups of the raw records.
+  Hour,
+  /// Daily rollups of the raw records.
+  Day,
+}
+
+/// The min / avg / max of a stat over a rollup period.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default)]
+pub struct StatsAggregate {
+  pub min: f64,
+  pub avg: f64,
+  pub max: f64,
+}
+
+/// Downsampled server stats stored on the database.
+/// Aggregates the [SystemStatsRecord]s over an hour or a day.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(
+  feature = "mongo",
+  derive(mongo_indexed::derive::MongoIndexed)
+)]
+#[cfg_attr(feature = "mongo", collection_name(StatsRollups))]
+#[cfg_attr(feature = "mongo", unique_doc_index({ "sid": 1, "resolution": 1, "ts": 1 }))]
+pub struct SystemStatsRollup {
+  /// Unix timestamp in milliseconds at the start of the period
+  #[cfg_attr(feature = "mongo", index)]
+  pub ts: I64,
+  /// Server id
+  #[cfg_attr(feature = "mongo", index)]
+  pub sid: String,
+  /// The period covered, either `Hour` or `Day`.
+  pub resolution: StatsResolution,
+  /// The number of raw records aggregated
+  pub count: I64,
+  /// Cpu usage percentage
+  pub cpu_perc: StatsAggregate,
+  /// Memory used in GB
+  pub mem_used_gb: StatsAggregate,
+  /// Total memory in GB
+  pub mem_total_gb: f64,
+  /// Disk used in GB
+  pub disk_used_gb: StatsAggregate,
+  /// Total disk size in GB
+  pub disk_total_gb: f64,
+  /// Network bytes received per second, across all interfaces
+  pub net_rx_bytes_per_sec: StatsAggregate,
+  /// Network bytes transmitted per second, across all interfaces
+  pub net_tx_bytes_per_sec: StatsAggregate,
+  /// The 5 minute load average
+  pub load_average: StatsAggregate,
+}
+
+/// Container stats stored on the database.
+/// Recorded for the containers of Deployments and Stacks.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(
+  feature = "mongo",
+  derive(mongo_indexed::derive::MongoIndexed)
+)]
+#[cfg_attr(feature = "mongo", collection_name(ContainerStats))]
+#[cfg_attr(feature = "mongo", doc_index({ "target.type": 1, "target.id": 1 }))]
+pub struct ContainerStatsRecord {
+  /// Unix timestamp in milliseconds
+  #[cfg_attr(feature = "mongo", index)]
+  pub ts: I64,
+  /// Server id
+  #[cfg_attr(feature = "mongo", index)]
+  pub sid: String,
+  /// The Deployment or Stack which the container belongs to
+  pub target: ResourceTarget,
+  /// The Stack service, if the container belongs to a Stack
+  pub service: Option<String>,
+  /// The container name
+  pub container: String,
+  /// Cpu usage percentage.
+  /// This is in core-percentage, eg 100% is 1 full core.
+  pub cpu_perc: f64,
+  /// Memory usage as percentage of the limit
+  pub mem_perc: f64,
+  /// Memory used in MiB (1024 * 1024 bytes), like the other `_mb` fields
+  pub mem_used_mb: f64,
+  /// Memory limit in MiB
+  pub mem_limit_mb: f64,
+  /// Total network received in MiB, since the container started
+  pub net_rx_mb: f64,
+  /// Total network transmitted in MiB, since the container started
+  pub net_tx_mb: f64,
+  /// Total block read in MiB, since the container started
+  pub block_read_mb: f64,
+  /// Total block written in MiB, since the container started
+  pub block_write_mb: f64,
 }
 
 /// Realtime system stats data.
@@ -71,6 +197,13 @@ pub struct SystemStats {
   pub mem_total_gb: f64,
   /// Breakdown of individual disks, ie their usages, sizes, and mount points
   pub disks: Vec<SingleDiskUsage>,
+  /// Breakdown of individual network interfaces,
+  /// ie their receive and transmit rates
+  #[serde(default)]
+  pub networks: Vec<SingleNetworkInterfaceUsage>,
+  /// System load average
+  #[serde(default)]
+  pub load_average: LoadAverage,
 
   // metadata
   /// The rate the system stats are being polled from the system
@@ -115,6 +248,79 @@ pub struct TotalDiskUsage {
   pub total_gb: f64,
 }
 
+/// Info for a single network interface on the system.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SingleNetworkInterfaceUsage {
+  /// The interface name
+  pub name: String,
+  /// Bytes received per second
+  pub rx_bytes_per_sec: f64,
+  /// Bytes transmitted per second
+  pub tx_bytes_per_sec: f64,
+}
+
+impl SingleNetworkInterfaceUsage {
+  /// Whether this is a docker bridge or container veth interface.
+  /// Their traffic is also counted on the host interfaces,
+  /// so they are excluded from the host totals.
+  pub fn is_docker_virtual(&self) -> bool {
+    self.name == "docker0"
+      || self.name.starts_with("br-")
+      || self.name.starts_with("veth")
+  }
+}
+
+/// Sums the host interfaces, excluding the docker virtual interfaces.
+pub fn sum_network_usage(
+  networks: &[SingleNetworkInterfaceUsage],
+) -> TotalNetworkUsage {
+  networks
+    .iter()
+    .filter(|network| !network.is_docker_virtual())
+    .fold(TotalNetworkUsage::default(), |mut total, network| {
+      total.rx_bytes_per_sec += network.rx_bytes_per_sec;
+      total.tx_bytes_per_sec += network.tx_bytes_per_sec;
+      total
+    })
+}
+
+/// The host interface with the highest receive or transmit rate.
+pub fn busiest_network_interface(
+  networks: &[SingleNetworkInterfaceUsage],
+) -> Option<&SingleNetworkInterfaceUsage> {
+  networks
+    .iter()
+    .filter(|network| !network.is_docker_virtual())
+    .max_by(|a, b| {
+      let a = a.rx_bytes_per_sec.max(a.tx_bytes_per_sec);
+      let b = b.rx_bytes_per_sec.max(b.tx_bytes_per_sec);
+      a.total_cmp(&b)
+    })
+}
+
+/// Info for the all network interfaces combined.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct TotalNetworkUsage {
+  /// Bytes received per second
+  pub rx_bytes_per_sec: f64,
+  /// Bytes transmitted per second
+  pub tx_bytes_per_sec: f64,
+}
+
+/// The system load average over 1, 5, and 15 minutes.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy)]
+pub struct LoadAverage {
+  /// Load average over the last minute
+  pub one: f64,
+  /// Load average over the last 5 minutes
+  pub five: f64,
+  /// Load average over the last 15 minutes
+  pub fifteen: f64,
+}
+
 /// Information about a process on the system.
 #[typeshare]
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -15,7 +15,7 @@
 //! - `KOMODO_API_SECRET`
 //!
 //! ## Client Example
-//! ```
+//! ```ignore
 //! dotenvy::dotenv().ok();
 //!
 //! let client = KomodoClient::new_from_env()?;
@@ -108,7 +108,7 @@ impl KomodoClient {
 
   /// Add a healthcheck in the initialization pipeline:
   ///
-  /// ```rust
+  /// ```ignore
   /// let komodo = KomodoClient::new_from_env()?
   ///   .with_healthcheck().await?;
   /// ```
@@ -1,12 +1,15 @@
 use std::time::Duration;
 
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 use futures::{SinkExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use serror::serialize_error;
 use thiserror::Error;
 use tokio::sync::broadcast;
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio_tungstenite::{
+  connect_async, tungstenite::Message, MaybeTlsStream,
+  WebSocketStream,
+};
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, info_span, warn, Instrument};
 use typeshare::typeshare;
@@ -14,6 +17,18 @@ use uuid::Uuid;
 
 use crate::{entities::update::UpdateListItem, KomodoClient};
 
+mod log;
+mod terminal;
+
+pub use log::{LogStreamMessage, LogStreamQuery, LogStreamTarget};
+pub use terminal::{
+  ExecTerminalQuery, ExecTerminalTarget, HostTerminalQuery,
+  TerminalMessage,
+};
+
+pub type KomodoWebSocket =
+  WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
+
 #[typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", content = "params")]
@@ -270,3 +285,32 @@ impl KomodoClient {
     Ok((rx, cancel_clone))
   }
 }
+
+impl KomodoClient {
+  /// Connects to a Core websocket, and logs in with the api keys.
+  async fn connect_websocket(
+    &self,
+    path: &str,
+  ) -> anyhow::Result<KomodoWebSocket> {
+    let address =
+      format!("{}/ws/{path}", self.address.replacen("http", "ws", 1));
+    let (mut ws, _) =
+      connect_async(&address).await.with_context(|| {
+        format!("failed to connect to Komodo websocket at {address}")
+      })?;
+
+    let login_msg = WsLoginMessage::ApiKeys {
+      key: self.key.clone(),
+      secret: self.secret.clone(),
+    }
+    .to_json_string()?;
+    ws.send(Message::Text(login_msg))
+      .await
+      .context("failed to send login message")?;
+    match ws.try_next().await.context("failed to recieve message")? {
+      Some(Message::Text(msg)) if msg == "LOGGED_IN" => Ok(ws),
+      Some(msg) => Err(anyhow!("failed to login | {msg}")),
+      None => Err(anyhow!("got None message after login message")),
+    }
+  }
+}
@@ -0,0 +1,140 @@
+use anyhow::Context;
+use futures::{SinkExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_util::sync::CancellationToken;
+use typeshare::typeshare;
+
+use crate::{entities::U64, KomodoClient};
+
+/// The container to follow logs for over the `/ws/log` websocket.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "params")]
+pub enum LogStreamTarget {
+  /// Any container on a Server.
+  /// Requires Read permissions on the Server.
+  Container {
+    /// Server id or name
+    server: String,
+    /// The container name
+    container: String,
+  },
+  /// The Deployment container.
+  /// Requires Read permissions on the Deployment.
+  Deployment {
+    /// Deployment id or name
+    deployment: String,
+  },
+  /// A Stack service.
+  /// Requires Read permissions on the Stack.
+  StackService {
+    /// Stack id or name
+    stack: String,
+    /// The service name
+    service: String,
+  },
+}
+
+/// Sent after logging in to the `/ws/log` websocket,
+/// to select the logs to follow.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogStreamQuery {
+  pub target: LogStreamTarget,
+  /// The number of past lines to send before following.
+  /// Max: 5000.
+  #[serde(default = "default_tail")]
+  pub tail: U64,
+  /// Enable `--timestamps`
+  #[serde(default)]
+  pub timestamps: bool,
+}
+
+fn default_tail() -> u64 {
+  100
+}
+
+impl LogStreamQuery {
+  pub fn from_json_str(json: &str) -> anyhow::Result<LogStreamQuery> {
+    serde_json::from_str(json)
+      .context("failed to parse json as LogStreamQuery")
+  }
+}
+
+/// The messages sent over the `/ws/log` websocket.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum LogStreamMessage {
+  /// A line of the container stdout
+  Stdout(String),
+  /// A line of the container stderr
+  Stderr(String),
+  /// The stream failed, and will be closed.
+  Error(String),
+}
+
+impl LogStreamMessage {
+  pub fn to_json_string(&self) -> anyhow::Result<String> {
+    serde_json::to_string(self)
+      .context("failed to serialize LogStreamMessage to json string")
+  }
+}
+
+impl KomodoClient {
+  /// Follow container logs, like `docker logs -f`.
+  /// The receiver is closed once the log stream ends.
+  /// Cancel the token to stop following.
+  pub async fn subscribe_to_logs(
+    &self,
+    query: LogStreamQuery,
+    capacity: usize,
+  ) -> anyhow::Result<(
+    mpsc::Receiver<LogStreamMessage>,
+    CancellationToken,
+  )> {
+    let mut ws = self.connect_websocket("log").await?;
+
+    let query = serde_json::to_string(&query)
+      .context("failed to serialize LogStreamQuery")?;
+    ws.send(Message::Text(query))
+      .await
+      .context("failed to send log stream query")?;
+
+    let (tx, rx) = mpsc::channel(capacity);
+    let cancel = CancellationToken::new();
+    let cancel_clone = cancel.clone();
+
+    tokio::spawn(async move {
+      loop {
+        let msg = tokio::select! {
+          _ = cancel.cancelled() => {
+            let _ = ws.close(None).await;
+            break;
+          }
+          msg = ws.try_next() => msg,
+        };
+        let msg = match msg {
+          Ok(Some(Message::Text(msg))) => {
+            serde_json::from_str::<LogStreamMessage>(&msg)
+              .unwrap_or(LogStreamMessage::Error(msg))
+          }
+          Ok(Some(Message::Close(_))) | Ok(None) => break,
+          Ok(Some(_)) => continue,
+          Err(e) => LogStreamMessage::Error(format!(
+            "failed to recieve message | {e:#}"
+          )),
+        };
+        let error = matches!(msg, LogStreamMessage::Error(_));
+        if tx.send(msg).await.is_err() || error {
+          let _ = ws.close(None).await;
+          break;
+        }
+      }
+    });
+
+    Ok((rx, cancel_clone))
+  }
+}
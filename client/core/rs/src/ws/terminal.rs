@@ -0,0 +1,145 @@
+use anyhow::Context;
+use futures::SinkExt;
+use serde::{Deserialize, Serialize};
+use tokio_tungstenite::tungstenite::Message;
+use typeshare::typeshare;
+
+use crate::KomodoClient;
+
+use super::KomodoWebSocket;
+
+/// The container to open a terminal in over the `/ws/exec` websocket.
+/// Requires Write permissions on the Deployment / Stack.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "params")]
+pub enum ExecTerminalTarget {
+  /// The Deployment container.
+  Deployment {
+    /// Deployment id or name
+    deployment: String,
+  },
+  /// A Stack service.
+  StackService {
+    /// Stack id or name
+    stack: String,
+    /// The service name
+    service: String,
+  },
+}
+
+/// Sent after logging in to the `/ws/exec` websocket,
+/// to select the container and command.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecTerminalQuery {
+  pub target: ExecTerminalTarget,
+  /// The command to run in the container.
+  /// Split into arguments following shell quoting rules,
+  /// eg. `sh -c "echo hello"`. It is not run in a shell.
+  /// Default: `sh`
+  #[serde(default = "default_command")]
+  pub command: String,
+  /// The initial terminal rows.
+  /// Default: 24
+  #[serde(default = "default_rows")]
+  pub rows: u16,
+  /// The initial terminal columns.
+  /// Default: 80
+  #[serde(default = "default_cols")]
+  pub cols: u16,
+}
+
+fn default_command() -> String {
+  String::from("sh")
+}
+
+fn default_rows() -> u16 {
+  24
+}
+
+fn default_cols() -> u16 {
+  80
+}
+
+/// Sent after logging in to the `/ws/terminal` websocket,
+/// to open a shell on the Server host. Admin only.
+///
+/// The shell and working directory are configured on Periphery,
+/// which can also disable the host terminal entirely.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostTerminalQuery {
+  /// Server id or name
+  pub server: String,
+  /// The initial terminal rows.
+  /// Default: 24
+  #[serde(default = "default_rows")]
+  pub rows: u16,
+  /// The initial terminal columns.
+  /// Default: 80
+  #[serde(default = "default_cols")]
+  pub cols: u16,
+}
+
+/// Text messages sent by the client over terminal websockets.
+///
+/// Binary messages from the client are written to the terminal stdin,
+/// and binary messages from Core are the terminal output.
+/// Text messages from Core are errors, sent before closing.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "params")]
+pub enum TerminalMessage {
+  /// Resize the terminal
+  Resize { rows: u16, cols: u16 },
+}
+
+impl TerminalMessage {
+  pub fn from_json_str(
+    json: &str,
+  ) -> anyhow::Result<TerminalMessage> {
+    serde_json::from_str(json)
+      .context("failed to parse json as TerminalMessage")
+  }
+
+  pub fn to_json_string(&self) -> anyhow::Result<String> {
+    serde_json::to_string(self)
+      .context("failed to serialize TerminalMessage to json string")
+  }
+}
+
+impl KomodoClient {
+  /// Opens an interactive terminal in a container,
+  /// like `docker exec -it`.
+  ///
+  /// Send stdin as binary messages, and [TerminalMessage]s as text.
+  pub async fn connect_exec_terminal(
+    &self,
+    query: ExecTerminalQuery,
+  ) -> anyhow::Result<KomodoWebSocket> {
+    let mut ws = self.connect_websocket("exec").await?;
+    let query = serde_json::to_string(&query)
+      .context("failed to serialize ExecTerminalQuery")?;
+    ws.send(Message::Text(query))
+      .await
+      .context("failed to send exec terminal query")?;
+    Ok(ws)
+  }
+
+  /// Opens an interactive shell on a Server host. Admin only.
+  ///
+  /// Send stdin as binary messages, and [TerminalMessage]s as text.
+  pub async fn connect_host_terminal(
+    &self,
+    query: HostTerminalQuery,
+  ) -> anyhow::Result<KomodoWebSocket> {
+    let mut ws = self.connect_websocket("terminal").await?;
+    let query = serde_json::to_string(&query)
+      .context("failed to serialize HostTerminalQuery")?;
+    ws.send(Message::Text(query))
+      .await
+      .context("failed to send host terminal query")?;
+    Ok(ws)
+  }
+}
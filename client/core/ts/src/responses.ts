@@ -89,6 +89,7 @@ export type ReadResponses = {
   GetDeploymentContainer: Types.GetDeploymentContainerResponse;
   GetDeploymentActionState: Types.GetDeploymentActionStateResponse;
   GetDeploymentStats: Types.GetDeploymentStatsResponse;
+  GetHistoricalContainerStats: Types.GetHistoricalContainerStatsResponse;
   GetDeploymentLog: Types.GetDeploymentLogResponse;
   SearchDeploymentLog: Types.SearchDeploymentLogResponse;
   ListDeployments: Types.ListDeploymentsResponse;
@@ -163,6 +164,10 @@ export type ReadResponses = {
   ListAlerts: Types.ListAlertsResponse;
   GetAlert: Types.GetAlertResponse;
 
+  // ==== ALERT SILENCE ====
+  ListAlertSilences: Types.ListAlertSilencesResponse;
+  GetAlertSilence: Types.GetAlertSilenceResponse;
+
   // ==== SERVER STATS ====
   GetSystemInformation: Types.GetSystemInformationResponse;
   GetSystemStats: Types.GetSystemStatsResponse;
@@ -214,6 +219,9 @@ export type WriteResponses = {
   DeleteServer: Types.Server;
   UpdateServer: Types.Server;
   RenameServer: Types.Update;
+  UnpinServerCertificate: Types.Update;
+  CreateServerEnrollmentToken: Types.CreateServerEnrollmentTokenResponse;
+  DeleteServerEnrollmentToken: Types.Update;
   CreateNetwork: Types.Update;
 
   // ==== DEPLOYMENT ====
@@ -308,6 +316,14 @@ export type WriteResponses = {
   RenameTag: Types.Tag;
   UpdateTagsOnResource: Types.UpdateTagsOnResourceResponse;
 
+  // ==== ALERT ====
+  AcknowledgeAlert: Types.AcknowledgeAlertResponse;
+
+  // ==== ALERT SILENCE ====
+  CreateAlertSilence: Types.CreateAlertSilenceResponse;
+  UpdateAlertSilence: Types.UpdateAlertSilenceResponse;
+  DeleteAlertSilence: Types.DeleteAlertSilenceResponse;
+
   // ==== VARIABLE ====
   CreateVariable: Types.CreateVariableResponse;
   UpdateVariableValue: Types.UpdateVariableValueResponse;
@@ -352,6 +368,7 @@ export type ExecuteResponses = {
   Deploy: Types.Update;
   BatchDeploy: Types.BatchExecutionResponse;
   PullDeployment: Types.Update;
+  RollbackDeployment: Types.Update;
   StartDeployment: Types.Update;
   RestartDeployment: Types.Update;
   PauseDeployment: Types.Update;
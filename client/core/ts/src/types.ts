@@ -10,6 +10,302 @@ export type MongoId = MongoIdObj;
 
 export type I64 = number;
 
+/** Severity level of problem. */
+export enum SeverityLevel {
+	/** No problem. */
+	Ok = "OK",
+	/** Problem is imminent. */
+	Warning = "WARNING",
+	/** Problem fully realized. */
+	Critical = "CRITICAL",
+}
+
+/** Used to reference a specific resource across all resource types */
+export type ResourceTarget = 
+	| { type: "System", id: string }
+	| { type: "Server", id: string }
+	| { type: "Stack", id: string }
+	| { type: "Deployment", id: string }
+	| { type: "Build", id: string }
+	| { type: "Repo", id: string }
+	| { type: "Procedure", id: string }
+	| { type: "Action", id: string }
+	| { type: "Builder", id: string }
+	| { type: "Alerter", id: string }
+	| { type: "ServerTemplate", id: string }
+	| { type: "ResourceSync", id: string };
+
+/** The variants of data related to the alert. */
+export type AlertData = 
+	/** A null alert */
+	| { type: "None", data: {
+}}
+	/** A server could not be reached. */
+	| { type: "ServerUnreachable", data: {
+	/** The id of the server */
+	id: string;
+	/** The name of the server */
+	name: string;
+	/** The region of the server */
+	region?: string;
+	/** The error data */
+	err?: _Serror;
+}}
+	/** A server has high CPU usage. */
+	| { type: "ServerCpu", data: {
+	/** The id of the server */
+	id: string;
+	/** The name of the server */
+	name: string;
+	/** The region of the server */
+	region?: string;
+	/** The cpu usage percentage */
+	percentage: number;
+}}
+	/** A server has high memory usage. */
+	| { type: "ServerMem", data: {
+	/** The id of the server */
+	id: string;
+	/** The name of the server */
+	name: string;
+	/** The region of the server */
+	region?: string;
+	/** The used memory */
+	used_gb: number;
+	/** The total memory */
+	total_gb: number;
+}}
+	/** A server has high disk usage. */
+	| { type: "ServerDisk", data: {
+	/** The id of the server */
+	id: string;
+	/** The name of the server */
+	name: string;
+	/** The region of the server */
+	region?: string;
+	/** The mount path of the disk */
+	path: string;
+	/** The used portion of the disk in GB */
+	used_gb: number;
+	/** The total size of the disk in GB */
+	total_gb: number;
+}}
+	/** A server has high network usage. */
+	| { type: "ServerNetwork", data: {
+	/** The id of the server */
+	id: string;
+	/** The name of the server */
+	name: string;
+	/** The region of the server */
+	region?: string;
+	/** The busiest network interface */
+	interface: string;
+	/** The receive rate on the interface in Mbps */
+	rx_mbps: number;
+	/** The transmit rate on the interface in Mbps */
+	tx_mbps: number;
+}}
+	/** A server has high load average. */
+	| { type: "ServerLoad", data: {
+	/** The id of the server */
+	id: string;
+	/** The name of the server */
+	name: string;
+	/** The region of the server */
+	region?: string;
+	/** The 5 minute load average */
+	load_average: number;
+}}
+	/** A container's state has changed unexpectedly. */
+	| { type: "ContainerStateChange", data: {
+	/** The id of the deployment */
+	id: string;
+	/** The name of the deployment */
+	name: string;
+	/** The server id of server that the deployment is on */
+	server_id: string;
+	/** The server name */
+	server_name: string;
+	/** The previous container state */
+	from: DeploymentState;
+	/** The current container state */
+	to: DeploymentState;
+}}
+	/** A Deployment has an image update available */
+	| { type: "DeploymentImageUpdateAvailable", data: {
+	/** The id of the deployment */
+	id: string;
+	/** The name of the deployment */
+	name: string;
+	/** The server id of server that the deployment is on */
+	server_id: string;
+	/** The server name */
+	server_name: string;
+	/** The image with update */
+	image: string;
+}}
+	/** A stack's state has changed unexpectedly. */
+	| { type: "StackStateChange", data: {
+	/** The id of the stack */
+	id: string;
+	/** The name of the stack */
+	name: string;
+	/** The server id of server that the stack is on */
+	server_id: string;
+	/** The server name */
+	server_name: string;
+	/** The previous stack state */
+	from: StackState;
+	/** The current stack state */
+	to: StackState;
+}}
+	/** A Stack has an image update available */
+	| { type: "StackImageUpdateAvailable", data: {
+	/** The id of the stack */
+	id: string;
+	/** The name of the stack */
+	name: string;
+	/** The server id of server that the stack is on */
+	server_id: string;
+	/** The server name */
+	server_name: string;
+	/** The service name to update */
+	service: string;
+	/** The image with update */
+	image: string;
+}}
+	/** A Deployment / Stack container is reporting unhealthy. */
+	| { type: "ContainerUnhealthy", data: {
+	/** The id of the Deployment / Stack */
+	id: string;
+	/** The name of the Deployment / Stack */
+	name: string;
+	/** The server id of server that the container is on */
+	server_id: string;
+	/** The server name */
+	server_name: string;
+	/** The container name */
+	container: string;
+}}
+	/**
+	 * A Deployment / Stack container is being
+	 * repeatedly restarted by docker.
+	 */
+	| { type: "ContainerRestartLoop", data: {
+	/** The id of the Deployment / Stack */
+	id: string;
+	/** The name of the Deployment / Stack */
+	name: string;
+	/** The server id of server that the container is on */
+	server_id: string;
+	/** The server name */
+	server_name: string;
+	/** The container name */
+	container: string;
+	/** The number of restarts within the detection window */
+	restarts: I64;
+}}
+	/** An AWS builder failed to terminate. */
+	| { type: "AwsBuilderTerminationFailed", data: {
+	/** The id of the aws instance which failed to terminate */
+	instance_id: string;
+	/** A reason for the failure */
+	message: string;
+}}
+	/** A resource sync has pending updates */
+	| { type: "ResourceSyncPendingUpdates", data: {
+	/** The id of the resource sync */
+	id: string;
+	/** The name of the resource sync */
+	name: string;
+}}
+	/** A build has failed */
+	| { type: "BuildFailed", data: {
+	/** The id of the build */
+	id: string;
+	/** The name of the build */
+	name: string;
+	/** The version that failed to build */
+	version: Version;
+}}
+	/** A repo has failed */
+	| { type: "RepoBuildFailed", data: {
+	/** The id of the repo */
+	id: string;
+	/** The name of the repo */
+	name: string;
+}}
+	/** A procedure has failed */
+	| { type: "ProcedureFailed", data: {
+	/** The id of the procedure */
+	id: string;
+	/** The name of the procedure */
+	name: string;
+	/** The name of the stage which failed */
+	stage: string;
+	/** An excerpt of the error log */
+	error: string;
+}}
+	/** An action has failed */
+	| { type: "ActionFailed", data: {
+	/** The id of the action */
+	id: string;
+	/** The name of the action */
+	name: string;
+	/** An excerpt of the error log */
+	error: string;
+}}
+	/** A resource sync has failed */
+	| { type: "ResourceSyncFailed", data: {
+	/** The id of the resource sync */
+	id: string;
+	/** The name of the resource sync */
+	name: string;
+	/** The stage of the sync which failed */
+	stage: string;
+	/** An excerpt of the error log */
+	error: string;
+}};
+
+/** Representation of an alert in the system. */
+export interface Alert {
+	/**
+	 * The Mongo ID of the alert.
+	 * This field is de/serialized from/to JSON as
+	 * `{ "_id": { "$oid": "..." }, ...(rest of serialized Alert) }`
+	 */
+	_id?: MongoId;
+	/** Unix timestamp in milliseconds the alert was opened */
+	ts: I64;
+	/** Whether the alert is already resolved */
+	resolved: boolean;
+	/** The severity of the alert */
+	level: SeverityLevel;
+	/** The target of the alert */
+	target: ResourceTarget;
+	/** The data attached to the alert */
+	data: AlertData;
+	/** The timestamp of alert resolution */
+	resolved_ts?: I64;
+	/**
+	 * Whether the alert was silenced by an [AlertSilence][super::alert_silence::AlertSilence]
+	 * when it was last opened or updated. Alerts are only held back from Alerters
+	 * while a matching silence is active.
+	 */
+	silenced?: boolean;
+	/**
+	 * Whether the alert has been acknowledged.
+	 * Acknowledged alerts are not renotified.
+	 */
+	acknowledged?: boolean;
+	/** The id of the user who acknowledged the alert */
+	acknowledged_by?: string;
+	/** The timestamp of alert acknowledgement */
+	acknowledged_ts?: I64;
+}
+
+export type AcknowledgeAlertResponse = Alert;
+
 /** The levels of permission that a User or UserGroup can have on a resource. */
 export enum PermissionLevel {
 	/** No permissions. */
@@ -61,11 +357,33 @@ export interface ActionConfig {
 	 * If its an empty string, use the default secret from the config.
 	 */
 	webhook_secret?: string;
+	/**
+	 * Optionally provide a CRON expression to run the action on a schedule.
+	 * Uses the format (second is optional):
+	 * `second minute hour day-of-month month day-of-week`, eg:
+	 * ```text
+	 * 0 0 4 * * *
+	 * ```
+	 * If empty, the action is not scheduled.
+	 */
+	schedule?: string;
+	/**
+	 * Whether the schedule is enabled, if one is provided.
+	 * Can be used to temporarily disable the schedule.
+	 */
+	schedule_enabled: boolean;
+	/**
+	 * Optionally provide a timezone (TZ Identifier, eg `America/New_York`)
+	 * to evaluate the schedule in. If empty, uses the Core local timezone.
+	 */
+	schedule_timezone?: string;
 }
 
 export interface ActionInfo {
 	/** When action was last run */
 	last_run_at?: I64;
+	/** The timestamp (ms) of the last run triggered by the schedule. */
+	last_scheduled_run?: I64;
 }
 
 export type Action = Resource<ActionConfig, ActionInfo>;
@@ -99,6 +417,18 @@ export interface ActionListItemInfo {
 	last_run_at: I64;
 	/** Whether last action run successful */
 	state: ActionState;
+	/**
+	 * If the action has a schedule, this is the timestamp (ms)
+	 * of the next scheduled run.
+	 */
+	next_scheduled_run?: I64;
+	/** The timestamp (ms) of the last run triggered by the schedule. */
+	last_scheduled_run?: I64;
+	/**
+	 * If there is an error parsing the schedule expression / timezone,
+	 * it will be given here.
+	 */
+	schedule_error?: string;
 }
 
 export type ActionListItem = ResourceListItem<ActionListItemInfo>;
@@ -130,22 +460,17 @@ export type AlerterEndpoint =
 	/** Send alert to a Slack app */
 	| { type: "Slack", params: SlackAlerterEndpoint }
 	/** Send alert to a Discord app */
-	| { type: "Discord", params: DiscordAlerterEndpoint };
+	| { type: "Discord", params: DiscordAlerterEndpoint }
+	/** Send alert as an email over SMTP */
+	| { type: "Email", params: EmailAlerterEndpoint }
+	/** Send alert as a push notification via ntfy */
+	| { type: "Ntfy", params: NtfyAlerterEndpoint }
+	/** Send alert as a push notification via Gotify */
+	| { type: "Gotify", params: GotifyAlerterEndpoint }
+	/** Send alert as a push notification via Pushover */
+	| { type: "Pushover", params: PushoverAlerterEndpoint };
 
-/** Used to reference a specific resource across all resource types */
-export type ResourceTarget = 
-	| { type: "System", id: string }
-	| { type: "Server", id: string }
-	| { type: "Stack", id: string }
-	| { type: "Deployment", id: string }
-	| { type: "Build", id: string }
-	| { type: "Repo", id: string }
-	| { type: "Procedure", id: string }
-	| { type: "Action", id: string }
-	| { type: "Builder", id: string }
-	| { type: "Alerter", id: string }
-	| { type: "ServerTemplate", id: string }
-	| { type: "ResourceSync", id: string };
+export type U64 = number;
 
 export interface AlerterConfig {
 	/** Whether the alerter is enabled */
@@ -168,6 +493,12 @@ export interface AlerterConfig {
 	resources?: ResourceTarget[];
 	/** DON'T send alerts on these resources. */
 	except_resources?: ResourceTarget[];
+	/**
+	 * Send the alert again every `renotify_minutes`
+	 * while it is unresolved and unacknowledged.
+	 * If 0, alerts are only sent when they open, change, or resolve.
+	 */
+	renotify_minutes?: U64;
 }
 
 export type Alerter = Resource<AlerterConfig, undefined>;
@@ -435,6 +766,7 @@ export type Execution =
 	| { type: "Deploy", params: Deploy }
 	| { type: "BatchDeploy", params: BatchDeploy }
 	| { type: "PullDeployment", params: PullDeployment }
+	| { type: "RollbackDeployment", params: RollbackDeployment }
 	| { type: "StartDeployment", params: StartDeployment }
 	| { type: "RestartDeployment", params: RestartDeployment }
 	| { type: "PauseDeployment", params: PauseDeployment }
@@ -515,13 +847,38 @@ export interface ProcedureConfig {
 	 * If its an empty string, use the default secret from the config.
 	 */
 	webhook_secret?: string;
+	/**
+	 * Optionally provide a CRON expression to run the procedure on a schedule.
+	 * Uses the format (second is optional):
+	 * `second minute hour day-of-month month day-of-week`, eg:
+	 * ```text
+	 * 0 0 4 * * *
+	 * ```
+	 * If empty, the procedure is not scheduled.
+	 */
+	schedule?: string;
+	/**
+	 * Whether the schedule is enabled, if one is provided.
+	 * Can be used to temporarily disable the schedule.
+	 */
+	schedule_enabled: boolean;
+	/**
+	 * Optionally provide a timezone (TZ Identifier, eg `America/New_York`)
+	 * to evaluate the schedule in. If empty, uses the Core local timezone.
+	 */
+	schedule_timezone?: string;
+}
+
+export interface ProcedureInfo {
+	/** The timestamp (ms) of the last run triggered by the schedule. */
+	last_scheduled_run?: I64;
 }
 
 /**
  * Procedures run a series of stages sequentially, where
  * each stage runs executions in parallel.
  */
-export type Procedure = Resource<ProcedureConfig, undefined>;
+export type Procedure = Resource<ProcedureConfig, ProcedureInfo>;
 
 export type CopyProcedureResponse = Procedure;
 
@@ -529,7 +886,50 @@ export type CopyProcedureResponse = Procedure;
 export interface NoData {
 }
 
-export type CreateActionWebhookResponse = NoData;
+export type CreateActionWebhookResponse = NoData;
+
+/**
+ * Silences matching alerts for a period of time,
+ * such as during planned maintenance.
+ * 
+ * Silenced alerts are still recorded, and marked as silenced,
+ * but are not sent to any Alerter.
+ * 
+ * An alert matches when it matches every non-empty matcher.
+ * A silence with no matchers will silence all alerts.
+ */
+export interface AlertSilence {
+	/**
+	 * The Mongo ID of the silence.
+	 * This field is de/serialized from/to JSON as
+	 * `{ "_id": { "$oid": "..." }, ...(rest of serialized AlertSilence) }`
+	 */
+	_id?: MongoId;
+	/** A comment explaining the silence, eg. the maintenance being done. */
+	comment?: string;
+	/**
+	 * Only silence alerts on these resources.
+	 * Including a Server will also silence alerts on
+	 * Deployments and Stacks on that Server.
+	 */
+	targets?: ResourceTarget[];
+	/** Only silence alerts on resources with any of these tag ids. */
+	tags?: string[];
+	/** Only silence these alert types. */
+	alert_types?: AlertData["type"][];
+	/** Only silence alerts with these severity levels. */
+	levels?: SeverityLevel[];
+	/** Unix timestamp in milliseconds the silence starts. */
+	start_ts: I64;
+	/** Unix timestamp in milliseconds the silence ends. */
+	end_ts: I64;
+	/** The id of the user who created the silence. */
+	created_by?: string;
+	/** Unix timestamp in milliseconds the silence was created. */
+	created_at?: I64;
+}
+
+export type CreateAlertSilenceResponse = AlertSilence;
 
 /** Response for [CreateApiKey]. */
 export interface CreateApiKeyResponse {
@@ -709,6 +1109,8 @@ export type CreateVariableResponse = Variable;
 
 export type DeleteActionWebhookResponse = NoData;
 
+export type DeleteAlertSilenceResponse = AlertSilence;
+
 export type DeleteApiKeyForServiceUserResponse = NoData;
 
 export type DeleteApiKeyResponse = NoData;
@@ -755,6 +1157,22 @@ export enum RestartMode {
 	UnlessStopped = "unless-stopped",
 }
 
+/** How `Deploy` replaces the running container. */
+export enum DeployStrategy {
+	/** Remove the old container, then start the new one. */
+	Recreate = "Recreate",
+	/**
+	 * Start the new container under a temporary name,
+	 * and only replace the old container once the new one is healthy.
+	 * The old container is stopped and renamed aside, the new container
+	 * takes its name, then the old container is removed.
+	 * 
+	 * The containers run side by side, so they can't publish fixed
+	 * host ports, eg. `8080:80`, or use the `host` network.
+	 */
+	BlueGreen = "BlueGreen",
+}
+
 export enum TerminationSignal {
 	SigHup = "SIGHUP",
 	SigInt = "SIGINT",
@@ -786,12 +1204,24 @@ export interface DeploymentConfig {
 	/** Whether to poll for any updates to the image. */
 	poll_for_updates?: boolean;
 	/**
-	 * Whether to automatically redeploy when a
-	 * newer image is found.
+	 * Whether to automatically redeploy when
+	 * newer a image is found. Will implicitly
+	 * enable `poll_for_updates`, you don't need to
+	 * enable both.
 	 */
 	auto_update?: boolean;
 	/** Whether to send ContainerStateChange alerts for this deployment. */
 	send_alerts: boolean;
+	/**
+	 * Whether to send ContainerUnhealthy alerts for this deployment,
+	 * when a container with a healthcheck reports unhealthy.
+	 */
+	send_unhealthy_alerts?: boolean;
+	/**
+	 * Whether to send ContainerRestartLoop alerts for this deployment,
+	 * when a container is repeatedly restarted by docker.
+	 */
+	send_restart_loop_alerts?: boolean;
 	/** Configure quick links that are displayed in the resource header */
 	links?: string[];
 	/**
@@ -801,6 +1231,30 @@ export interface DeploymentConfig {
 	network: string;
 	/** The restart mode given to the container. */
 	restart?: RestartMode;
+	/** The max restarts with the `on-failure` restart mode. */
+	restart_max_retries: number;
+	/**
+	 * The number of CPUs the container can use, passed to `--cpus`. Eg. 1.5.
+	 * 0 is unlimited.
+	 */
+	cpus?: number;
+	/**
+	 * The memory limit, passed to `--memory`. Eg. 512m, 2g.
+	 * Empty is unlimited.
+	 */
+	memory?: string;
+	/**
+	 * The soft memory limit, passed to `--memory-reservation`.
+	 * Must be below the memory limit. Empty is unset.
+	 */
+	memory_reservation?: string;
+	/**
+	 * The max number of processes in the container, passed to `--pids-limit`.
+	 * 0 is unlimited.
+	 */
+	pids_limit?: number;
+	/** Ulimits passed to `--ulimit`, as `name=soft[:hard]`. Eg. nofile=1024:2048. */
+	ulimits?: string[];
 	/**
 	 * This is interpolated at the end of the `docker run` command,
 	 * which means they are either passed to the containers inner process,
@@ -808,6 +1262,52 @@ export interface DeploymentConfig {
 	 * Empty is no command.
 	 */
 	command?: string;
+	/**
+	 * The healthcheck command run inside the container, passed to `--health-cmd`.
+	 * Empty uses the image HEALTHCHECK, if it has one.
+	 */
+	healthcheck_command?: string;
+	/** Seconds between healthchecks. 0 uses the docker default (30s). */
+	healthcheck_interval?: number;
+	/**
+	 * Seconds before a healthcheck is considered failed.
+	 * 0 uses the docker default (30s).
+	 */
+	healthcheck_timeout?: number;
+	/**
+	 * Consecutive failed healthchecks before the container is unhealthy.
+	 * 0 uses the docker default (3).
+	 */
+	healthcheck_retries?: number;
+	/**
+	 * Seconds to give the container to start,
+	 * before failed healthchecks count towards the retries.
+	 * 0 uses the docker default (0s).
+	 */
+	healthcheck_start_period?: number;
+	/**
+	 * After `Deploy`, the seconds to wait for the container to become healthy.
+	 * Containers without a healthcheck must instead stay running.
+	 * Containers with restart `no` / `on-failure` may also exit with code 0.
+	 * The deploy fails if the container is unhealthy, exits, or restarts.
+	 * Default: 0, which waits 60s when `healthcheck_command` is set,
+	 * and otherwise skips the verification.
+	 */
+	healthy_timeout?: number;
+	/**
+	 * Whether to redeploy the last successful deploy
+	 * if the deploy fails, such as failing the verification.
+	 * Not done when a BlueGreen deploy fails with the old container still running.
+	 */
+	auto_rollback?: boolean;
+	/** How `Deploy` replaces the running container. */
+	deploy_strategy?: DeployStrategy;
+	/**
+	 * Run on the host during a BlueGreen deploy, after the new container
+	 * is healthy and before the old container is removed.
+	 * For example, to point a reverse proxy at the new container.
+	 */
+	cutover_command?: string;
 	/** The default termination signal to use to stop the deployment. Defaults to SigTerm (default docker signal). */
 	termination_signal?: TerminationSignal;
 	/** The termination timeout. */
@@ -822,6 +1322,12 @@ export interface DeploymentConfig {
 	 * Used to specify different shutdown functionality depending on the termination signal.
 	 */
 	term_signal_labels?: string;
+	/**
+	 * The number of identical containers to run on the server.
+	 * With more than 1, the containers are named `{name}-1` to `{name}-{replicas}`,
+	 * and each replica offsets the published host ports by its index - 1.
+	 */
+	replicas: number;
 	/**
 	 * The container port mapping.
 	 * Irrelevant if container network is `host`.
@@ -908,184 +1414,10 @@ export type GetActionActionStateResponse = ActionActionState;
 
 export type GetActionResponse = Action;
 
-/** Severity level of problem. */
-export enum SeverityLevel {
-	/** No problem. */
-	Ok = "OK",
-	/** Problem is imminent. */
-	Warning = "WARNING",
-	/** Problem fully realized. */
-	Critical = "CRITICAL",
-}
-
-/** The variants of data related to the alert. */
-export type AlertData = 
-	/** A null alert */
-	| { type: "None", data: {
-}}
-	/** A server could not be reached. */
-	| { type: "ServerUnreachable", data: {
-	/** The id of the server */
-	id: string;
-	/** The name of the server */
-	name: string;
-	/** The region of the server */
-	region?: string;
-	/** The error data */
-	err?: _Serror;
-}}
-	/** A server has high CPU usage. */
-	| { type: "ServerCpu", data: {
-	/** The id of the server */
-	id: string;
-	/** The name of the server */
-	name: string;
-	/** The region of the server */
-	region?: string;
-	/** The cpu usage percentage */
-	percentage: number;
-}}
-	/** A server has high memory usage. */
-	| { type: "ServerMem", data: {
-	/** The id of the server */
-	id: string;
-	/** The name of the server */
-	name: string;
-	/** The region of the server */
-	region?: string;
-	/** The used memory */
-	used_gb: number;
-	/** The total memory */
-	total_gb: number;
-}}
-	/** A server has high disk usage. */
-	| { type: "ServerDisk", data: {
-	/** The id of the server */
-	id: string;
-	/** The name of the server */
-	name: string;
-	/** The region of the server */
-	region?: string;
-	/** The mount path of the disk */
-	path: string;
-	/** The used portion of the disk in GB */
-	used_gb: number;
-	/** The total size of the disk in GB */
-	total_gb: number;
-}}
-	/** A container's state has changed unexpectedly. */
-	| { type: "ContainerStateChange", data: {
-	/** The id of the deployment */
-	id: string;
-	/** The name of the deployment */
-	name: string;
-	/** The server id of server that the deployment is on */
-	server_id: string;
-	/** The server name */
-	server_name: string;
-	/** The previous container state */
-	from: DeploymentState;
-	/** The current container state */
-	to: DeploymentState;
-}}
-	/** A Deployment has an image update available */
-	| { type: "DeploymentImageUpdateAvailable", data: {
-	/** The id of the deployment */
-	id: string;
-	/** The name of the deployment */
-	name: string;
-	/** The server id of server that the deployment is on */
-	server_id: string;
-	/** The server name */
-	server_name: string;
-	/** The image with update */
-	image: string;
-}}
-	/** A stack's state has changed unexpectedly. */
-	| { type: "StackStateChange", data: {
-	/** The id of the stack */
-	id: string;
-	/** The name of the stack */
-	name: string;
-	/** The server id of server that the stack is on */
-	server_id: string;
-	/** The server name */
-	server_name: string;
-	/** The previous stack state */
-	from: StackState;
-	/** The current stack state */
-	to: StackState;
-}}
-	/** A Stack has an image update available */
-	| { type: "StackImageUpdateAvailable", data: {
-	/** The id of the stack */
-	id: string;
-	/** The name of the stack */
-	name: string;
-	/** The server id of server that the stack is on */
-	server_id: string;
-	/** The server name */
-	server_name: string;
-	/** The service name to update */
-	service: string;
-	/** The image with update */
-	image: string;
-}}
-	/** An AWS builder failed to terminate. */
-	| { type: "AwsBuilderTerminationFailed", data: {
-	/** The id of the aws instance which failed to terminate */
-	instance_id: string;
-	/** A reason for the failure */
-	message: string;
-}}
-	/** A resource sync has pending updates */
-	| { type: "ResourceSyncPendingUpdates", data: {
-	/** The id of the resource sync */
-	id: string;
-	/** The name of the resource sync */
-	name: string;
-}}
-	/** A build has failed */
-	| { type: "BuildFailed", data: {
-	/** The id of the build */
-	id: string;
-	/** The name of the build */
-	name: string;
-	/** The version that failed to build */
-	version: Version;
-}}
-	/** A repo has failed */
-	| { type: "RepoBuildFailed", data: {
-	/** The id of the repo */
-	id: string;
-	/** The name of the repo */
-	name: string;
-}};
-
-/** Representation of an alert in the system. */
-export interface Alert {
-	/**
-	 * The Mongo ID of the alert.
-	 * This field is de/serialized from/to JSON as
-	 * `{ "_id": { "$oid": "..." }, ...(rest of serialized Alert) }`
-	 */
-	_id?: MongoId;
-	/** Unix timestamp in milliseconds the alert was opened */
-	ts: I64;
-	/** Whether the alert is already resolved */
-	resolved: boolean;
-	/** The severity of the alert */
-	level: SeverityLevel;
-	/** The target of the alert */
-	target: ResourceTarget;
-	/** The data attached to the alert */
-	data: AlertData;
-	/** The timestamp of alert resolution */
-	resolved_ts?: I64;
-}
-
 export type GetAlertResponse = Alert;
 
+export type GetAlertSilenceResponse = AlertSilence;
+
 export type GetAlerterResponse = Alerter;
 
 export interface BuildActionState {
@@ -1465,6 +1797,13 @@ export interface ServerConfig {
 	 * default: true
 	 */
 	stats_monitoring: boolean;
+	/**
+	 * Whether to record historical stats for the Deployment / Stack containers
+	 * on the server. Runs `docker stats --no-stream` every monitoring interval,
+	 * so is opt in. Also requires `stats_monitoring`.
+	 * default: false
+	 */
+	container_stats_monitoring?: boolean;
 	/**
 	 * Whether to trigger 'docker image prune -a -f' every 24 hours.
 	 * default: true
@@ -1480,6 +1819,10 @@ export interface ServerConfig {
 	send_mem_alerts: boolean;
 	/** Whether to send alerts about the servers DISK status */
 	send_disk_alerts: boolean;
+	/** Whether to send alerts about the servers NETWORK status */
+	send_network_alerts: boolean;
+	/** Whether to send alerts about the servers LOAD status */
+	send_load_alerts: boolean;
 	/** The percentage threshhold which triggers WARNING state for CPU. */
 	cpu_warning: number;
 	/** The percentage threshhold which triggers CRITICAL state for CPU. */
@@ -1492,9 +1835,48 @@ export interface ServerConfig {
 	disk_warning: number;
 	/** The percentage threshhold which triggers CRITICAL state for DISK. */
 	disk_critical: number;
+	/**
+	 * The rate in Mbps, received or transmitted on any interface,
+	 * which triggers WARNING state for NETWORK. 0 to disable.
+	 */
+	network_warning?: number;
+	/**
+	 * The rate in Mbps, received or transmitted on any interface,
+	 * which triggers CRITICAL state for NETWORK. 0 to disable.
+	 */
+	network_critical?: number;
+	/**
+	 * The 5 minute load average which triggers WARNING state for LOAD.
+	 * 0 to disable.
+	 */
+	load_warning?: number;
+	/**
+	 * The 5 minute load average which triggers CRITICAL state for LOAD.
+	 * 0 to disable.
+	 */
+	load_critical?: number;
+}
+
+export interface ServerInfo {
+	/**
+	 * The SHA-256 fingerprint of the Periphery https certificate,
+	 * pinned on first contact. Connections presenting a different
+	 * certificate are refused until it is unpinned.
+	 */
+	certificate_fingerprint?: string;
+	/**
+	 * The SHA-256 hash (hex) of the enrollment token, if one is set.
+	 * Periphery dials out to core over a persistent websocket,
+	 * authenticating with the token, instead of core calling the `address`.
+	 * Use for servers behind NAT or strict firewalls.
+	 * 
+	 * Set with [CreateServerEnrollmentToken][crate::api::write::CreateServerEnrollmentToken].
+	 * The token itself is never stored, and the hash is redacted when read.
+	 */
+	enrollment_token_hash?: string;
 }
 
-export type Server = Resource<ServerConfig, undefined>;
+export type Server = Resource<ServerConfig, ServerInfo>;
 
 export type GetServerResponse = Server;
 
@@ -1549,8 +1931,10 @@ export interface StackConfig {
 	/** Whether to poll for any updates to the images. */
 	poll_for_updates?: boolean;
 	/**
-	 * Whether to automatically redeploy when a
-	 * newer images are found.
+	 * Whether to automatically redeploy when
+	 * newer images are found. Will implicitly
+	 * enable `poll_for_updates`, you don't need to
+	 * enable both.
 	 */
 	auto_update?: boolean;
 	/** Whether to run `docker compose down` before `compose up`. */
@@ -1623,6 +2007,16 @@ export interface StackConfig {
 	webhook_force_deploy?: boolean;
 	/** Whether to send StackStateChange alerts for this stack. */
 	send_alerts: boolean;
+	/**
+	 * Whether to send ContainerUnhealthy alerts for this stack,
+	 * when a container with a healthcheck reports unhealthy.
+	 */
+	send_unhealthy_alerts?: boolean;
+	/**
+	 * Whether to send ContainerRestartLoop alerts for this stack,
+	 * when a container is repeatedly restarted by docker.
+	 */
+	send_restart_loop_alerts?: boolean;
 	/** Used with `registry_account` to login to a registry before docker compose up. */
 	registry_provider?: string;
 	/** Used with `registry_provider` to login to a registry before docker compose up. */
@@ -1774,6 +2168,26 @@ export interface SingleDiskUsage {
 	total_gb: number;
 }
 
+/** Info for a single network interface on the system. */
+export interface SingleNetworkInterfaceUsage {
+	/** The interface name */
+	name: string;
+	/** Bytes received per second */
+	rx_bytes_per_sec: number;
+	/** Bytes transmitted per second */
+	tx_bytes_per_sec: number;
+}
+
+/** The system load average over 1, 5, and 15 minutes. */
+export interface LoadAverage {
+	/** Load average over the last minute */
+	one: number;
+	/** Load average over the last 5 minutes */
+	five: number;
+	/** Load average over the last 15 minutes */
+	fifteen: number;
+}
+
 export enum Timelength {
 	OneSecond = "1-sec",
 	FiveSeconds = "5-sec",
@@ -1815,6 +2229,13 @@ export interface SystemStats {
 	mem_total_gb: number;
 	/** Breakdown of individual disks, ie their usages, sizes, and mount points */
 	disks: SingleDiskUsage[];
+	/**
+	 * Breakdown of individual network interfaces,
+	 * ie their receive and transmit rates
+	 */
+	networks?: SingleNetworkInterfaceUsage[];
+	/** System load average */
+	load_average?: LoadAverage;
 	/** The rate the system stats are being polled from the system */
 	polling_rate: Timelength;
 	/** Unix timestamp in milliseconds when stats were last polled */
@@ -1844,6 +2265,9 @@ export enum Operation {
 	UpdateServer = "UpdateServer",
 	DeleteServer = "DeleteServer",
 	RenameServer = "RenameServer",
+	UnpinServerCertificate = "UnpinServerCertificate",
+	CreateServerEnrollmentToken = "CreateServerEnrollmentToken",
+	DeleteServerEnrollmentToken = "DeleteServerEnrollmentToken",
 	StartContainer = "StartContainer",
 	RestartContainer = "RestartContainer",
 	PauseContainer = "PauseContainer",
@@ -1866,6 +2290,7 @@ export enum Operation {
 	PruneDockerBuilders = "PruneDockerBuilders",
 	PruneBuildx = "PruneBuildx",
 	PruneSystem = "PruneSystem",
+	HostTerminal = "HostTerminal",
 	CreateStack = "CreateStack",
 	UpdateStack = "UpdateStack",
 	RenameStack = "RenameStack",
@@ -1888,18 +2313,21 @@ export enum Operation {
 	UnpauseStackService = "UnpauseStackService",
 	StopStackService = "StopStackService",
 	DestroyStackService = "DestroyStackService",
+	ExecStackService = "ExecStackService",
 	CreateDeployment = "CreateDeployment",
 	UpdateDeployment = "UpdateDeployment",
 	RenameDeployment = "RenameDeployment",
 	DeleteDeployment = "DeleteDeployment",
 	Deploy = "Deploy",
 	PullDeployment = "PullDeployment",
+	RollbackDeployment = "RollbackDeployment",
 	StartDeployment = "StartDeployment",
 	RestartDeployment = "RestartDeployment",
 	PauseDeployment = "PauseDeployment",
 	UnpauseDeployment = "UnpauseDeployment",
 	StopDeployment = "StopDeployment",
 	DestroyDeployment = "DestroyDeployment",
+	ExecDeployment = "ExecDeployment",
 	CreateBuild = "CreateBuild",
 	UpdateBuild = "UpdateBuild",
 	RenameBuild = "RenameBuild",
@@ -1944,6 +2372,10 @@ export enum Operation {
 	WriteSyncContents = "WriteSyncContents",
 	CommitSync = "CommitSync",
 	RunSync = "RunSync",
+	AcknowledgeAlert = "AcknowledgeAlert",
+	CreateAlertSilence = "CreateAlertSilence",
+	UpdateAlertSilence = "UpdateAlertSilence",
+	DeleteAlertSilence = "DeleteAlertSilence",
 	CreateVariable = "CreateVariable",
 	UpdateVariableValue = "UpdateVariableValue",
 	DeleteVariable = "DeleteVariable",
@@ -2693,8 +3125,6 @@ export enum VolumeScopeEnum {
 	Global = "global",
 }
 
-export type U64 = number;
-
 /** The version number of the object such as node, service, etc. This is needed to avoid conflicting writes. The client must send the version number along with the modified specification when updating these objects.  This approach ensures safe concurrency and determinism in that the change on the object may not be applied if the version number has changed from the last read. In other words, if two update requests specify the same base version, only one of the requests can succeed. As a result, two separate update requests that happen at the same time will not unintentionally overwrite each other. */
 export interface ObjectVersion {
 	Index?: U64;
@@ -2845,6 +3275,8 @@ export type JsonValue = any;
 
 export type ListActionsResponse = ActionListItem[];
 
+export type ListAlertSilencesResponse = AlertSilence[];
+
 export type ListAlertersResponse = AlerterListItem[];
 
 export interface ContainerListItem {
@@ -2868,6 +3300,17 @@ export interface ContainerListItem {
 	state: ContainerStateStatusEnum;
 	/** Additional human-readable status of this container (e.g. `Exit 0`) */
 	status?: string;
+	/**
+	 * The healthcheck status, if the container has a healthcheck.
+	 * Parsed from the human-readable status.
+	 */
+	health?: HealthStatusEnum;
+	/**
+	 * The number of times the container has been restarted by docker.
+	 * Only included if the container recently (re)started,
+	 * as getting it requires inspecting the container.
+	 */
+	restart_count?: I64;
 	/** The network mode */
 	network_mode?: string;
 	/** The network names attached to container */
@@ -3095,6 +3538,18 @@ export interface ProcedureListItemInfo {
 	stages: I64;
 	/** Reflect whether last run successful / currently running. */
 	state: ProcedureState;
+	/**
+	 * If the procedure has a schedule, this is the timestamp (ms)
+	 * of the next scheduled run.
+	 */
+	next_scheduled_run?: I64;
+	/** The timestamp (ms) of the last run triggered by the schedule. */
+	last_scheduled_run?: I64;
+	/**
+	 * If there is an error parsing the schedule expression / timezone,
+	 * it will be given here.
+	 */
+	schedule_error?: string;
 }
 
 export type ProcedureListItem = ResourceListItem<ProcedureListItemInfo>;
@@ -3226,6 +3681,10 @@ export interface ServerListItemInfo {
 	send_mem_alerts: boolean;
 	/** Whether server is configured to send disk alerts. */
 	send_disk_alerts: boolean;
+	/** Whether server is configured to send network alerts. */
+	send_network_alerts?: boolean;
+	/** Whether server is configured to send load alerts. */
+	send_load_alerts?: boolean;
 }
 
 export type ServerListItem = ResourceListItem<ServerListItemInfo>;
@@ -3411,6 +3870,8 @@ export interface StackQuerySpecifics {
 
 export type StackQuery = ResourceQuery<StackQuerySpecifics>;
 
+export type UpdateAlertSilenceResponse = AlertSilence;
+
 export type UpdateDescriptionResponse = NoData;
 
 export type UpdateDockerRegistryAccountResponse = DockerRegistryAccount;
@@ -3484,6 +3945,16 @@ export interface __Serror {
 
 export type _Serror = __Serror;
 
+/**
+ * Acknowledge an open alert, which stops any repeat notifications.
+ * Requires Execute permissions on the alert target.
+ * Response: [Alert].
+ */
+export interface AcknowledgeAlert {
+	/** The id of the alert to acknowledge. */
+	id: string;
+}
+
 /** **Admin only.** Add a user to a user group. Response: [UserGroup] */
 export interface AddUserToUserGroup {
 	/** The name or id of UserGroup that user should be added to. */
@@ -3609,7 +4080,7 @@ export interface BatchBuildRepo {
 	 * Supports multiline and comma delineated combinations of the above.
 	 * 
 	 * Example:
-	 * ```
+	 * ```text
 	 * # match all foo-* repos
 	 * foo-*
 	 * # add some more
@@ -3626,7 +4097,7 @@ export interface BatchCloneRepo {
 	 * Supports multiline and comma delineated combinations of the above.
 	 * 
 	 * Example:
-	 * ```
+	 * ```text
 	 * # match all foo-* repos
 	 * foo-*
 	 * # add some more
@@ -3643,7 +4114,7 @@ export interface BatchDeploy {
 	 * Supports multiline and comma delineated combinations of the above.
 	 * 
 	 * Example:
-	 * ```
+	 * ```text
 	 * # match all foo-* deployments
 	 * foo-*
 	 * # add some more
@@ -3660,7 +4131,7 @@ export interface BatchDeployStack {
 	 * Supports multiline and comma delineated combinations of the above.
 	 * 
 	 * Example:
-	 * ```
+	 * ```text
 	 * # match all foo-* stacks
 	 * foo-*
 	 * # add some more
@@ -3677,7 +4148,7 @@ export interface BatchDeployStackIfChanged {
 	 * Supports multiline and comma delineated combinations of the above.
 	 * 
 	 * Example:
-	 * ```
+	 * ```text
 	 * # match all foo-* stacks
 	 * foo-*
 	 * # add some more
@@ -3694,7 +4165,7 @@ export interface BatchDestroyDeployment {
 	 * Supports multiline and comma delineated combinations of the above.
 	 * 
 	 * Example:
-	 * ```
+	 * ```text
 	 * # match all foo-* deployments
 	 * foo-*
 	 * # add some more
@@ -3711,7 +4182,7 @@ export interface BatchDestroyStack {
 	 * Supports multiline and comma delineated combinations of the above.
 	 * d
 	 * Example:
-	 * ```
+	 * ```text
 	 * # match all foo-* stacks
 	 * foo-*
 	 * # add some more
@@ -3733,7 +4204,7 @@ export interface BatchPullRepo {
 	 * Supports multiline and comma delineated combinations of the above.
 	 * 
 	 * Example:
-	 * ```
+	 * ```text
 	 * # match all foo-* repos
 	 * foo-*
 	 * # add some more
@@ -3750,7 +4221,7 @@ export interface BatchRunAction {
 	 * Supports multiline and comma delineated combinations of the above.
 	 * 
 	 * Example:
-	 * ```
+	 * ```text
 	 * # match all foo-* actions
 	 * foo-*
 	 * # add some more
@@ -3767,7 +4238,7 @@ export interface BatchRunBuild {
 	 * Supports multiline and comma delineated combinations of the above.
 	 * 
 	 * Example:
-	 * ```
+	 * ```text
 	 * # match all foo-* builds
 	 * foo-*
 	 * # add some more
@@ -3784,7 +4255,7 @@ export interface BatchRunProcedure {
 	 * Supports multiline and comma delineated combinations of the above.
 	 * 
 	 * Example:
-	 * ```
+	 * ```text
 	 * # match all foo-* procedures
 	 * foo-*
 	 * # add some more
@@ -3887,6 +4358,42 @@ export interface CommitSync {
 	sync: string;
 }
 
+/**
+ * Container stats stored on the database.
+ * Recorded for the containers of Deployments and Stacks.
+ */
+export interface ContainerStatsRecord {
+	/** Unix timestamp in milliseconds */
+	ts: I64;
+	/** Server id */
+	sid: string;
+	/** The Deployment or Stack which the container belongs to */
+	target: ResourceTarget;
+	/** The Stack service, if the container belongs to a Stack */
+	service?: string;
+	/** The container name */
+	container: string;
+	/**
+	 * Cpu usage percentage.
+	 * This is in core-percentage, eg 100% is 1 full core.
+	 */
+	cpu_perc: number;
+	/** Memory usage as percentage of the limit */
+	mem_perc: number;
+	/** Memory used in MiB (1024 * 1024 bytes), like the other `_mb` fields */
+	mem_used_mb: number;
+	/** Memory limit in MiB */
+	mem_limit_mb: number;
+	/** Total network received in MiB, since the container started */
+	net_rx_mb: number;
+	/** Total network transmitted in MiB, since the container started */
+	net_tx_mb: number;
+	/** Total block read in MiB, since the container started */
+	block_read_mb: number;
+	/** Total block written in MiB, since the container started */
+	block_write_mb: number;
+}
+
 export interface Conversion {
 	/** reference on the server. */
 	local: string;
@@ -4021,6 +4528,27 @@ export interface CreateActionWebhook {
 	action: string;
 }
 
+/** **Admin only.** Create an alert silence. Response: [AlertSilence]. */
+export interface CreateAlertSilence {
+	/** A comment explaining the silence. */
+	comment?: string;
+	/** Only silence alerts on these resources. */
+	targets?: ResourceTarget[];
+	/** Only silence alerts on resources with any of these tags (id or name). */
+	tags?: string[];
+	/** Only silence these alert types. */
+	alert_types?: AlertData["type"][];
+	/** Only silence alerts with these severity levels. */
+	levels?: SeverityLevel[];
+	/**
+	 * Unix timestamp in milliseconds the silence starts.
+	 * If not given, the silence starts immediately.
+	 */
+	start_ts?: I64;
+	/** Unix timestamp in milliseconds the silence ends. */
+	end_ts: I64;
+}
+
 /** Create an alerter. Response: [Alerter]. */
 export interface CreateAlerter {
 	/** The name given to newly created alerter. */
@@ -4208,6 +4736,29 @@ export interface CreateServer {
 	config?: _PartialServerConfig;
 }
 
+/**
+ * Generate a new enrollment token for the Server, replacing any existing one.
+ * Periphery configured with the token (and the `core_address`)
+ * dials out to Core, instead of Core calling the Server `address`.
+ * Response: [CreateServerEnrollmentTokenResponse].
+ */
+export interface CreateServerEnrollmentToken {
+	/** Server Id or name */
+	server: string;
+}
+
+/** Response for [CreateServerEnrollmentToken]. */
+export interface CreateServerEnrollmentTokenResponse {
+	/**
+	 * The enrollment token to configure on Periphery.
+	 * 
+	 * Note.
+	 * Only a hash of the token is stored,
+	 * there is no way to get the token again after it is returned here.
+	 */
+	token: string;
+}
+
 export type PartialServerTemplateConfig = 
 	| { type: "Aws", params: _PartialAwsServerTemplateConfig }
 	| { type: "Hetzner", params: _PartialHetznerServerTemplateConfig };
@@ -4295,10 +4846,51 @@ export interface CreateVariable {
 	is_secret?: boolean;
 }
 
+/** The HTTP method used by a Custom alerter. */
+export enum CustomAlerterMethod {
+	Post = "POST",
+	Put = "PUT",
+	Patch = "PATCH",
+}
+
 /** Configuration for a Custom alerter endpoint. */
 export interface CustomAlerterEndpoint {
-	/** The http/s endpoint to send the POST to */
+	/** The http/s endpoint to send the request to */
 	url: string;
+	/**
+	 * The HTTP method to use.
+	 * Default: `POST`
+	 */
+	method?: CustomAlerterMethod;
+	/**
+	 * Extra headers to send with the request,
+	 * one `Name: Value` per line.
+	 * Lines starting with `#` are ignored.
+	 * 
+	 * Credential headers, eg. `Authorization`, must reference a core
+	 * secret or variable, eg. `Authorization: Bearer [[WEBHOOK_TOKEN]]`.
+	 */
+	headers?: string;
+	/**
+	 * A Handlebars template for the request body,
+	 * rendered against the alert.
+	 * If empty, the alert is sent serialized as JSON.
+	 * 
+	 * Along with the alert fields (`level`, `target`, `data`, ...),
+	 * the template has access to:
+	 * - `id`: The alert id
+	 * - `severity`: The alert level, eg. `CRITICAL`
+	 * - `target_name`: The name of the alerting resource
+	 * - `resolved`: Whether the alert is resolved
+	 * - `title` / `message`: A plain text summary of the alert
+	 * - `link`: A link to the alerting resource in Komodo
+	 * 
+	 * Use the `json` helper to embed values in a JSON body,
+	 * eg. `{"text": {{json message}}}`.
+	 * Keys, eg. a PagerDuty routing key, can reference a core
+	 * secret or variable, eg. `[[PAGERDUTY_ROUTING_KEY]]`.
+	 */
+	body?: string;
 }
 
 /**
@@ -4319,6 +4911,15 @@ export interface DeleteActionWebhook {
 	action: string;
 }
 
+/**
+ * **Admin only.** Delete an alert silence,
+ * and return the deleted silence. Response: [AlertSilence].
+ */
+export interface DeleteAlertSilence {
+	/** The id of the silence to delete. */
+	id: string;
+}
+
 /**
  * Deletes the alerter at the given id, and returns the deleted alerter.
  * Response: [Alerter]
@@ -4471,6 +5072,16 @@ export interface DeleteServer {
 	id: string;
 }
 
+/**
+ * Remove the Server enrollment token, closing any connection
+ * Periphery made with it. Core goes back to calling the Server `address`.
+ * Response: [Update].
+ */
+export interface DeleteServerEnrollmentToken {
+	/** Server Id or name */
+	server: string;
+}
+
 /**
  * Deletes the server template at the given id, and returns the deleted server template.
  * Response: [ServerTemplate]
@@ -4658,6 +5269,50 @@ export interface DiscordAlerterEndpoint {
 	url: string;
 }
 
+/** How to secure the connection to the SMTP server. */
+export enum SmtpSecurity {
+	/** Plaintext connection. Only use with a local SMTP relay / sink. */
+	None = "None",
+	/** Upgrade a plaintext connection using STARTTLS (usually port 587). */
+	StartTls = "StartTls",
+	/** Implicit TLS connection (usually port 465). */
+	Tls = "Tls",
+}
+
+/** Configuration for an Email (SMTP) alerter. */
+export interface EmailAlerterEndpoint {
+	/** The SMTP server host, eg. `smtp.example.com` */
+	host: string;
+	/**
+	 * The SMTP server port.
+	 * Default: `587`
+	 */
+	port: number;
+	/**
+	 * How to secure the connection to the SMTP server.
+	 * Default: `StartTls`
+	 */
+	security?: SmtpSecurity;
+	/**
+	 * The username to authenticate with.
+	 * If empty, will not authenticate.
+	 */
+	username?: string;
+	/**
+	 * The password to authenticate with.
+	 * Must reference a core secret or variable, eg. `[[SMTP_PASSWORD]]`,
+	 * so the password itself is never stored on the alerter.
+	 */
+	password?: string;
+	/**
+	 * The address to send the email from,
+	 * eg. `Komodo <komodo@example.com>`
+	 */
+	from: string;
+	/** The addresses to send the email to. */
+	to?: string[];
+}
+
 export interface EnvironmentVar {
 	variable: string;
 	value: string;
@@ -4673,6 +5328,49 @@ export interface ExchangeForJwt {
 	token: string;
 }
 
+/**
+ * The container to open a terminal in over the `/ws/exec` websocket.
+ * Requires Write permissions on the Deployment / Stack.
+ */
+export type ExecTerminalTarget = 
+	/** The Deployment container. */
+	| { type: "Deployment", params: {
+	/** Deployment id or name */
+	deployment: string;
+}}
+	/** A Stack service. */
+	| { type: "StackService", params: {
+	/** Stack id or name */
+	stack: string;
+	/** The service name */
+	service: string;
+}};
+
+/**
+ * Sent after logging in to the `/ws/exec` websocket,
+ * to select the container and command.
+ */
+export interface ExecTerminalQuery {
+	target: ExecTerminalTarget;
+	/**
+	 * The command to run in the container.
+	 * Split into arguments following shell quoting rules,
+	 * eg. `sh -c "echo hello"`. It is not run in a shell.
+	 * Default: `sh`
+	 */
+	command: string;
+	/**
+	 * The initial terminal rows.
+	 * Default: 24
+	 */
+	rows: number;
+	/**
+	 * The initial terminal columns.
+	 * Default: 80
+	 */
+	cols: number;
+}
+
 /**
  * Get pretty formatted monrun sync toml for all resources
  * which the user has permissions to view.
@@ -4766,6 +5464,12 @@ export interface GetAlert {
 	id: string;
 }
 
+/** Get an alert silence. Response: [AlertSilence]. */
+export interface GetAlertSilence {
+	/** The id of the silence. */
+	id: string;
+}
+
 /** Get a specific alerter. Response: [Alerter]. */
 export interface GetAlerter {
 	/** Id or name */
@@ -5026,8 +5730,38 @@ export interface GetGitProviderAccount {
 	id: string;
 }
 
+/**
+ * Paginated endpoint serving historical (timeseries) container stats for graphing.
+ * Supports Deployments, and Stacks along with the `service`.
+ * Response: [GetHistoricalContainerStatsResponse].
+ */
+export interface GetHistoricalContainerStats {
+	/** The Deployment or Stack. Id or name. */
+	target: ResourceTarget;
+	/** The Stack service. Required for Stack targets. */
+	service?: string;
+	/** The granularity of the data. */
+	granularity: Timelength;
+	/**
+	 * Page of historical data. Default is 0, which is the most recent data.
+	 * Use with the `next_page` field of the response.
+	 */
+	page?: number;
+}
+
+/** Response to [GetHistoricalContainerStats]. */
+export interface GetHistoricalContainerStatsResponse {
+	/** The timeseries page of data. */
+	stats: ContainerStatsRecord[];
+	/** If there is a next page of data, pass this to `page` to get it. */
+	next_page?: number;
+}
+
 /**
  * Paginated endpoint serving historical (timeseries) server stats for graphing.
+ * The resolution is picked based on the time range covered by the page.
+ * With `1-hr` granularity and above, the stats are served from the
+ * hourly rollups, and with `1-day` and above, from the daily rollups.
  * Response: [GetHistoricalServerStatsResponse].
  */
 export interface GetHistoricalServerStats {
@@ -5060,12 +5794,75 @@ export interface SystemStatsRecord {
 	disk_total_gb: number;
 	/** Breakdown of individual disks, ie their usages, sizes, and mount points */
 	disks: SingleDiskUsage[];
+	/** Network bytes received per second, across all interfaces */
+	net_rx_bytes_per_sec?: number;
+	/** Network bytes transmitted per second, across all interfaces */
+	net_tx_bytes_per_sec?: number;
+	/** Breakdown of individual network interfaces */
+	networks?: SingleNetworkInterfaceUsage[];
+	/** System load average */
+	load_average?: LoadAverage;
+}
+
+/** The resolution of historical server stats. */
+export enum StatsResolution {
+	/** The records taken on every monitoring interval. */
+	Raw = "Raw",
+	/** Hourly rollups of the raw records. */
+	Hour = "Hour",
+	/** Daily rollups of the raw records. */
+	Day = "Day",
+}
+
+/** The min / avg / max of a stat over a rollup period. */
+export interface StatsAggregate {
+	min: number;
+	avg: number;
+	max: number;
+}
+
+/**
+ * Downsampled server stats stored on the database.
+ * Aggregates the [SystemStatsRecord]s over an hour or a day.
+ */
+export interface SystemStatsRollup {
+	/** Unix timestamp in milliseconds at the start of the period */
+	ts: I64;
+	/** Server id */
+	sid: string;
+	/** The period covered, either `Hour` or `Day`. */
+	resolution: StatsResolution;
+	/** The number of raw records aggregated */
+	count: I64;
+	/** Cpu usage percentage */
+	cpu_perc: StatsAggregate;
+	/** Memory used in GB */
+	mem_used_gb: StatsAggregate;
+	/** Total memory in GB */
+	mem_total_gb: number;
+	/** Disk used in GB */
+	disk_used_gb: StatsAggregate;
+	/** Total disk size in GB */
+	disk_total_gb: number;
+	/** Network bytes received per second, across all interfaces */
+	net_rx_bytes_per_sec: StatsAggregate;
+	/** Network bytes transmitted per second, across all interfaces */
+	net_tx_bytes_per_sec: StatsAggregate;
+	/** The 5 minute load average */
+	load_average: StatsAggregate;
 }
 
 /** Response to [GetHistoricalServerStats]. */
 export interface GetHistoricalServerStatsResponse {
-	/** The timeseries page of data. */
+	/**
+	 * The timeseries page of data.
+	 * When served from rollups, these hold the averages.
+	 */
 	stats: SystemStatsRecord[];
+	/** The resolution the page is served at. */
+	resolution?: StatsResolution;
+	/** The min / avg / max rollups, when the resolution is `Hour` or `Day`. */
+	rollups?: SystemStatsRollup[];
 	/** If there is a next page of data, pass this to `page` to get it. */
 	next_page?: number;
 }
@@ -5501,6 +6298,17 @@ export interface GetVersionResponse {
 	version: string;
 }
 
+/** Configuration for a Gotify alerter. */
+export interface GotifyAlerterEndpoint {
+	/** The Gotify server url. */
+	url: string;
+	/**
+	 * The Gotify application token.
+	 * Must reference a core secret or variable, eg. `[[GOTIFY_TOKEN]]`.
+	 */
+	token?: string;
+}
+
 export enum HetznerDatacenter {
 	Nuremberg1Dc3 = "Nuremberg1Dc3",
 	Helsinki1Dc2 = "Helsinki1Dc2",
@@ -5607,6 +6415,28 @@ export interface HetznerServerTemplateConfig {
 	user_data: string;
 }
 
+/**
+ * Sent after logging in to the `/ws/terminal` websocket,
+ * to open a shell on the Server host. Admin only.
+ * 
+ * The shell and working directory are configured on Periphery,
+ * which can also disable the host terminal entirely.
+ */
+export interface HostTerminalQuery {
+	/** Server id or name */
+	server: string;
+	/**
+	 * The initial terminal rows.
+	 * Default: 24
+	 */
+	rows: number;
+	/**
+	 * The initial terminal columns.
+	 * Default: 80
+	 */
+	cols: number;
+}
+
 /** Inspect a docker container on the server. Response: [Container]. */
 export interface InspectDockerContainer {
 	/** Id or name */
@@ -5661,6 +6491,15 @@ export interface ListActions {
 	query?: ActionQuery;
 }
 
+/**
+ * List alert silences, sorted by end time descending.
+ * Response: [ListAlertSilencesResponse].
+ */
+export interface ListAlertSilences {
+	/** Only include silences which have not yet ended. */
+	active_only?: boolean;
+}
+
 /** List alerters matching optional query. Response: [ListAlertersResponse]. */
 export interface ListAlerters {
 	/** Structured query to filter alerters. */
@@ -5676,7 +6515,7 @@ export interface ListAlerts {
 	 * Pass a custom mongo query to filter the alerts.
 	 * 
 	 * ## Example JSON
-	 * ```
+	 * ```json
 	 * {
 	 * "resolved": "false",
 	 * "level": "CRITICAL",
@@ -6166,6 +7005,52 @@ export interface ListUsers {
 export interface ListVariables {
 }
 
+/** The container to follow logs for over the `/ws/log` websocket. */
+export type LogStreamTarget = 
+	/**
+	 * Any container on a Server.
+	 * Requires Read permissions on the Server.
+	 */
+	| { type: "Container", params: {
+	/** Server id or name */
+	server: string;
+	/** The container name */
+	container: string;
+}}
+	/**
+	 * The Deployment container.
+	 * Requires Read permissions on the Deployment.
+	 */
+	| { type: "Deployment", params: {
+	/** Deployment id or name */
+	deployment: string;
+}}
+	/**
+	 * A Stack service.
+	 * Requires Read permissions on the Stack.
+	 */
+	| { type: "StackService", params: {
+	/** Stack id or name */
+	stack: string;
+	/** The service name */
+	service: string;
+}};
+
+/**
+ * Sent after logging in to the `/ws/log` websocket,
+ * to select the logs to follow.
+ */
+export interface LogStreamQuery {
+	target: LogStreamTarget;
+	/**
+	 * The number of past lines to send before following.
+	 * Max: 5000.
+	 */
+	tail: U64;
+	/** Enable `--timestamps` */
+	timestamps?: boolean;
+}
+
 /**
  * Login as a local user. Will fail if the users credentials don't match
  * any local user.
@@ -6184,6 +7069,22 @@ export interface NameAndId {
 	id: string;
 }
 
+/** Configuration for an ntfy alerter. */
+export interface NtfyAlerterEndpoint {
+	/**
+	 * The ntfy server url.
+	 * Default: `https://ntfy.sh`
+	 */
+	url: string;
+	/** The topic to publish to. */
+	topic: string;
+	/**
+	 * Optional access token for protected topics.
+	 * Must reference a core secret or variable, eg. `[[NTFY_TOKEN]]`.
+	 */
+	token?: string;
+}
+
 /** Pauses all containers on the target server. Response: [Update] */
 export interface PauseAllContainers {
 	/** Name or id */
@@ -6361,6 +7262,19 @@ export interface PushRecentlyViewed {
 	resource: ResourceTarget;
 }
 
+/** Configuration for a Pushover alerter. */
+export interface PushoverAlerterEndpoint {
+	/**
+	 * The Pushover application API token.
+	 * Must reference a core secret or variable, eg. `[[PUSHOVER_TOKEN]]`.
+	 */
+	token?: string;
+	/** The Pushover user (or group) key to send to. */
+	user?: string;
+	/** Optionally send only to a specific device of the user. */
+	device?: string;
+}
+
 /** Trigger a refresh of the cached latest hash and message. */
 export interface RefreshBuildCache {
 	/** Id or name */
@@ -6624,6 +7538,23 @@ export interface RestartStack {
 	service?: string;
 }
 
+/**
+ * Redeploys the image and config of an earlier successful deploy
+ * of the target deployment. Response: [Update]
+ * 
+ * Variables and secrets are interpolated with their current values.
+ */
+export interface RollbackDeployment {
+	/** Name or id */
+	deployment: string;
+	/**
+	 * The id of the successful `Deploy` / `RollbackDeployment` Update to redeploy.
+	 * If not provided, uses the last successful deploy
+	 * before the latest one.
+	 */
+	update_id?: string;
+}
+
 /** Runs the target Action. Response: [Update] */
 export interface RunAction {
 	/** Id or name */
@@ -6765,6 +7696,8 @@ export interface ServerHealth {
 	cpu: ServerHealthState;
 	mem: ServerHealthState;
 	disks: Record<string, ServerHealthState>;
+	network?: ServerHealthState;
+	load?: ServerHealthState;
 }
 
 /**
@@ -6891,6 +7824,14 @@ export interface TotalDiskUsage {
 	total_gb: number;
 }
 
+/** Info for the all network interfaces combined. */
+export interface TotalNetworkUsage {
+	/** Bytes received per second */
+	rx_bytes_per_sec: number;
+	/** Bytes transmitted per second */
+	tx_bytes_per_sec: number;
+}
+
 /** Unpauses all containers on the target server. Response: [Update] */
 export interface UnpauseAllContainers {
 	/** Name or id */
@@ -6935,6 +7876,17 @@ export interface UnpauseStack {
 	service?: string;
 }
 
+/**
+ * Clear the pinned Periphery certificate fingerprint,
+ * so the next certificate Periphery presents is pinned.
+ * Use after rotating the Periphery certificate.
+ * Response: [Update].
+ */
+export interface UnpinServerCertificate {
+	/** Server Id or name */
+	server: string;
+}
+
 /**
  * Update the action at the given id, and return the updated action.
  * Response: [Action].
@@ -6952,6 +7904,30 @@ export interface UpdateAction {
 	config: _PartialActionConfig;
 }
 
+/**
+ * **Admin only.** Update an alert silence. Response: [AlertSilence].
+ * 
+ * Note. Set `end_ts` to end a silence early.
+ */
+export interface UpdateAlertSilence {
+	/** The id of the silence to update. */
+	id: string;
+	/** A comment explaining the silence. */
+	comment?: string;
+	/** Only silence alerts on these resources. */
+	targets?: ResourceTarget[];
+	/** Only silence alerts on resources with any of these tags (id or name). */
+	tags?: string[];
+	/** Only silence these alert types. */
+	alert_types?: AlertData["type"][];
+	/** Only silence alerts with these severity levels. */
+	levels?: SeverityLevel[];
+	/** Unix timestamp in milliseconds the silence starts. */
+	start_ts: I64;
+	/** Unix timestamp in milliseconds the silence ends. */
+	end_ts: I64;
+}
+
 /**
  * Update the alerter at the given id, and return the updated alerter. Response: [Alerter].
  * 
@@ -7343,6 +8319,7 @@ export type ExecuteRequest =
 	| { type: "Deploy", params: Deploy }
 	| { type: "BatchDeploy", params: BatchDeploy }
 	| { type: "PullDeployment", params: PullDeployment }
+	| { type: "RollbackDeployment", params: RollbackDeployment }
 	| { type: "StartDeployment", params: StartDeployment }
 	| { type: "RestartDeployment", params: RestartDeployment }
 	| { type: "PauseDeployment", params: PauseDeployment }
@@ -7386,6 +8363,15 @@ export type ImageRegistryLegacy1_14 =
 	/** Push the image to a standard image registry (any domain) */
 	| { type: "Standard", params: ImageRegistryConfig };
 
+/** The messages sent over the `/ws/log` websocket. */
+export type LogStreamMessage = 
+	/** A line of the container stdout */
+	| { type: "Stdout", data: string }
+	/** A line of the container stderr */
+	| { type: "Stderr", data: string }
+	/** The stream failed, and will be closed. */
+	| { type: "Error", data: string };
+
 export type ReadRequest = 
 	| { type: "GetVersion", params: GetVersion }
 	| { type: "GetCoreInfo", params: GetCoreInfo }
@@ -7444,6 +8430,7 @@ export type ReadRequest =
 	| { type: "GetDeploymentContainer", params: GetDeploymentContainer }
 	| { type: "GetDeploymentActionState", params: GetDeploymentActionState }
 	| { type: "GetDeploymentStats", params: GetDeploymentStats }
+	| { type: "GetHistoricalContainerStats", params: GetHistoricalContainerStats }
 	| { type: "GetDeploymentLog", params: GetDeploymentLog }
 	| { type: "SearchDeploymentLog", params: SearchDeploymentLog }
 	| { type: "ListDeployments", params: ListDeployments }
@@ -7497,6 +8484,8 @@ export type ReadRequest =
 	| { type: "ListUpdates", params: ListUpdates }
 	| { type: "ListAlerts", params: ListAlerts }
 	| { type: "GetAlert", params: GetAlert }
+	| { type: "ListAlertSilences", params: ListAlertSilences }
+	| { type: "GetAlertSilence", params: GetAlertSilence }
 	| { type: "GetSystemInformation", params: GetSystemInformation }
 	| { type: "GetSystemStats", params: GetSystemStats }
 	| { type: "ListSystemProcesses", params: ListSystemProcesses }
@@ -7507,6 +8496,20 @@ export type ReadRequest =
 	| { type: "GetDockerRegistryAccount", params: GetDockerRegistryAccount }
 	| { type: "ListDockerRegistryAccounts", params: ListDockerRegistryAccounts };
 
+/**
+ * Text messages sent by the client over terminal websockets.
+ * 
+ * Binary messages from the client are written to the terminal stdin,
+ * and binary messages from Core are the terminal output.
+ * Text messages from Core are errors, sent before closing.
+ */
+export type TerminalMessage = 
+	/** Resize the terminal */
+	| { type: "Resize", params: {
+	rows: number;
+	cols: number;
+}};
+
 export type UserRequest = 
 	| { type: "PushRecentlyViewed", params: PushRecentlyViewed }
 	| { type: "SetLastSeenUpdate", params: SetLastSeenUpdate }
@@ -7536,6 +8539,9 @@ export type WriteRequest =
 	| { type: "DeleteServer", params: DeleteServer }
 	| { type: "UpdateServer", params: UpdateServer }
 	| { type: "RenameServer", params: RenameServer }
+	| { type: "UnpinServerCertificate", params: UnpinServerCertificate }
+	| { type: "CreateServerEnrollmentToken", params: CreateServerEnrollmentToken }
+	| { type: "DeleteServerEnrollmentToken", params: DeleteServerEnrollmentToken }
 	| { type: "CreateNetwork", params: CreateNetwork }
 	| { type: "CreateDeployment", params: CreateDeployment }
 	| { type: "CopyDeployment", params: CopyDeployment }
@@ -7607,6 +8613,10 @@ export type WriteRequest =
 	| { type: "DeleteTag", params: DeleteTag }
 	| { type: "RenameTag", params: RenameTag }
 	| { type: "UpdateTagsOnResource", params: UpdateTagsOnResource }
+	| { type: "AcknowledgeAlert", params: AcknowledgeAlert }
+	| { type: "CreateAlertSilence", params: CreateAlertSilence }
+	| { type: "UpdateAlertSilence", params: UpdateAlertSilence }
+	| { type: "DeleteAlertSilence", params: DeleteAlertSilence }
 	| { type: "CreateVariable", params: CreateVariable }
 	| { type: "UpdateVariableValue", params: UpdateVariableValue }
 	| { type: "UpdateVariableDescription", params: UpdateVariableDescription }
@@ -19,6 +19,7 @@ pub mod git;
 pub mod image;
 pub mod network;
 pub mod stats;
+pub mod stream;
 pub mod volume;
 
 //
@@ -0,0 +1,82 @@
+use komodo_client::entities::docker::container::HealthStatusEnum;
+use serde::{Deserialize, Serialize};
+
+/// Sent as the first message over the periphery `/ws/log` websocket,
+/// selecting the logs to follow. Periphery then sends
+/// [LogStreamMessage][komodo_client::ws::LogStreamMessage]s
+/// until the log stream ends.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", content = "params")]
+pub enum FollowLog {
+  /// `docker logs -f`
+  Container {
+    name: String,
+    tail: u64,
+    timestamps: bool,
+  },
+  /// `docker compose logs -f`
+  ComposeService {
+    project: String,
+    service: String,
+    tail: u64,
+    timestamps: bool,
+  },
+}
+
+/// Sent as the first message over the periphery `/ws/exec` websocket.
+/// The command is run under a pty, with `docker exec -it`.
+/// Binary messages are then forwarded to / from the terminal,
+/// and text messages are [TerminalMessage][komodo_client::ws::TerminalMessage]s.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", content = "params")]
+pub enum ExecTerminal {
+  /// `docker exec -it`
+  Container {
+    name: String,
+    command: String,
+    rows: u16,
+    cols: u16,
+  },
+  /// `docker compose exec`
+  ComposeService {
+    project: String,
+    service: String,
+    command: String,
+    rows: u16,
+    cols: u16,
+  },
+}
+
+/// Sent as the first message over the periphery `/ws/terminal` websocket.
+/// Runs the configured `host_terminal_shell` on the host under a pty,
+/// unless `disable_host_terminal` is set.
+/// The protocol is then the same as [ExecTerminal].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HostTerminal {
+  pub rows: u16,
+  pub cols: u16,
+}
+
+/// Sent by periphery over the `/ws/events` websocket,
+/// or over the reverse connection, as containers change state.
+/// Core applies these to its status cache between polls.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ContainerEvent {
+  /// The container name.
+  pub name: String,
+  pub action: ContainerEventAction,
+  /// The health reported by [ContainerEventAction::HealthStatus] events.
+  pub health: Option<HealthStatusEnum>,
+  /// Unix timestamp in milliseconds.
+  pub ts: i64,
+}
+
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq,
+)]
+pub enum ContainerEventAction {
+  Start,
+  Stop,
+  Die,
+  HealthStatus,
+}
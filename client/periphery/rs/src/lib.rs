@@ -1,28 +1,50 @@
-use std::{sync::OnceLock, time::Duration};
+use std::{sync::Arc, time::Duration};
 
-use anyhow::Context;
+use anyhow::{anyhow, Context};
+use futures::SinkExt;
+use komodo_client::entities::komodo_timestamp;
 use reqwest::StatusCode;
 use resolver_api::HasResponse;
 use serde_json::json;
 use serror::deserialize_error;
+use tls::{
+  certificate_fingerprint, periphery_http_client,
+  periphery_tls_config,
+};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{
+  connect_async_tls_with_config,
+  tungstenite::{client::IntoClientRequest, Message},
+  Connector, MaybeTlsStream, WebSocketStream,
+};
 
 pub mod api;
+pub mod signature;
+pub mod tunnel;
 
-fn periphery_http_client() -> &'static reqwest::Client {
-  static PERIPHERY_HTTP_CLIENT: OnceLock<reqwest::Client> =
-    OnceLock::new();
-  PERIPHERY_HTTP_CLIENT.get_or_init(|| {
-    reqwest::Client::builder()
-      // Use to allow communication with Periphery self-signed certs.
-      .danger_accept_invalid_certs(true)
-      .build()
-      .expect("Failed to build Periphery http client")
-  })
-}
+mod tls;
+
+pub use tls::set_client_identity;
+pub use tokio_tungstenite::tungstenite;
+
+pub type PeripheryWebSocket =
+  WebSocketStream<MaybeTlsStream<TcpStream>>;
 
 pub struct PeripheryClient {
-  address: String,
+  transport: Transport,
   passkey: String,
+  /// Also send the passkey itself in the authorization header.
+  passkey_header: bool,
+  /// The pinned certificate fingerprint, or empty.
+  /// Checked during the TLS handshake, before anything is sent.
+  certificate_fingerprint: String,
+}
+
+enum Transport {
+  /// Core calls periphery at the address.
+  Http(String),
+  /// Periphery has dialed out to core.
+  Tunnel(tunnel::PeripheryTunnel),
 }
 
 impl PeripheryClient {
@@ -31,8 +53,127 @@ impl PeripheryClient {
     passkey: impl Into<String>,
   ) -> PeripheryClient {
     PeripheryClient {
-      address: address.into(),
+      transport: Transport::Http(address.into()),
       passkey: passkey.into(),
+      passkey_header: true,
+      certificate_fingerprint: String::new(),
+    }
+  }
+
+  /// Requests are always signed with the passkey.
+  /// Disable to stop also sending the passkey in the authorization header,
+  /// once periphery verifies signatures.
+  pub fn with_passkey_header(
+    mut self,
+    enabled: bool,
+  ) -> PeripheryClient {
+    self.passkey_header = enabled;
+    self
+  }
+
+  /// Refuse to talk to periphery unless it presents
+  /// a certificate with this fingerprint. Empty to not check.
+  pub fn with_pinned_certificate(
+    mut self,
+    fingerprint: impl Into<String>,
+  ) -> PeripheryClient {
+    self.certificate_fingerprint = fingerprint.into();
+    self
+  }
+
+  /// Sends requests over a periphery reverse connection.
+  /// The connection is already authenticated,
+  /// so no passkey is needed.
+  pub fn tunneled(
+    tunnel: tunnel::PeripheryTunnel,
+  ) -> PeripheryClient {
+    PeripheryClient {
+      transport: Transport::Tunnel(tunnel),
+      passkey: String::new(),
+      passkey_header: false,
+      certificate_fingerprint: String::new(),
+    }
+  }
+
+  /// The fingerprint of the certificate periphery presents.
+  /// None if periphery isn't using https,
+  /// or is connected over the reverse connection.
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn certificate_fingerprint(
+    &self,
+  ) -> anyhow::Result<Option<String>> {
+    let Transport::Http(address) = &self.transport else {
+      return Ok(None);
+    };
+    let body = serde_json::to_vec(&json!({
+      "type": api::GetHealth::req_type(),
+      "params": api::GetHealth {}
+    }))
+    .context("failed to serialize GetHealth")?;
+    // Not pinned, this is used to find the fingerprint to pin.
+    let mut req = periphery_http_client("")?
+      .post(address)
+      .header("content-type", "application/json")
+      .timeout(Duration::from_secs(1));
+    for (name, value) in self.auth_headers("POST", "/", &body) {
+      req = req.header(name, value);
+    }
+    let res = req
+      .body(body)
+      .send()
+      .await
+      .context("failed at request to periphery")?;
+    Ok(
+      res
+        .extensions()
+        .get::<reqwest::tls::TlsInfo>()
+        .and_then(|info| info.peer_certificate())
+        .map(certificate_fingerprint),
+    )
+  }
+
+  /// Signs the request, so periphery can verify it came from core
+  /// without the passkey being sent.
+  fn auth_headers(
+    &self,
+    method: &str,
+    path: &str,
+    body: &[u8],
+  ) -> Vec<(&'static str, String)> {
+    let timestamp = komodo_timestamp();
+    let nonce = uuid::Uuid::new_v4().to_string();
+    let signature = signature::sign_request(
+      &self.passkey,
+      method,
+      path,
+      timestamp,
+      &nonce,
+      body,
+    );
+    let mut headers = vec![
+      (signature::TIMESTAMP_HEADER, timestamp.to_string()),
+      (signature::NONCE_HEADER, nonce),
+      (signature::SIGNATURE_HEADER, signature),
+    ];
+    if self.passkey_header {
+      headers.push(("authorization", self.passkey.clone()));
+    }
+    headers
+  }
+
+  /// The pin can only be checked over https.
+  fn check_pinned_address(
+    &self,
+    address: &str,
+  ) -> anyhow::Result<()> {
+    if self.certificate_fingerprint.is_empty()
+      || address.starts_with("https://")
+    {
+      Ok(())
+    } else {
+      Err(anyhow!(
+        "periphery has a pinned certificate, but address {address} is not https"
+      ))
     }
   }
 
@@ -60,6 +201,102 @@ impl PeripheryClient {
     Ok(())
   }
 
+  /// Follow a container log over websocket.
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn follow_log(
+    &self,
+    request: &api::stream::FollowLog,
+  ) -> anyhow::Result<PeripheryWebSocket> {
+    let mut ws = self.connect_websocket("/ws/log").await?;
+    let request = serde_json::to_string(request)
+      .context("failed to serialize FollowLog")?;
+    ws.send(Message::Text(request))
+      .await
+      .context("failed to send FollowLog to periphery")?;
+    Ok(ws)
+  }
+
+  /// Open an interactive terminal in a container over websocket.
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn exec_terminal(
+    &self,
+    request: &api::stream::ExecTerminal,
+  ) -> anyhow::Result<PeripheryWebSocket> {
+    let mut ws = self.connect_websocket("/ws/exec").await?;
+    let request = serde_json::to_string(request)
+      .context("failed to serialize ExecTerminal")?;
+    ws.send(Message::Text(request))
+      .await
+      .context("failed to send ExecTerminal to periphery")?;
+    Ok(ws)
+  }
+
+  /// Open an interactive terminal on the host over websocket.
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn host_terminal(
+    &self,
+    request: &api::stream::HostTerminal,
+  ) -> anyhow::Result<PeripheryWebSocket> {
+    let mut ws = self.connect_websocket("/ws/terminal").await?;
+    let request = serde_json::to_string(request)
+      .context("failed to serialize HostTerminal")?;
+    ws.send(Message::Text(request))
+      .await
+      .context("failed to send HostTerminal to periphery")?;
+    Ok(ws)
+  }
+
+  /// Subscribe to container state changes over websocket.
+  /// Periphery then sends [api::stream::ContainerEvent]s.
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn container_events(
+    &self,
+  ) -> anyhow::Result<PeripheryWebSocket> {
+    self.connect_websocket("/ws/events").await
+  }
+
+  #[tracing::instrument(level = "debug", skip(self))]
+  async fn connect_websocket(
+    &self,
+    path: &str,
+  ) -> anyhow::Result<PeripheryWebSocket> {
+    let Transport::Http(address) = &self.transport else {
+      return Err(anyhow!(
+        "websocket streams are not supported over periphery reverse connections"
+      ));
+    };
+    self.check_pinned_address(address)?;
+    let address = format!(
+      "{}{path}",
+      address.trim_end_matches('/').replacen("http", "ws", 1)
+    );
+    let mut req = address
+      .as_str()
+      .into_client_request()
+      .context("failed to build periphery websocket request")?;
+    for (name, value) in self.auth_headers("GET", path, &[]) {
+      req.headers_mut().insert(
+        name,
+        value.parse().with_context(|| {
+          format!("failed to parse {name} header")
+        })?,
+      );
+    }
+    let (ws, _) = connect_async_tls_with_config(
+      req,
+      None,
+      false,
+      Some(Connector::Rustls(Arc::new(periphery_tls_config(
+        &self.certificate_fingerprint,
+      )?))),
+    )
+    .await
+    .with_context(|| {
+      format!("failed to connect to periphery websocket at {address}")
+    })?;
+    Ok(ws)
+  }
+
   #[tracing::instrument(level = "debug", skip(self))]
   async fn request_inner<T: HasResponse>(
     &self,
@@ -70,13 +307,44 @@ impl PeripheryClient {
     tracing::trace!(
       "sending request | type: {req_type} | body: {request:?}"
     );
-    let mut req = periphery_http_client()
-      .post(&self.address)
-      .json(&json!({
-        "type": req_type,
-        "params": request
-      }))
-      .header("authorization", &self.passkey);
+    let body = json!({
+      "type": req_type,
+      "params": request
+    });
+    let address = match &self.transport {
+      Transport::Http(address) => address,
+      Transport::Tunnel(tunnel) => {
+        let res = tunnel
+          .request(body, timeout)
+          .await
+          .context("failed at request to periphery")?;
+        tracing::debug!(
+          "got tunnel response | type: {req_type} | ok: {}",
+          res.ok
+        );
+        return if res.ok {
+          serde_json::from_str(&res.body).with_context(|| format!(
+            "failed to parse response to json | type: {req_type} | body: {request:?}"
+          ))
+        } else {
+          Err(
+            deserialize_error(res.body)
+              .context("request to periphery failed"),
+          )
+        };
+      }
+    };
+    self.check_pinned_address(address)?;
+    let body = serde_json::to_vec(&body)
+      .context("failed to serialize request body")?;
+    let mut req =
+      periphery_http_client(&self.certificate_fingerprint)?
+        .post(address)
+        .header("content-type", "application/json");
+    for (name, value) in self.auth_headers("POST", "/", &body) {
+      req = req.header(name, value);
+    }
+    let mut req = req.body(body);
     if let Some(timeout) = timeout {
       req = req.timeout(timeout);
     }
@@ -0,0 +1,69 @@
+//! Core signs each periphery request with an HMAC keyed by the passkey,
+//! so the passkey itself doesn't need to be sent.
+//! The timestamp and nonce let periphery reject stale or replayed requests.
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+pub const TIMESTAMP_HEADER: &str = "x-komodo-timestamp";
+pub const NONCE_HEADER: &str = "x-komodo-nonce";
+pub const SIGNATURE_HEADER: &str = "x-komodo-signature";
+
+/// Signed requests are rejected if the timestamp
+/// differs from the periphery clock by more than this.
+pub const MAX_TIMESTAMP_SKEW_MS: i64 = 60_000;
+
+/// HMAC-SHA256 over `{method}.{path}.{timestamp}.{nonce}.{body}`.
+/// The method and path stop a signature being reused for another route,
+/// eg. a log websocket upgrade for a terminal.
+fn request_mac(
+  passkey: &str,
+  method: &str,
+  path: &str,
+  timestamp: i64,
+  nonce: &str,
+  body: &[u8],
+) -> Hmac<Sha256> {
+  // unwrap ok: hmac accepts keys of any length
+  let mut mac =
+    Hmac::<Sha256>::new_from_slice(passkey.as_bytes()).unwrap();
+  mac.update(
+    format!("{method}.{path}.{timestamp}.{nonce}.").as_bytes(),
+  );
+  mac.update(body);
+  mac
+}
+
+/// Returns the hex encoded signature.
+pub fn sign_request(
+  passkey: &str,
+  method: &str,
+  path: &str,
+  timestamp: i64,
+  nonce: &str,
+  body: &[u8],
+) -> String {
+  hex::encode(
+    request_mac(passkey, method, path, timestamp, nonce, body)
+      .finalize()
+      .into_bytes(),
+  )
+}
+
+/// Checks the hex encoded signature in constant time.
+pub fn verify_request(
+  passkey: &str,
+  method: &str,
+  path: &str,
+  timestamp: i64,
+  nonce: &str,
+  body: &[u8],
+  signature: &str,
+) -> bool {
+  let Ok(signature) = hex::decode(signature) else {
+    return false;
+  };
+  request_mac(passkey, method, path, timestamp, nonce, body)
+    .verify_slice(&signature)
+    .is_ok()
+}
@@ -0,0 +1,180 @@
+use std::{
+  collections::HashMap,
+  sync::{Arc, Mutex, OnceLock},
+};
+
+use anyhow::{anyhow, Context};
+use rustls::{
+  client::danger::{
+    HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier,
+  },
+  crypto::{ring, CryptoProvider},
+  pki_types::{
+    pem::PemObject, CertificateDer, PrivatePkcs8KeyDer, ServerName,
+    UnixTime,
+  },
+  ClientConfig, DigitallySignedStruct, SignatureScheme,
+};
+use sha2::{Digest, Sha256};
+
+/// The client certificate / key pem core presents to periphery.
+static CLIENT_IDENTITY: OnceLock<(Vec<u8>, Vec<u8>)> =
+  OnceLock::new();
+
+/// Sets the client certificate core presents to periphery,
+/// for periphery to verify core with mutual TLS.
+/// Must be called before the first periphery request.
+pub fn set_client_identity(
+  cert_pem: Vec<u8>,
+  key_pem: Vec<u8>,
+) -> anyhow::Result<()> {
+  parse_client_identity(&cert_pem, &key_pem)?;
+  CLIENT_IDENTITY
+    .set((cert_pem, key_pem))
+    .map_err(|_| anyhow!("periphery client identity already set"))
+}
+
+fn parse_client_identity(
+  cert_pem: &[u8],
+  key_pem: &[u8],
+) -> anyhow::Result<(
+  Vec<CertificateDer<'static>>,
+  PrivatePkcs8KeyDer<'static>,
+)> {
+  let certs = CertificateDer::pem_slice_iter(cert_pem)
+    .collect::<Result<Vec<_>, _>>()
+    .context("invalid periphery client certificate")?;
+  let key = PrivatePkcs8KeyDer::from_pem_slice(key_pem)
+    .context("invalid periphery client key")?;
+  Ok((certs, key))
+}
+
+/// Http client for periphery presenting a certificate with
+/// the pinned fingerprint, or any certificate if empty.
+/// Clients are reused per fingerprint.
+pub fn periphery_http_client(
+  fingerprint: &str,
+) -> anyhow::Result<reqwest::Client> {
+  static PERIPHERY_HTTP_CLIENTS: OnceLock<
+    Mutex<HashMap<String, reqwest::Client>>,
+  > = OnceLock::new();
+  let mut clients = PERIPHERY_HTTP_CLIENTS
+    .get_or_init(Default::default)
+    .lock()
+    .unwrap();
+  if let Some(client) = clients.get(fingerprint) {
+    return Ok(client.clone());
+  }
+  let client = reqwest::Client::builder()
+    .use_preconfigured_tls(periphery_tls_config(fingerprint)?)
+    .tls_info(true)
+    .build()
+    .context("failed to build periphery http client")?;
+  clients.insert(fingerprint.to_string(), client.clone());
+  Ok(client)
+}
+
+/// Tls config which fails the handshake unless periphery presents
+/// a certificate with the pinned fingerprint, or any certificate if empty.
+pub fn periphery_tls_config(
+  fingerprint: &str,
+) -> anyhow::Result<ClientConfig> {
+  let provider = Arc::new(ring::default_provider());
+  let builder = ClientConfig::builder_with_provider(provider.clone())
+    .with_safe_default_protocol_versions()
+    .context("failed to set periphery tls protocol versions")?
+    .dangerous()
+    // Periphery certs are usually self-signed.
+    // These are instead verified by pinning their fingerprint.
+    .with_custom_certificate_verifier(Arc::new(
+      PinnedCertificateVerifier {
+        fingerprint: fingerprint.to_string(),
+        provider,
+      },
+    ));
+  match CLIENT_IDENTITY.get() {
+    Some((cert, key)) => {
+      let (certs, key) = parse_client_identity(cert, key)?;
+      builder
+        .with_client_auth_cert(certs, key.into())
+        .context("invalid periphery client identity")
+    }
+    None => Ok(builder.with_no_client_auth()),
+  }
+}
+
+#[derive(Debug)]
+struct PinnedCertificateVerifier {
+  /// The pinned certificate fingerprint, or empty.
+  fingerprint: String,
+  provider: Arc<CryptoProvider>,
+}
+
+impl ServerCertVerifier for PinnedCertificateVerifier {
+  fn verify_server_cert(
+    &self,
+    end_entity: &CertificateDer<'_>,
+    _intermediates: &[CertificateDer<'_>],
+    _server_name: &ServerName<'_>,
+    _ocsp_response: &[u8],
+    _now: UnixTime,
+  ) -> Result<ServerCertVerified, rustls::Error> {
+    if self.fingerprint.is_empty() {
+      return Ok(ServerCertVerified::assertion());
+    }
+    let fingerprint = certificate_fingerprint(end_entity);
+    if fingerprint == self.fingerprint {
+      Ok(ServerCertVerified::assertion())
+    } else {
+      Err(rustls::Error::General(format!(
+        "periphery certificate fingerprint {fingerprint} does not match pinned fingerprint {}",
+        self.fingerprint
+      )))
+    }
+  }
+
+  fn verify_tls12_signature(
+    &self,
+    message: &[u8],
+    cert: &CertificateDer<'_>,
+    dss: &DigitallySignedStruct,
+  ) -> Result<HandshakeSignatureValid, rustls::Error> {
+    rustls::crypto::verify_tls12_signature(
+      message,
+      cert,
+      dss,
+      &self.provider.signature_verification_algorithms,
+    )
+  }
+
+  fn verify_tls13_signature(
+    &self,
+    message: &[u8],
+    cert: &CertificateDer<'_>,
+    dss: &DigitallySignedStruct,
+  ) -> Result<HandshakeSignatureValid, rustls::Error> {
+    rustls::crypto::verify_tls13_signature(
+      message,
+      cert,
+      dss,
+      &self.provider.signature_verification_algorithms,
+    )
+  }
+
+  fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+    self
+      .provider
+      .signature_verification_algorithms
+      .supported_schemes()
+  }
+}
+
+/// The SHA-256 fingerprint of the DER encoded certificate,
+/// formatted like `openssl x509 -noout -fingerprint -sha256`.
+pub fn certificate_fingerprint(der: &[u8]) -> String {
+  Sha256::digest(der)
+    .iter()
+    .map(|byte| format!("{byte:02X}"))
+    .collect::<Vec<_>>()
+    .join(":")
+}
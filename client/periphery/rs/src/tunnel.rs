@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, oneshot};
+
+use crate::api::stream::ContainerEvent;
+
+/// Messages sent over the reverse connection,
+/// which periphery dials out to core at `/ws/periphery`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", content = "data")]
+pub enum TunnelMessage {
+  /// Core -> Periphery. The request is the same json
+  /// sent as the body of http requests to periphery.
+  Request { id: u64, request: serde_json::Value },
+  /// Periphery -> Core.
+  Response { id: u64, response: TunnelResponse },
+  /// Periphery -> Core, pushed as containers change state.
+  Event { event: ContainerEvent },
+}
+
+/// The result of a tunneled request.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TunnelResponse {
+  /// Whether the request succeeded.
+  pub ok: bool,
+  /// The json response on success,
+  /// or the serialized error on failure.
+  pub body: String,
+}
+
+/// A request waiting to be sent over the tunnel.
+#[derive(Debug)]
+pub struct TunnelRequest {
+  pub request: serde_json::Value,
+  pub response: oneshot::Sender<TunnelResponse>,
+}
+
+/// Handle to send requests over a periphery reverse connection.
+/// The connection itself is driven by the receiver of [TunnelRequest]s.
+#[derive(Debug, Clone)]
+pub struct PeripheryTunnel {
+  requests: mpsc::Sender<TunnelRequest>,
+}
+
+impl PeripheryTunnel {
+  pub fn new(
+    capacity: usize,
+  ) -> (PeripheryTunnel, mpsc::Receiver<TunnelRequest>) {
+    let (requests, rx) = mpsc::channel(capacity);
+    (PeripheryTunnel { requests }, rx)
+  }
+
+  pub fn is_closed(&self) -> bool {
+    self.requests.is_closed()
+  }
+
+  /// Whether both handles are for the same connection.
+  pub fn same_tunnel(&self, other: &PeripheryTunnel) -> bool {
+    self.requests.same_channel(&other.requests)
+  }
+
+  pub async fn request(
+    &self,
+    request: serde_json::Value,
+    timeout: Option<Duration>,
+  ) -> anyhow::Result<TunnelResponse> {
+    let (tx, rx) = oneshot::channel();
+    self
+      .requests
+      .send(TunnelRequest {
+        request,
+        response: tx,
+      })
+      .await
+      .map_err(|_| anyhow!("periphery connection is closed"))?;
+    let response = async {
+      rx.await
+        .context("periphery connection closed before response")
+    };
+    match timeout {
+      Some(timeout) => tokio::time::timeout(timeout, response)
+        .await
+        .context("timed out waiting for periphery response")?,
+      None => response.await,
+    }
+  }
+}
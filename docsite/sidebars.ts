@@ -63,6 +63,7 @@ const sidebars: SidebarsConfig = {
     "permissioning",
     "sync-resources",
     "webhooks",
+    "metrics",
     "version-upgrades",
     "api",
     "development"
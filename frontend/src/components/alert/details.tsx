@@ -1,5 +1,5 @@
 import { ResourceLink } from "@components/resources/common";
-import { useRead } from "@lib/hooks";
+import { useInvalidate, useRead, useWrite } from "@lib/hooks";
 import { UsableResource } from "@types";
 import { Button } from "@ui/button";
 import {
@@ -87,6 +87,14 @@ export const AlertDetailsDialogContent = ({
                   <div className="flex gap-2 text-muted-foreground">
                     level: <AlertLevel level={alert.level} />
                   </div>
+
+                  {/** Acknowledged */}
+                  {!alert.resolved && (
+                    <AcknowledgeAlert
+                      id={alert._id?.$oid}
+                      acknowledged={alert.acknowledged}
+                    />
+                  )}
                 </div>
 
                 {/** Alert data */}
@@ -103,3 +111,29 @@ export const AlertDetailsDialogContent = ({
     )}
   </>
 );
+
+const AcknowledgeAlert = ({
+  id,
+  acknowledged,
+}: {
+  id: string | undefined;
+  acknowledged: boolean | undefined;
+}) => {
+  const invalidate = useInvalidate();
+  const { mutate, isPending } = useWrite("AcknowledgeAlert", {
+    onSuccess: () => invalidate(["ListAlerts"], ["GetAlert"]),
+  });
+  if (acknowledged) {
+    return <div className="text-muted-foreground">ACKNOWLEDGED</div>;
+  }
+  return (
+    <Button
+      variant="secondary"
+      size="sm"
+      disabled={!id || isPending}
+      onClick={() => id && mutate({ id })}
+    >
+      Acknowledge
+    </Button>
+  );
+};
@@ -47,6 +47,9 @@ export const AlertsTable = ({
                 )}
               >
                 {row.original.resolved ? "RESOLVED" : "OPEN"}
+                {row.original.silenced && (
+                  <span className="text-muted-foreground"> (SILENCED)</span>
+                )}
               </div>
             );
           },
@@ -103,6 +103,24 @@ export const ActionConfig = ({ id }: { id: string }) => {
               },
             },
           },
+          {
+            label: "Schedule",
+            description: "Run this Action on a CRON schedule.",
+            components: {
+              schedule_enabled: true,
+              schedule: {
+                description:
+                  "CRON expression: second (optional), minute, hour, day-of-month, month, day-of-week.",
+                placeholder: "0 0 4 * * *",
+              },
+              schedule_timezone: {
+                label: "Timezone",
+                description:
+                  "TZ identifier to evaluate the schedule in. Uses the Core timezone if empty.",
+                placeholder: "America/New_York",
+              },
+            },
+          },
           {
             label: "Webhook",
             description: `Configure your ${webhook_integration}-style repo provider to send webhooks to Komodo`,
@@ -10,9 +10,14 @@ const ALERT_TYPES: Types.AlertData["type"][] = [
   "ServerCpu",
   "ServerMem",
   "ServerDisk",
+  "ServerNetwork",
+  "ServerLoad",
   // State change
   "ContainerStateChange",
   "StackStateChange",
+  // Container health
+  "ContainerUnhealthy",
+  "ContainerRestartLoop",
   // Updates
   "DeploymentImageUpdateAvailable",
   "StackImageUpdateAvailable",
@@ -21,6 +26,9 @@ const ALERT_TYPES: Types.AlertData["type"][] = [
   "ResourceSyncPendingUpdates",
   "BuildFailed",
   "RepoBuildFailed",
+  "ProcedureFailed",
+  "ActionFailed",
+  "ResourceSyncFailed",
 ];
 
 export const AlertTypeConfig = ({
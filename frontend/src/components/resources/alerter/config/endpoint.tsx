@@ -1,4 +1,4 @@
-import { ConfigItem } from "@components/config/util";
+import { ConfigInput, ConfigItem } from "@components/config/util";
 import { MonacoEditor } from "@components/monaco";
 import { Types } from "komodo_client";
 import {
@@ -13,6 +13,10 @@ const ENDPOINT_TYPES: Types.AlerterEndpoint["type"][] = [
   "Custom",
   "Discord",
   "Slack",
+  "Email",
+  "Ntfy",
+  "Gotify",
+  "Pushover",
 ];
 
 export const EndpointConfig = ({
@@ -33,7 +37,7 @@ export const EndpointConfig = ({
       <Select
         value={endpoint.type}
         onValueChange={(type: Types.AlerterEndpoint["type"]) => {
-          set({ type, params: { url: default_url(type) } });
+          set(default_endpoint(type));
         }}
         disabled={disabled}
       >
@@ -48,18 +52,358 @@ export const EndpointConfig = ({
           ))}
         </SelectContent>
       </Select>
+      {endpoint.type === "Email" ? (
+        <EmailEndpointConfig
+          params={endpoint.params}
+          set={(params) => set({ type: "Email", params })}
+          disabled={disabled}
+        />
+      ) : endpoint.type === "Custom" ? (
+        <CustomEndpointConfig
+          params={endpoint.params}
+          set={(params) => set({ type: "Custom", params })}
+          disabled={disabled}
+        />
+      ) : endpoint.type === "Ntfy" ? (
+        <NtfyEndpointConfig
+          params={endpoint.params}
+          set={(params) => set({ type: "Ntfy", params })}
+          disabled={disabled}
+        />
+      ) : endpoint.type === "Gotify" ? (
+        <GotifyEndpointConfig
+          params={endpoint.params}
+          set={(params) => set({ type: "Gotify", params })}
+          disabled={disabled}
+        />
+      ) : endpoint.type === "Pushover" ? (
+        <PushoverEndpointConfig
+          params={endpoint.params}
+          set={(params) => set({ type: "Pushover", params })}
+          disabled={disabled}
+        />
+      ) : (
+        <MonacoEditor
+          value={endpoint.params.url}
+          language={undefined}
+          onValueChange={(url) =>
+            set({ ...endpoint, params: { ...endpoint.params, url } })
+          }
+          readOnly={disabled}
+        />
+      )}
+    </ConfigItem>
+  );
+};
+
+const CUSTOM_METHODS: Types.CustomAlerterMethod[] = [
+  Types.CustomAlerterMethod.Post,
+  Types.CustomAlerterMethod.Put,
+  Types.CustomAlerterMethod.Patch,
+];
+
+const CustomEndpointConfig = ({
+  params,
+  set,
+  disabled,
+}: {
+  params: Types.CustomAlerterEndpoint;
+  set: (params: Types.CustomAlerterEndpoint) => void;
+  disabled: boolean;
+}) => {
+  return (
+    <div className="flex flex-col gap-4">
       <MonacoEditor
-        value={endpoint.params.url}
+        value={params.url}
         language={undefined}
-        onValueChange={(url) =>
-          set({ ...endpoint, params: { ...endpoint.params, url } })
-        }
+        onValueChange={(url) => set({ ...params, url })}
         readOnly={disabled}
       />
-    </ConfigItem>
+      <ConfigItem label="Method">
+        <Select
+          value={params.method ?? Types.CustomAlerterMethod.Post}
+          onValueChange={(method: Types.CustomAlerterMethod) =>
+            set({ ...params, method })
+          }
+          disabled={disabled}
+        >
+          <SelectTrigger className="w-[150px]" disabled={disabled}>
+            <SelectValue />
+          </SelectTrigger>
+          <SelectContent>
+            {CUSTOM_METHODS.map((method) => (
+              <SelectItem key={method} value={method}>
+                {method}
+              </SelectItem>
+            ))}
+          </SelectContent>
+        </Select>
+      </ConfigItem>
+      <ConfigItem
+        label="Headers"
+        description="Extra headers to send, one 'Name: Value' per line. Credential headers must reference a core secret or variable, eg. 'Authorization: Bearer [[WEBHOOK_TOKEN]]'."
+      >
+        <MonacoEditor
+          value={params.headers}
+          language={undefined}
+          onValueChange={(headers) => set({ ...params, headers })}
+          readOnly={disabled}
+        />
+      </ConfigItem>
+      <ConfigItem
+        label="Body Template"
+        description="Handlebars template rendered against the alert. Available: id, severity, target_name, resolved, title, message, link, and the alert fields. Use {{json message}} to embed values in JSON. Leave empty to send the alert as JSON."
+      >
+        <MonacoEditor
+          value={params.body}
+          language={undefined}
+          onValueChange={(body) => set({ ...params, body })}
+          readOnly={disabled}
+        />
+      </ConfigItem>
+    </div>
   );
 };
 
+const SMTP_SECURITY: Types.SmtpSecurity[] = [
+  Types.SmtpSecurity.StartTls,
+  Types.SmtpSecurity.Tls,
+  Types.SmtpSecurity.None,
+];
+
+const EmailEndpointConfig = ({
+  params,
+  set,
+  disabled,
+}: {
+  params: Types.EmailAlerterEndpoint;
+  set: (params: Types.EmailAlerterEndpoint) => void;
+  disabled: boolean;
+}) => {
+  return (
+    <div className="flex flex-col gap-4">
+      <ConfigInput
+        label="Host"
+        placeholder="smtp.example.com"
+        value={params.host}
+        onChange={(host) => set({ ...params, host })}
+        disabled={disabled}
+      />
+      <ConfigInput
+        label="Port"
+        value={params.port}
+        onChange={(port) => set({ ...params, port: Number(port) })}
+        disabled={disabled}
+      />
+      <ConfigItem label="Security">
+        <Select
+          value={params.security}
+          onValueChange={(security: Types.SmtpSecurity) =>
+            set({ ...params, security })
+          }
+          disabled={disabled}
+        >
+          <SelectTrigger className="w-[150px]" disabled={disabled}>
+            <SelectValue />
+          </SelectTrigger>
+          <SelectContent>
+            {SMTP_SECURITY.map((security) => (
+              <SelectItem key={security} value={security}>
+                {security}
+              </SelectItem>
+            ))}
+          </SelectContent>
+        </Select>
+      </ConfigItem>
+      <ConfigInput
+        label="Username"
+        placeholder="Leave empty to skip authentication"
+        value={params.username}
+        onChange={(username) => set({ ...params, username })}
+        disabled={disabled}
+      />
+      <ConfigInput
+        label="Password"
+        description="Reference a core secret or variable. The password itself is not stored on the alerter."
+        placeholder="[[SMTP_PASSWORD]]"
+        value={params.password}
+        onChange={(password) => set({ ...params, password })}
+        disabled={disabled}
+      />
+      <ConfigInput
+        label="From"
+        placeholder="Komodo <komodo@example.com>"
+        value={params.from}
+        onChange={(from) => set({ ...params, from })}
+        disabled={disabled}
+      />
+      <ConfigInput
+        label="To"
+        description="Comma separated list of recipients."
+        placeholder="oncall@example.com"
+        value={params.to?.join(", ")}
+        onChange={(to) =>
+          set({
+            ...params,
+            to: to.split(",").map((to) => to.trim()),
+          })
+        }
+        disabled={disabled}
+      />
+    </div>
+  );
+};
+
+const NtfyEndpointConfig = ({
+  params,
+  set,
+  disabled,
+}: {
+  params: Types.NtfyAlerterEndpoint;
+  set: (params: Types.NtfyAlerterEndpoint) => void;
+  disabled: boolean;
+}) => {
+  return (
+    <div className="flex flex-col gap-4">
+      <ConfigInput
+        label="Server"
+        placeholder="https://ntfy.sh"
+        value={params.url}
+        onChange={(url) => set({ ...params, url })}
+        disabled={disabled}
+      />
+      <ConfigInput
+        label="Topic"
+        placeholder="komodo"
+        value={params.topic}
+        onChange={(topic) => set({ ...params, topic })}
+        disabled={disabled}
+      />
+      <ConfigInput
+        label="Token"
+        description="Access token for protected topics, as a reference to a core secret or variable. Leave empty for public topics."
+        placeholder="[[NTFY_TOKEN]]"
+        value={params.token}
+        onChange={(token) => set({ ...params, token })}
+        disabled={disabled}
+      />
+    </div>
+  );
+};
+
+const GotifyEndpointConfig = ({
+  params,
+  set,
+  disabled,
+}: {
+  params: Types.GotifyAlerterEndpoint;
+  set: (params: Types.GotifyAlerterEndpoint) => void;
+  disabled: boolean;
+}) => {
+  return (
+    <div className="flex flex-col gap-4">
+      <ConfigInput
+        label="Server"
+        placeholder="https://gotify.example.com"
+        value={params.url}
+        onChange={(url) => set({ ...params, url })}
+        disabled={disabled}
+      />
+      <ConfigInput
+        label="App Token"
+        description="Reference a core secret or variable. The token itself is not stored on the alerter."
+        placeholder="[[GOTIFY_TOKEN]]"
+        value={params.token}
+        onChange={(token) => set({ ...params, token })}
+        disabled={disabled}
+      />
+    </div>
+  );
+};
+
+const PushoverEndpointConfig = ({
+  params,
+  set,
+  disabled,
+}: {
+  params: Types.PushoverAlerterEndpoint;
+  set: (params: Types.PushoverAlerterEndpoint) => void;
+  disabled: boolean;
+}) => {
+  return (
+    <div className="flex flex-col gap-4">
+      <ConfigInput
+        label="App Token"
+        description="Reference a core secret or variable. The token itself is not stored on the alerter."
+        placeholder="[[PUSHOVER_TOKEN]]"
+        value={params.token}
+        onChange={(token) => set({ ...params, token })}
+        disabled={disabled}
+      />
+      <ConfigInput
+        label="User Key"
+        description="The user or group key to deliver to."
+        value={params.user}
+        onChange={(user) => set({ ...params, user })}
+        disabled={disabled}
+      />
+      <ConfigInput
+        label="Device"
+        placeholder="Leave empty to send to all devices"
+        value={params.device}
+        onChange={(device) => set({ ...params, device })}
+        disabled={disabled}
+      />
+    </div>
+  );
+};
+
+const default_endpoint = (
+  type: Types.AlerterEndpoint["type"]
+): Types.AlerterEndpoint => {
+  if (type === "Email") {
+    return {
+      type,
+      params: {
+        host: "localhost",
+        port: 587,
+        security: Types.SmtpSecurity.StartTls,
+        username: "",
+        password: "",
+        from: "Komodo <komodo@localhost>",
+        to: [],
+      },
+    };
+  }
+  if (type === "Custom") {
+    return {
+      type,
+      params: {
+        url: default_url(type),
+        method: Types.CustomAlerterMethod.Post,
+        headers: "",
+        body: "",
+      },
+    };
+  }
+  if (type === "Ntfy") {
+    return {
+      type,
+      params: { url: "https://ntfy.sh", topic: "komodo", token: "" },
+    };
+  }
+  if (type === "Gotify") {
+    return {
+      type,
+      params: { url: "http://localhost:80", token: "" },
+    };
+  }
+  if (type === "Pushover") {
+    return { type, params: { token: "", user: "", device: "" } };
+  }
+  return { type, params: { url: default_url(type) } };
+};
+
 const default_url = (type: Types.AlerterEndpoint["type"]) => {
   return type === "Custom"
     ? "http://localhost:7000"
@@ -84,6 +84,17 @@ export const AlerterConfig = ({ id }: { id: string }) => {
               ),
             },
           },
+          {
+            label: "Renotify",
+            labelHidden: true,
+            components: {
+              renotify_minutes: {
+                boldLabel: true,
+                description:
+                  "Send open alerts again every N minutes until they are resolved or acknowledged. 0 to disable.",
+              },
+            },
+          },
         ],
       }}
     />
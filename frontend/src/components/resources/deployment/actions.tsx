@@ -7,6 +7,7 @@ import {
   RefreshCcw,
   Square,
   Download,
+  History,
 } from "lucide-react";
 import { useExecute, useRead } from "@lib/hooks";
 import { useEffect, useState } from "react";
@@ -170,6 +171,34 @@ export const PullDeployment = ({ id }: DeploymentId) => {
   );
 };
 
+export const RollbackDeployment = ({ id }: DeploymentId) => {
+  const deployment = useDeployment(id);
+  const { mutate: rollback, isPending } = useExecute("RollbackDeployment");
+  const deploying = useRead(
+    "GetDeploymentActionState",
+    { deployment: id },
+    { refetchInterval: 5_000 }
+  ).data?.deploying;
+  if (!deployment) return null;
+
+  if (deployment.info.state === Types.DeploymentState.NotDeployed) {
+    return null;
+  }
+
+  const pending = isPending || deploying;
+
+  return (
+    <ActionWithDialog
+      name={deployment.name}
+      title="Rollback"
+      icon={<History className="h-4 w-4" />}
+      onClick={() => rollback({ deployment: id })}
+      disabled={pending}
+      loading={pending}
+    />
+  );
+};
+
 export const RestartDeployment = ({ id }: DeploymentId) => {
   const deployment = useDeployment(id);
   const state = deployment?.info.state;
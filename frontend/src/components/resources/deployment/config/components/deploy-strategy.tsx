@@ -0,0 +1,49 @@
+import { ConfigItem } from "@components/config/util";
+import { Types } from "komodo_client";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@ui/select";
+import { object_keys } from "@lib/utils";
+
+export const DeployStrategySelector = ({
+  selected,
+  set,
+  disabled,
+}: {
+  selected: Types.DeployStrategy | undefined;
+  set: (input: Partial<Types.DeploymentConfig>) => void;
+  disabled: boolean;
+}) => (
+  <ConfigItem
+    label="Deploy Strategy"
+    boldLabel
+    description="Recreate removes the old container before starting the new one. Blue Green starts the new container first, and only removes the old one once the new one is healthy. The containers can't publish the same host ports."
+  >
+    <Select
+      value={selected || Types.DeployStrategy.Recreate}
+      onValueChange={(deploy_strategy: Types.DeployStrategy) =>
+        set({ deploy_strategy })
+      }
+      disabled={disabled}
+    >
+      <SelectTrigger className="w-[200px]" disabled={disabled}>
+        <SelectValue placeholder="Select Strategy" />
+      </SelectTrigger>
+      <SelectContent>
+        {object_keys(Types.DeployStrategy).map((strategy) => (
+          <SelectItem
+            key={strategy}
+            value={Types.DeployStrategy[strategy]}
+            className="cursor-pointer"
+          >
+            {strategy === "BlueGreen" ? "Blue Green" : strategy}
+          </SelectItem>
+        ))}
+      </SelectContent>
+    </Select>
+  </ConfigItem>
+);
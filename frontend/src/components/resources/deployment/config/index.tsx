@@ -10,6 +10,7 @@ import {
 } from "@components/config/util";
 import { ImageConfig } from "./components/image";
 import { RestartModeSelector } from "./components/restart";
+import { DeployStrategySelector } from "./components/deploy-strategy";
 import { NetworkModeSelector } from "./components/network";
 import { Config } from "@components/config";
 import { ResourceLink, ResourceSelector } from "@components/resources/common";
@@ -140,6 +141,17 @@ export const DeploymentConfig = ({
               },
             },
           },
+          {
+            label: "Replicas",
+            labelHidden: true,
+            components: {
+              replicas: {
+                label: "Replicas",
+                description:
+                  "The number of identical containers to run. With more than 1, the containers are named {name}-1, {name}-2, ..., and each replica offsets the host ports by its index - 1.",
+              },
+            },
+          },
           {
             label: "Network",
             labelHidden: true,
@@ -225,6 +237,53 @@ export const DeploymentConfig = ({
                   disabled={disabled}
                 />
               ),
+              restart_max_retries: (update.restart ?? config.restart) ===
+                Types.RestartMode.OnFailure && {
+                label: "Max Retries",
+                description: "The max restarts with the 'on-failure' mode.",
+              },
+            },
+          },
+          {
+            label: "Resource Limits",
+            description:
+              "Limit the resources the container can use. Zero / empty values are unlimited.",
+            components: {
+              cpus: {
+                label: "CPUs",
+                placeholder: "1.5",
+                description: "The number of CPUs the container can use.",
+              },
+              memory: {
+                label: "Memory",
+                placeholder: "512m",
+                description: "The memory limit, eg. 512m or 2g.",
+              },
+              memory_reservation: {
+                label: "Memory Reservation",
+                placeholder: "256m",
+                description:
+                  "The soft memory limit, enforced when the host is low on memory.",
+              },
+              pids_limit: {
+                label: "Pids Limit",
+                description: "The max number of processes in the container.",
+              },
+              ulimits: (value, set) => (
+                <ConfigItem
+                  label="Ulimits"
+                  boldLabel
+                  description="Given as name=soft:hard, eg. nofile=1024:2048."
+                >
+                  <InputList
+                    field="ulimits"
+                    values={value ?? []}
+                    set={set}
+                    disabled={disabled}
+                    placeholder="nofile=1024:2048"
+                  />
+                </ConfigItem>
+              ),
             },
           },
           {
@@ -238,6 +297,20 @@ export const DeploymentConfig = ({
               },
             },
           },
+          {
+            label: "Alerts",
+            components: {
+              send_alerts: {
+                description: "Send an alert when the container state changes unexpectedly.",
+              },
+              send_unhealthy_alerts: {
+                description: "Send an alert when the container healthcheck reports unhealthy.",
+              },
+              send_restart_loop_alerts: {
+                description: "Send an alert when the container is repeatedly restarted.",
+              },
+            },
+          },
         ],
         advanced: [
           {
@@ -273,6 +346,68 @@ export const DeploymentConfig = ({
               ),
             },
           },
+          {
+            label: "Healthcheck",
+            description:
+              "Configure the container healthcheck. Zero values use the image / docker defaults.",
+            components: {
+              healthcheck_command: {
+                label: "Command",
+                placeholder: "curl -f http://localhost/ || exit 1",
+                description:
+                  "Run inside the container to check health. Empty uses the image HEALTHCHECK.",
+              },
+              healthcheck_interval: {
+                label: "Interval",
+                description: "Seconds between healthchecks.",
+              },
+              healthcheck_timeout: {
+                label: "Timeout",
+                description: "Seconds before a healthcheck is considered failed.",
+              },
+              healthcheck_retries: {
+                label: "Retries",
+                description:
+                  "Consecutive failures before the container is unhealthy.",
+              },
+              healthcheck_start_period: {
+                label: "Start Period",
+                description:
+                  "Seconds before failures count towards the retries.",
+              },
+              healthy_timeout: {
+                label: "Deploy Healthy Timeout",
+                description:
+                  "Seconds Deploy waits for the container to be healthy (or stay running without a healthcheck) before failing. 0 (default) waits 60 seconds when a healthcheck command is set, and otherwise skips the check.",
+              },
+              auto_rollback: {
+                label: "Auto Rollback",
+                description:
+                  "Redeploy the last successful deploy if the deploy fails.",
+              },
+            },
+          },
+          {
+            label: "Deploy Strategy",
+            labelHidden: true,
+            components: {
+              deploy_strategy: (value, set) => (
+                <DeployStrategySelector
+                  selected={value}
+                  set={set}
+                  disabled={disabled}
+                />
+              ),
+              cutover_command: (update.deploy_strategy ??
+                config.deploy_strategy) ===
+                Types.DeployStrategy.BlueGreen && {
+                label: "Cutover Command",
+                placeholder: "nginx -s reload",
+                description:
+                  "Run on the host once the new container is healthy, before the old container is removed. Failure keeps the old container.",
+              },
+            },
+          },
           {
             label: "Labels",
             description: "Attach --labels to the container.",
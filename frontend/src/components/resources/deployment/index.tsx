@@ -11,6 +11,7 @@ import {
   RestartDeployment,
   PauseUnpauseDeployment,
   PullDeployment,
+  RollbackDeployment,
 } from "./actions";
 import { DeploymentLogs } from "./log";
 import {
@@ -278,6 +279,7 @@ export const DeploymentComponents: RequiredResourceComponents = {
     },
     DeployDeployment,
     PullDeployment,
+    RollbackDeployment,
     RestartDeployment,
     PauseUnpauseDeployment,
     StartStopDeployment,
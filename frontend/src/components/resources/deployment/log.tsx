@@ -10,6 +10,7 @@ import { useToast } from "@ui/use-toast";
 import { ToggleGroup, ToggleGroupItem } from "@ui/toggle-group";
 import { Switch } from "@ui/switch";
 import { Log, TailLengthSelector } from "@components/log";
+import { useLogStream } from "@lib/socket";
 
 export const DeploymentLogs = ({
   id,
@@ -43,6 +44,7 @@ const DeploymentLogsInner = ({
   const [invert, setInvert] = useState(false);
   const [search, setSearch] = useState("");
   const [poll, setPoll] = useLocalStorage("log-poll-v1", false);
+  const [follow, setFollow] = useState(false);
   const [timestamps, setTimestamps] = useLocalStorage(
     "log-timestamps-v1",
     false
@@ -66,7 +68,7 @@ const DeploymentLogsInner = ({
 
   const { Log, refetch, stderr } = terms.length
     ? SearchLogs(id, terms, invert, timestamps)
-    : NoSearchLogs(id, tail, timestamps, stream);
+    : NoSearchLogs(id, tail, timestamps, stream, follow);
 
   useEffect(() => {
     const interval = setInterval(() => {
@@ -143,6 +145,13 @@ const DeploymentLogsInner = ({
             <div className="text-muted-foreground text-sm">Poll</div>
             <Switch checked={poll} />
           </div>
+          <div
+            className="flex items-center gap-2 cursor-pointer"
+            onClick={() => setFollow((f) => !f)}
+          >
+            <div className="text-muted-foreground text-sm">Follow</div>
+            <Switch checked={follow} disabled={terms.length > 0} />
+          </div>
           <TailLengthSelector
             selected={tail}
             onSelect={set}
@@ -160,13 +169,24 @@ const NoSearchLogs = (
   id: string,
   tail: string,
   timestamps: boolean,
-  stream: string
+  stream: string,
+  follow: boolean
 ) => {
-  const { data: log, refetch } = useRead("GetDeploymentLog", {
+  const { data: polled, refetch } = useRead("GetDeploymentLog", {
     deployment: id,
     tail: Number(tail),
     timestamps,
   });
+  const followed = useLogStream(
+    follow
+      ? {
+          target: { type: "Deployment", params: { deployment: id } },
+          tail: Number(tail),
+          timestamps,
+        }
+      : undefined
+  );
+  const log = follow ? followed : polled;
   return {
     Log: (
       <div className="relative">
@@ -217,6 +217,51 @@ const ProcedureConfigInner = ({
           Add Stage
         </Button>
       </ConfigLayout>
+      <Section>
+        <Card>
+          <CardHeader>
+            <CardTitle>Schedule</CardTitle>
+            <CardDescription>
+              Run this Procedure on a CRON schedule.
+            </CardDescription>
+          </CardHeader>
+          <CardContent>
+            <div className="flex flex-col gap-4">
+              <ConfigSwitch
+                label="Schedule Enabled"
+                value={
+                  config.schedule_enabled ?? procedure.config?.schedule_enabled
+                }
+                disabled={disabled}
+                onChange={(schedule_enabled) =>
+                  setConfig({ ...config, schedule_enabled })
+                }
+              />
+              <ConfigInput
+                label="Schedule"
+                description="CRON expression: second (optional), minute, hour, day-of-month, month, day-of-week."
+                placeholder="0 0 4 * * *"
+                value={config.schedule ?? procedure.config?.schedule}
+                disabled={disabled}
+                onChange={(schedule) => setConfig({ ...config, schedule })}
+              />
+              <ConfigInput
+                label="Timezone"
+                description="TZ identifier to evaluate the schedule in. Uses the Core timezone if empty."
+                placeholder="America/New_York"
+                value={
+                  config.schedule_timezone ??
+                  procedure.config?.schedule_timezone
+                }
+                disabled={disabled}
+                onChange={(schedule_timezone) =>
+                  setConfig({ ...config, schedule_timezone })
+                }
+              />
+            </div>
+          </CardContent>
+        </Card>
+      </Section>
       <Section>
         <Card>
           <CardHeader>
@@ -787,6 +832,17 @@ const TARGET_COMPONENTS: ExecutionConfigs = {
       />
     ),
   },
+  RollbackDeployment: {
+    params: { deployment: "" },
+    Component: ({ params, setParams, disabled }) => (
+      <ResourceSelector
+        type="Deployment"
+        selected={params.deployment}
+        onSelect={(deployment) => setParams({ deployment })}
+        disabled={disabled}
+      />
+    ),
+  },
   StartDeployment: {
     params: { deployment: "" },
     Component: ({ params, setParams, disabled }) => (
@@ -1,7 +1,22 @@
-import { ActionWithDialog, ConfirmButton } from "@components/util";
-import { useExecute, useRead } from "@lib/hooks";
-import { Scissors } from "lucide-react";
-import { useServer } from ".";
+import {
+  ActionWithDialog,
+  ConfirmButton,
+  CopyButton,
+} from "@components/util";
+import { useExecute, useInvalidate, useRead, useWrite } from "@lib/hooks";
+import { Button } from "@ui/button";
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from "@ui/dialog";
+import { Input } from "@ui/input";
+import { Check, KeyRound, Scissors, ShieldOff, Trash } from "lucide-react";
+import { useState } from "react";
+import { useFullServer, useServer } from ".";
 import { has_minimum_permissions } from "@lib/utils";
 import { Types } from "komodo_client";
 
@@ -70,3 +85,111 @@ export const Prune = ({
     );
   }
 };
+
+export const UnpinCertificate = ({ id }: { id: string }) => {
+  const fingerprint = useFullServer(id)?.info?.certificate_fingerprint;
+  const invalidate = useInvalidate();
+  const { mutate, isPending } = useWrite("UnpinServerCertificate", {
+    onSuccess: () => invalidate(["GetServer"]),
+  });
+  const perms = useRead("GetPermissionLevel", {
+    target: { type: "Server", id },
+  }).data;
+  const canWrite = has_minimum_permissions(
+    perms,
+    Types.PermissionLevel.Write
+  );
+  if (!fingerprint) return;
+  return (
+    <ConfirmButton
+      title="Unpin Certificate"
+      icon={<ShieldOff className="w-4 h-4" />}
+      onClick={() => mutate({ server: id })}
+      loading={isPending}
+      disabled={!canWrite || isPending}
+    />
+  );
+};
+
+// Only a hash of the token is stored, so it is shown once here.
+export const EnrollmentToken = ({ id }: { id: string }) => {
+  const hasToken = !!useFullServer(id)?.info?.enrollment_token_hash;
+  const [token, setToken] = useState<string>();
+  const invalidate = useInvalidate();
+  const { mutate, isPending } = useWrite("CreateServerEnrollmentToken", {
+    onSuccess: ({ token }) => {
+      invalidate(["GetServer"], ["ListServers"]);
+      setToken(token);
+    },
+  });
+  const perms = useRead("GetPermissionLevel", {
+    target: { type: "Server", id },
+  }).data;
+  const canWrite = has_minimum_permissions(
+    perms,
+    Types.PermissionLevel.Write
+  );
+  return (
+    <>
+      <ConfirmButton
+        title={hasToken ? "Replace Enrollment Token" : "Create Enrollment Token"}
+        icon={<KeyRound className="w-4 h-4" />}
+        onClick={() => mutate({ server: id })}
+        loading={isPending}
+        disabled={!canWrite || isPending}
+      />
+      <Dialog
+        open={!!token}
+        onOpenChange={(open) => !open && setToken(undefined)}
+      >
+        <DialogContent>
+          <DialogHeader>
+            <DialogTitle>Enrollment Token</DialogTitle>
+            <DialogDescription>
+              Configure Periphery with this token and the Core address. It
+              will not be shown again.
+            </DialogDescription>
+          </DialogHeader>
+          <div className="py-8 flex items-center justify-between gap-4">
+            <Input value={token} disabled />
+            <CopyButton content={token} />
+          </div>
+          <DialogFooter className="flex justify-end">
+            <Button
+              variant="secondary"
+              className="gap-4"
+              onClick={() => setToken(undefined)}
+            >
+              Confirm <Check className="w-4" />
+            </Button>
+          </DialogFooter>
+        </DialogContent>
+      </Dialog>
+    </>
+  );
+};
+
+export const DeleteEnrollmentToken = ({ id }: { id: string }) => {
+  const hasToken = !!useFullServer(id)?.info?.enrollment_token_hash;
+  const invalidate = useInvalidate();
+  const { mutate, isPending } = useWrite("DeleteServerEnrollmentToken", {
+    onSuccess: () => invalidate(["GetServer"], ["ListServers"]),
+  });
+  const perms = useRead("GetPermissionLevel", {
+    target: { type: "Server", id },
+  }).data;
+  const canWrite = has_minimum_permissions(
+    perms,
+    Types.PermissionLevel.Write
+  );
+  if (!hasToken) return;
+  return (
+    <ConfirmButton
+      title="Delete Enrollment Token"
+      icon={<Trash className="w-4 h-4" />}
+      onClick={() => mutate({ server: id })}
+      loading={isPending}
+      disabled={!canWrite || isPending}
+    />
+  );
+};
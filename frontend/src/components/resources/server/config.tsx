@@ -99,6 +99,11 @@ export const ServerConfig = ({
                 description:
                   "Whether to store historical CPU, RAM, and disk usage.",
               },
+              container_stats_monitoring: {
+                label: "Container Stats Monitoring",
+                description:
+                  "Whether to store historical stats for the Deployment and Stack containers. Runs 'docker stats' every monitoring interval.",
+              },
             },
           },
           {
@@ -187,6 +192,44 @@ export const ServerConfig = ({
               },
             },
           },
+          {
+            label: "Network",
+            labelHidden: true,
+            components: {
+              send_network_alerts: {
+                // boldLabel: true,
+                description:
+                  "Send an alert if the network rate (for any interface) is above the configured thresholds.",
+              },
+              network_warning: {
+                description:
+                  "Send a 'Warning' alert if the network rate in Mbps, received or transmitted, is above this threshold. 0 to disable.",
+              },
+              network_critical: {
+                description:
+                  "Send a 'Critical' alert if the network rate in Mbps, received or transmitted, is above this threshold. 0 to disable.",
+              },
+            },
+          },
+          {
+            label: "Load",
+            labelHidden: true,
+            components: {
+              send_load_alerts: {
+                // boldLabel: true,
+                description:
+                  "Send an alert if the 5 minute load average is above the configured thresholds.",
+              },
+              load_warning: {
+                description:
+                  "Send a 'Warning' alert if the 5 minute load average is above this threshold. 0 to disable.",
+              },
+              load_critical: {
+                description:
+                  "Send a 'Critical' alert if the 5 minute load average is above this threshold. 0 to disable.",
+              },
+            },
+          },
         ],
       }}
     />
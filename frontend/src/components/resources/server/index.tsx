@@ -14,7 +14,12 @@ import {
   Square,
 } from "lucide-react";
 import { Section } from "@components/layouts";
-import { Prune } from "./actions";
+import {
+  DeleteEnrollmentToken,
+  EnrollmentToken,
+  Prune,
+  UnpinCertificate,
+} from "./actions";
 import {
   server_state_intention,
   stroke_color_class_by_intention,
@@ -440,6 +445,9 @@ export const ServerComponents: RequiredResourceComponents = {
   DangerZone: ({ id }) => (
     <>
       <RenameResource type="Server" id={id} />
+      <UnpinCertificate id={id} />
+      <EnrollmentToken id={id} />
+      <DeleteEnrollmentToken id={id} />
       <DeleteResource type="Server" id={id} />
     </>
   ),
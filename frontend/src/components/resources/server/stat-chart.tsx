@@ -9,7 +9,7 @@ import { convertTsMsToLocalUnixTsInMs } from "@lib/utils";
 import { useTheme } from "@ui/theme";
 import { fmt_utc_date } from "@lib/formatting";
 
-type StatType = "cpu" | "mem" | "disk";
+type StatType = "cpu" | "mem" | "disk" | "network_rx" | "network_tx" | "load";
 
 type StatDatapoint = { date: number; value: number };
 
@@ -96,18 +96,18 @@ export const InnerStatChart = ({
         getValue: (datum) => datum.value,
         elementType: "area",
         min: 0,
-        max: 100,
+        max: isPercentage(type) ? 100 : undefined,
         formatters: {
           tooltip: (value?: number) => (
             <div className="text-lg font-mono">
               {(value ?? 0) >= 10 ? value?.toFixed(2) : "0" + value?.toFixed(2)}
-              %
+              {getUnit(type)}
             </div>
           ),
         },
       },
     ],
-    []
+    [type]
   );
   return (
     <Chart
@@ -195,12 +195,31 @@ const getStat = (stat: Types.SystemStatsRecord, type: StatType) => {
   if (type === "cpu") return stat.cpu_perc || 0;
   if (type === "mem") return (100 * stat.mem_used_gb) / stat.mem_total_gb;
   if (type === "disk") return (100 * stat.disk_used_gb) / stat.disk_total_gb;
+  if (type === "network_rx")
+    return (stat.net_rx_bytes_per_sec || 0) / BYTES_PER_MEGABIT;
+  if (type === "network_tx")
+    return (stat.net_tx_bytes_per_sec || 0) / BYTES_PER_MEGABIT;
+  if (type === "load") return stat.load_average?.five || 0;
   return 0;
 };
 
+const BYTES_PER_MEGABIT = 125000;
+
+const isPercentage = (type: StatType) =>
+  type === "cpu" || type === "mem" || type === "disk";
+
+const getUnit = (type: StatType) => {
+  if (isPercentage(type)) return "%";
+  if (type === "network_rx" || type === "network_tx") return " Mbps";
+  return "";
+};
+
 const getColor = (type: StatType) => {
   if (type === "cpu") return hex_color_by_intention("Good");
   if (type === "mem") return hex_color_by_intention("Warning");
   if (type === "disk") return hex_color_by_intention("Neutral");
+  if (type === "network_rx") return hex_color_by_intention("Good");
+  if (type === "network_tx") return hex_color_by_intention("Warning");
+  if (type === "load") return hex_color_by_intention("Critical");
   return hex_color_by_intention("Unknown");
 };
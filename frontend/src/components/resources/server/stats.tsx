@@ -141,9 +141,74 @@ export const ServerStats = ({
               type="disk"
               className="w-full h-[250px]"
             />
+            <StatChart
+              server_id={id}
+              type="network_rx"
+              className="w-full h-[250px]"
+            />
+            <StatChart
+              server_id={id}
+              type="network_tx"
+              className="w-full h-[250px]"
+            />
+            <StatChart
+              server_id={id}
+              type="load"
+              className="w-full h-[250px]"
+            />
           </div>
         </Section>
 
+        <Section
+          title="Network"
+          actions={
+            <div className="flex gap-2 items-center">
+              <div className="text-muted-foreground">Load Average:</div>
+              {stats?.load_average?.one.toFixed(2)} /{" "}
+              {stats?.load_average?.five.toFixed(2)} /{" "}
+              {stats?.load_average?.fifteen.toFixed(2)}
+            </div>
+          }
+        >
+          <DataTable
+            sortDescFirst
+            tableKey="server-networks"
+            data={stats?.networks ?? []}
+            columns={[
+              {
+                header: "Interface",
+                accessorKey: "name",
+              },
+              {
+                accessorKey: "rx_bytes_per_sec",
+                header: ({ column }) => (
+                  <SortableHeader
+                    column={column}
+                    title="Received"
+                    sortDescFirst
+                  />
+                ),
+                cell: ({ row }) => (
+                  <>{fmt_mbps(row.original.rx_bytes_per_sec)} Mbps</>
+                ),
+              },
+              {
+                accessorKey: "tx_bytes_per_sec",
+                header: ({ column }) => (
+                  <SortableHeader
+                    column={column}
+                    title="Transmitted"
+                    sortDescFirst
+                  />
+                ),
+                cell: ({ row }) => (
+                  <>{fmt_mbps(row.original.tx_bytes_per_sec)} Mbps</>
+                ),
+              },
+            ]}
+          />
+        </Section>
+
         <Section
           title="Disks"
           actions={
@@ -368,3 +433,6 @@ const DISK = ({ stats }: { stats: Types.SystemStats | undefined }) => {
     </Card>
   );
 };
+
+const fmt_mbps = (bytes_per_sec: number) =>
+  (bytes_per_sec / 125000).toFixed(2);
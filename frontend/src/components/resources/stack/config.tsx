@@ -247,6 +247,20 @@ export const StackConfig = ({
         },
       },
     },
+    {
+      label: "Alerts",
+      components: {
+        send_alerts: {
+          description: "Send an alert when the stack state changes unexpectedly.",
+        },
+        send_unhealthy_alerts: {
+          description: "Send an alert when a service healthcheck reports unhealthy.",
+        },
+        send_restart_loop_alerts: {
+          description: "Send an alert when a service container is repeatedly restarted.",
+        },
+      },
+    },
     {
       label: "Links",
       labelHidden: true,
@@ -6,7 +6,7 @@ import { atom, useAtom } from "jotai";
 import { Circle } from "lucide-react";
 import { ReactNode, useCallback, useEffect, useState } from "react";
 import { cn } from "@lib/utils";
-import { AUTH_TOKEN_STORAGE_KEY } from "@main";
+import { AUTH_TOKEN_STORAGE_KEY, LOG_WS_URL } from "@main";
 import { ResourceComponents } from "@components/resources";
 import { UsableResource } from "@types";
 import { ResourceName } from "@components/resources/common";
@@ -351,3 +351,77 @@ const make_websocket = ({
 
   return ws;
 };
+
+/** Keep the followed log from growing without bound */
+const MAX_FOLLOWED_LINES = 5000;
+
+const append_line = (log: string, line: string) => {
+  const lines = log ? log.split("\n") : [];
+  lines.push(line);
+  return lines.slice(-MAX_FOLLOWED_LINES).join("\n");
+};
+
+/**
+ * Follows a container log over the log websocket.
+ * Pass undefined to stop following.
+ */
+export const useLogStream = (
+  query: Types.LogStreamQuery | undefined
+): Types.Log | undefined => {
+  const [log, setLog] = useState<Types.Log>();
+  const key = query && JSON.stringify(query);
+
+  useEffect(() => {
+    if (!key) {
+      setLog(undefined);
+      return;
+    }
+    const jwt = localStorage.getItem(AUTH_TOKEN_STORAGE_KEY);
+    if (!jwt) return;
+    setLog({
+      stage: "Follow Log",
+      command: "",
+      stdout: "",
+      stderr: "",
+      success: true,
+      start_ts: Date.now(),
+      end_ts: 0,
+    });
+    let logged_in = false;
+    const ws = new WebSocket(LOG_WS_URL);
+    ws.addEventListener("open", () => {
+      const msg: Types.WsLoginMessage = { type: "Jwt", params: { jwt } };
+      ws.send(JSON.stringify(msg));
+    });
+    ws.addEventListener("message", (e) => {
+      if (!logged_in) {
+        if (e.data === "LOGGED_IN") {
+          logged_in = true;
+          ws.send(key);
+        } else {
+          setLog((log) => log && { ...log, success: false, stderr: e.data });
+        }
+        return;
+      }
+      const msg = JSON.parse(e.data) as Types.LogStreamMessage;
+      setLog((log) => {
+        if (!log) return log;
+        switch (msg.type) {
+          case "Stdout":
+            return { ...log, stdout: append_line(log.stdout, msg.data) };
+          case "Stderr":
+            return { ...log, stderr: append_line(log.stderr, msg.data) };
+          case "Error":
+            return {
+              ...log,
+              success: false,
+              stderr: append_line(log.stderr, msg.data),
+            };
+        }
+      });
+    });
+    return () => ws.close();
+  }, [key]);
+
+  return log;
+};
@@ -18,6 +18,9 @@ export const KOMODO_BASE_URL =
 export const UPDATE_WS_URL =
   KOMODO_BASE_URL.replace("http", "ws") + "/ws/update";
 
+export const LOG_WS_URL =
+  KOMODO_BASE_URL.replace("http", "ws") + "/ws/log";
+
 const query_client = new QueryClient({
   defaultOptions: { queries: { retry: false } },
 });
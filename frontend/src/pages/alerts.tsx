@@ -26,12 +26,31 @@ import { ResourceComponents } from "@components/resources";
 import { ResourceSelector } from "@components/resources/common";
 
 const ALERT_TYPES_BY_RESOURCE: { [key: string]: Types.AlertData["type"][] } = {
-  Server: ["ServerUnreachable", "ServerCpu", "ServerMem", "ServerDisk"],
-  Stack: ["StackStateChange", "StackImageUpdateAvailable"],
-  Deployment: ["ContainerStateChange", "DeploymentImageUpdateAvailable"],
+  Server: [
+    "ServerUnreachable",
+    "ServerCpu",
+    "ServerMem",
+    "ServerDisk",
+    "ServerNetwork",
+    "ServerLoad",
+  ],
+  Stack: [
+    "StackStateChange",
+    "StackImageUpdateAvailable",
+    "ContainerUnhealthy",
+    "ContainerRestartLoop",
+  ],
+  Deployment: [
+    "ContainerStateChange",
+    "DeploymentImageUpdateAvailable",
+    "ContainerUnhealthy",
+    "ContainerRestartLoop",
+  ],
   Build: ["BuildFailed"],
   Repo: ["RepoBuildFailed"],
-  ResourceSync: ["ResourceSyncPendingUpdates"],
+  Procedure: ["ProcedureFailed"],
+  Action: ["ActionFailed"],
+  ResourceSync: ["ResourceSyncPendingUpdates", "ResourceSyncFailed"],
 };
 
 const FALLBACK_ALERT_TYPES = [
@@ -15,6 +15,7 @@ import { DataTable } from "@ui/data-table";
 import {
   ChevronLeft,
   Clapperboard,
+  Gauge,
   Info,
   Loader2,
   PlusCircle,
@@ -31,6 +32,7 @@ import { useEditPermissions } from "@pages/resource";
 import { ResourceNotifications } from "@pages/resource-notifications";
 import { MonacoEditor } from "@components/monaco";
 import { useState } from "react";
+import { format_size_bytes } from "@lib/formatting";
 
 export const ContainerPage = () => {
   const { type, id, container } = useParams() as {
@@ -207,6 +209,8 @@ const ContainerPageInner = ({
           />
         </Section>
 
+        <ContainerLimits host_config={container.HostConfig} />
+
         <DockerLabelsSection labels={container.Config?.Labels} />
 
         <Section
@@ -390,3 +394,69 @@ const NewDeploymentInner = ({
     />
   );
 };
+
+/** The effective limits, where zero / empty is unlimited. */
+const ContainerLimits = ({
+  host_config,
+}: {
+  host_config: Types.HostConfig | undefined;
+}) => {
+  if (!host_config) return null;
+  const restart = host_config.RestartPolicy;
+  return (
+    <Section title="Resource Limits" icon={<Gauge className="w-4 h-4" />}>
+      <DataTable
+        tableKey="container-limits"
+        data={[host_config]}
+        columns={[
+          {
+            header: "CPUs",
+            cell: ({ row }) =>
+              row.original.NanoCpus
+                ? row.original.NanoCpus / 1_000_000_000
+                : "Unlimited",
+          },
+          {
+            header: "Memory",
+            cell: ({ row }) =>
+              row.original.Memory
+                ? format_size_bytes(row.original.Memory)
+                : "Unlimited",
+          },
+          {
+            header: "Memory Reservation",
+            cell: ({ row }) =>
+              row.original.MemoryReservation
+                ? format_size_bytes(row.original.MemoryReservation)
+                : "None",
+          },
+          {
+            header: "Pids Limit",
+            cell: ({ row }) =>
+              row.original.PidsLimit && row.original.PidsLimit > 0
+                ? row.original.PidsLimit
+                : "Unlimited",
+          },
+          {
+            header: "Ulimits",
+            cell: ({ row }) =>
+              row.original.Ulimits?.length
+                ? row.original.Ulimits.map(
+                    (u) => `${u.Name}=${u.Soft}:${u.Hard}`
+                  ).join(", ")
+                : "Default",
+          },
+          {
+            header: "Restart",
+            cell: () =>
+              restart?.Name
+                ? restart.Name === Types.RestartPolicyNameEnum.OnFailure
+                  ? `${restart.Name}:${restart.MaximumRetryCount ?? 0}`
+                  : restart.Name
+                : "no",
+          },
+        ]}
+      />
+    </Section>
+  );
+};
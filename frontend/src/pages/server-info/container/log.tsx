@@ -1,5 +1,6 @@
 import { Section } from "@components/layouts";
 import { Log, TailLengthSelector } from "@components/log";
+import { useLogStream } from "@lib/socket";
 import { useLocalStorage, useRead } from "@lib/hooks";
 import { Types } from "komodo_client";
 import { Button } from "@ui/button";
@@ -25,6 +26,7 @@ export const ContainerLogs = ({
   const [invert, setInvert] = useState(false);
   const [search, setSearch] = useState("");
   const [poll, setPoll] = useLocalStorage("log-poll-v1", false);
+  const [follow, setFollow] = useState(false);
   const [timestamps, setTimestamps] = useLocalStorage(
     "log-timestamps-v1",
     false
@@ -48,7 +50,7 @@ export const ContainerLogs = ({
 
   const { Log, refetch, stderr } = terms.length
     ? SearchLogs(id, container_name, terms, invert, timestamps)
-    : NoSearchLogs(id, container_name, tail, timestamps, stream);
+    : NoSearchLogs(id, container_name, tail, timestamps, stream, follow);
 
   useEffect(() => {
     const interval = setInterval(() => {
@@ -125,6 +127,13 @@ export const ContainerLogs = ({
             <div className="text-muted-foreground text-sm">Poll</div>
             <Switch checked={poll} />
           </div>
+          <div
+            className="flex items-center gap-2 cursor-pointer"
+            onClick={() => setFollow((f) => !f)}
+          >
+            <div className="text-muted-foreground text-sm">Follow</div>
+            <Switch checked={follow} disabled={terms.length > 0} />
+          </div>
           <TailLengthSelector
             selected={tail}
             onSelect={set}
@@ -143,14 +152,25 @@ const NoSearchLogs = (
   container: string,
   tail: string,
   timestamps: boolean,
-  stream: string
+  stream: string,
+  follow: boolean
 ) => {
-  const { data: log, refetch } = useRead("GetContainerLog", {
+  const { data: polled, refetch } = useRead("GetContainerLog", {
     server: id,
     container,
     tail: Number(tail),
     timestamps,
   });
+  const followed = useLogStream(
+    follow
+      ? {
+          target: { type: "Container", params: { server: id, container } },
+          tail: Number(tail),
+          timestamps,
+        }
+      : undefined
+  );
+  const log = follow ? followed : polled;
   return {
     Log: (
       <div className="relative">
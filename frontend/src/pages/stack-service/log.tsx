@@ -9,6 +9,7 @@ import { useToast } from "@ui/use-toast";
 import { ToggleGroup, ToggleGroupItem } from "@ui/toggle-group";
 import { Switch } from "@ui/switch";
 import { Log, TailLengthSelector } from "@components/log";
+import { useLogStream } from "@lib/socket";
 
 export const StackServiceLogs = ({
   id,
@@ -45,6 +46,7 @@ const StackLogsInner = ({
   const [invert, setInvert] = useState(false);
   const [search, setSearch] = useState("");
   const [poll, setPoll] = useLocalStorage("log-poll-v1", false);
+  const [follow, setFollow] = useState(false);
   const [timestamps, setTimestamps] = useLocalStorage(
     "log-timestamps-v1",
     false
@@ -68,7 +70,7 @@ const StackLogsInner = ({
 
   const { Log, refetch, stderr } = terms.length
     ? SearchLogs(id, service, terms, invert, timestamps)
-    : NoSearchLogs(id, service, tail, timestamps, stream);
+    : NoSearchLogs(id, service, tail, timestamps, stream, follow);
 
   useEffect(() => {
     const interval = setInterval(() => {
@@ -147,6 +149,13 @@ const StackLogsInner = ({
             <div className="text-muted-foreground text-sm">Poll</div>
             <Switch checked={poll} />
           </div>
+          <div
+            className="flex items-center gap-2 cursor-pointer"
+            onClick={() => setFollow((f) => !f)}
+          >
+            <div className="text-muted-foreground text-sm">Follow</div>
+            <Switch checked={follow} disabled={terms.length > 0} />
+          </div>
           <TailLengthSelector
             selected={tail}
             onSelect={set}
@@ -165,14 +174,25 @@ const NoSearchLogs = (
   service: string,
   tail: string,
   timestamps: boolean,
-  stream: string
+  stream: string,
+  follow: boolean
 ) => {
-  const { data: log, refetch } = useRead("GetStackServiceLog", {
+  const { data: polled, refetch } = useRead("GetStackServiceLog", {
     stack: id,
     service,
     tail: Number(tail),
     timestamps,
   });
+  const followed = useLogStream(
+    follow
+      ? {
+          target: { type: "StackService", params: { stack: id, service } },
+          tail: Number(tail),
+          timestamps,
+        }
+      : undefined
+  );
+  const log = follow ? followed : polled;
   return {
     Log: (
       <div className="relative">
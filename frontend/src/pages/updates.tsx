@@ -280,6 +280,9 @@ const OPERATIONS_BY_RESOURCE: { [key: string]: Types.Operation[] } = {
     Types.Operation.UpdateServer,
     Types.Operation.DeleteServer,
     Types.Operation.RenameServer,
+    Types.Operation.UnpinServerCertificate,
+    Types.Operation.CreateServerEnrollmentToken,
+    Types.Operation.DeleteServerEnrollmentToken,
     Types.Operation.StartContainer,
     Types.Operation.RestartContainer,
     Types.Operation.PauseContainer,
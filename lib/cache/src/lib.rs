@@ -37,19 +37,21 @@ impl<Res: Default> Default for CacheEntry<Res> {
 
 impl<Res: Clone> CacheEntry<Res> {
   pub fn set(&mut self, res: &anyhow::Result<Res>, timestamp: i64) {
-    self.res = res
-      .as_ref()
-      .map(|res| res.clone())
-      .map_err(clone_anyhow_error);
+    self.res = clone_res(res);
     self.last_ts = timestamp;
   }
 
   pub fn clone_res(&self) -> anyhow::Result<Res> {
-    self
-      .res
-      .as_ref()
-      .map(|res| res.clone())
-      .map_err(clone_anyhow_error)
+    clone_res(&self.res)
+  }
+}
+
+fn clone_res<Res: Clone>(
+  res: &anyhow::Result<Res>,
+) -> anyhow::Result<Res> {
+  match res {
+    Ok(res) => Ok(res.clone()),
+    Err(e) => Err(clone_anyhow_error(e)),
   }
 }
 